@@ -1,3 +1,6 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 
 fn main() {
@@ -6,10 +9,200 @@ fn main() {
         .arg("+%Y-%m-%d %H:%M:%S")
         .output()
         .expect("Failed to get timestamp");
-    
+
     let timestamp = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    
+
     println!("cargo:rustc-env=BUILD_TIMESTAMP={}", timestamp);
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=src/");
+    println!("cargo:rerun-if-changed=colormaps/");
+
+    generate_colormap_luts();
+}
+
+/// Default number of entries in a generated lookup table
+const DEFAULT_LUT_SIZE: usize = 256;
+
+/// Turn every `colormaps/<name>.txt` into a `<NAME>_LUT: [[u8; 3]; N]` const
+/// in `$OUT_DIR/colormap_luts.rs`.
+///
+/// Each non-empty line that doesn't start with `#` is a color: either `r g b`
+/// (0-255, lines evenly spaced from 0 to 1) or `x r g b` with an explicit
+/// position `x` (0-1, increasing, from 0 to 1). Colors in between are
+/// interpolated linearly, so a 256-line file is copied as-is.
+///
+/// Directives may appear instead of or before the colors:
+/// - `size N`: number of table entries (default 256), e.g. 4096 for maps
+///   generated from a formula, or the number of colors of a qualitative map
+/// - `cubehelix start rotation hue gamma`: generate the table from Green's
+///   (2011) cubehelix formula instead of listing colors
+/// - `glasbey`: generate `size` maximally distinct colors (Glasbey et al. 2007)
+fn generate_colormap_luts() {
+    let mut paths: Vec<_> = fs::read_dir("colormaps")
+        .expect("Failed to read colormaps/")
+        .map(|entry| entry.expect("Failed to read colormaps/").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+
+    let mut code = String::from("// Generated by build.rs from colormaps/*.txt\n");
+    for path in &paths {
+        let stem = path.file_stem().unwrap().to_string_lossy();
+        let spec = parse_colormap(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        writeln!(code, "\npub const {}_LUT: [[u8; 3]; {}] = [", stem.to_uppercase(), spec.size).unwrap();
+        let colors: Vec<[u8; 3]> = match spec.source {
+            Source::Glasbey => glasbey(spec.size),
+            source => (0..spec.size)
+                .map(|i| {
+                    let t = i as f64 / (spec.size - 1) as f64;
+                    match &source {
+                        Source::Cubehelix(params) => cubehelix(*params, t),
+                        _ => interpolate(&spec.points, t),
+                    }
+                })
+                .collect(),
+        };
+        for [r, g, b] in colors {
+            writeln!(code, "    [{:3}, {:3}, {:3}],", r, g, b).unwrap();
+        }
+        code.push_str("];\n");
+    }
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("colormap_luts.rs"), code).expect("Failed to write colormap_luts.rs");
+}
+
+/// A parsed colormap file
+struct ColormapSpec {
+    size: usize,
+    source: Source,
+    /// Control points (position, color) of `Source::Points`
+    points: Vec<(f64, [f64; 3])>,
+}
+
+/// Where the colors of a colormap file come from
+enum Source {
+    /// Listed colors, interpolated
+    Points,
+    /// Cubehelix start, rotation, hue and gamma
+    Cubehelix([f64; 4]),
+    /// Greedily chosen distinct colors
+    Glasbey,
+}
+
+/// Read a colormap file
+fn parse_colormap(path: &Path) -> Result<ColormapSpec, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut spec = ColormapSpec { size: DEFAULT_LUT_SIZE, source: Source::Points, points: Vec::new() };
+    let mut rows = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let mut words = line.split_whitespace();
+        let first = words.next().unwrap();
+        let numbers = |words: std::str::SplitWhitespace| {
+            words
+                .map(|v| v.parse::<f64>().map_err(|_| format!("Invalid number \"{}\"", v)))
+                .collect::<Result<Vec<f64>, String>>()
+        };
+        match first {
+            "size" => {
+                spec.size = match numbers(words)?.as_slice() {
+                    &[n] if n >= 2.0 && n.fract() == 0.0 => n as usize,
+                    _ => return Err("Expected \"size N\" with N at least 2".to_string()),
+                }
+            }
+            "cubehelix" => {
+                spec.source = match numbers(words)?.as_slice() {
+                    &[start, rotation, hue, gamma] => Source::Cubehelix([start, rotation, hue, gamma]),
+                    _ => return Err("Expected \"cubehelix start rotation hue gamma\"".to_string()),
+                }
+            }
+            "glasbey" => spec.source = Source::Glasbey,
+            _ => rows.push(numbers(line.split_whitespace())?),
+        }
+    }
+    if !matches!(spec.source, Source::Points) {
+        return if rows.is_empty() { Ok(spec) } else { Err("Colors and a generated colormap are exclusive".to_string()) };
+    }
+    if rows.len() < 2 {
+        return Err("A colormap needs at least 2 colors".to_string());
+    }
+
+    let explicit = rows[0].len() == 4;
+    let points = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| match (explicit, row.as_slice()) {
+            (false, &[r, g, b]) => Ok((i as f64 / (rows.len() - 1) as f64, [r, g, b])),
+            (true, &[x, r, g, b]) => Ok((x, [r, g, b])),
+            _ => Err(format!("Color {}: expected {} numbers", i + 1, if explicit { 4 } else { 3 })),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let in_order = points.windows(2).all(|w| w[0].0 <= w[1].0);
+    if !in_order || points[0].0 != 0.0 || points[points.len() - 1].0 != 1.0 {
+        return Err("Positions must increase from 0 to 1".to_string());
+    }
+    if points.iter().flat_map(|(_, rgb)| rgb).any(|v| !(0.0..=255.0).contains(v)) {
+        return Err("Color values must be 0-255".to_string());
+    }
+    spec.points = points;
+    Ok(spec)
+}
+
+/// Color at position `t` (0-1) of a cubehelix colormap (Green 2011, as in matplotlib)
+fn cubehelix([start, rotation, hue, gamma]: [f64; 4], t: f64) -> [u8; 3] {
+    let tg = t.powf(gamma);
+    let amplitude = hue * tg * (1.0 - tg) / 2.0;
+    let phi = 2.0 * std::f64::consts::PI * (start / 3.0 + rotation * t);
+    let (cos, sin) = (phi.cos(), phi.sin());
+    let channel = |p0: f64, p1: f64| ((tg + amplitude * (p0 * cos + p1 * sin)).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(-0.14861, 1.78277), channel(-0.29227, -0.90649), channel(1.97294, 0.0)]
+}
+
+/// `count` colors that are each as far as possible (in OKLab) from the ones
+/// before them and from black and white, picked from a grid of sRGB colors
+fn glasbey(count: usize) -> Vec<[u8; 3]> {
+    const STEPS: usize = 24;
+    let level = |i: usize| (i * 255 / (STEPS - 1)) as u8;
+    let candidates: Vec<[u8; 3]> =
+        (0..STEPS.pow(3)).map(|i| [level(i / (STEPS * STEPS)), level(i / STEPS % STEPS), level(i % STEPS)]).collect();
+    let lab: Vec<[f64; 3]> = candidates.iter().map(|&rgb| oklab(rgb)).collect();
+    let distance = |a: [f64; 3], b: [f64; 3]| (0..3).map(|c| (a[c] - b[c]).powi(2)).sum::<f64>();
+
+    // Distance from each candidate to the nearest color taken so far
+    let (black, white) = (oklab([0, 0, 0]), oklab([255, 255, 255]));
+    let mut nearest: Vec<f64> = lab.iter().map(|&c| distance(c, black).min(distance(c, white))).collect();
+    let mut colors = Vec::with_capacity(count);
+    for _ in 0..count {
+        let best = (0..candidates.len()).max_by(|&a, &b| nearest[a].total_cmp(&nearest[b])).unwrap();
+        colors.push(candidates[best]);
+        for (d, &c) in nearest.iter_mut().zip(&lab) {
+            *d = d.min(distance(c, lab[best]));
+        }
+    }
+    colors
+}
+
+/// sRGB (0-255) to OKLab (Björn Ottosson, 2020)
+fn oklab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(|v| {
+        let c = v as f64 / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    });
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// Color at position `t` (0-1), interpolated linearly between control points
+fn interpolate(points: &[(f64, [f64; 3])], t: f64) -> [u8; 3] {
+    let i = points[1..].iter().position(|&(x, _)| x >= t).unwrap_or(points.len() - 2);
+    let ((x0, c0), (x1, c1)) = (points[i], points[i + 1]);
+    let f = if x1 > x0 { ((t - x0) / (x1 - x0)).clamp(0.0, 1.0) } else { 1.0 };
+    [0, 1, 2].map(|c| (c0[c] + (c1[c] - c0[c]) * f).round() as u8)
 }
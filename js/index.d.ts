@@ -14,9 +14,47 @@ export type ArrayType =
   | 'Uint32Array'
   | 'BigInt64Array'
   | 'BigUint64Array'
+  | 'Float16Array'
   | 'Float32Array'
   | 'Float64Array';
 
+/**
+ * What happens to the display limits when new data arrives:
+ * recompute from every frame, never change, recompute unless the user
+ * changed them, or follow a central percentile range of every frame.
+ */
+export type LimitsPolicy = 'auto' | 'keep' | 'keep-if-modified' | 'percentile';
+
+/**
+ * How automatic limits are computed: full finite range, central percentile
+ * range, IRAF/DS9 ZScale, or median ± k·MAD.
+ */
+export type ScaleMode = 'minmax' | 'percentile' | 'zscale' | 'mad';
+
+/**
+ * Pixels that can get a fixed color instead of a colormap color: NaN/Inf
+ * values, values below the lower limit, and values above the upper limit.
+ */
+export type SpecialColor = 'bad' | 'under' | 'over';
+
+/**
+ * Color space in which colors between colormap stops are interpolated.
+ */
+export type ColorSpace = 'srgb' | 'linear' | 'oklab';
+
+/**
+ * How values are mapped to a colormap: sequential and diverging colormaps
+ * clamp, cyclic ones wrap around, and qualitative ones give each integer label
+ * its own color.
+ */
+export type ColormapKind = 'sequential' | 'diverging' | 'cyclic' | 'qualitative';
+
+/**
+ * A colormap stop: an `[r, g, b]` color (0-255), or a `[position, [r, g, b]]`
+ * control point with position 0-1.
+ */
+export type ColormapStop = [number, number, number] | [number, [number, number, number]];
+
 /**
  * Create a new viewer instance in the specified container.
  *
@@ -34,15 +72,371 @@ export function createViewer(containerId: string): Promise<void>;
  * @param buffer - The raw pixel data.
  * @param width - Image width in pixels.
  * @param height - Image height in pixels.
- * @param arrayType - JavaScript TypedArray type name for interpreting the buffer.
- * @throws If the viewer is not found or data is invalid.
+ * @param arrayType - Element type of the buffer: a TypedArray name, a Rust-style
+ *                    type ("f32", "u16", "f16", "bf16", "c64") or a NumPy dtype with
+ *                    optional byte order prefix ("f4", "<f2", ">i2", "|u1", "uint16", "<c8").
+ * @throws If the viewer is not found, the type is unknown, or the buffer length
+ *         does not match width * height elements.
  */
 export function setImageData(
   containerId: string,
   buffer: ArrayBuffer,
   width: number,
   height: number,
-  arrayType: ArrayType
+  arrayType: ArrayType | string
+): void;
+
+/**
+ * Set image data for a viewer from a 2D view into a larger buffer.
+ *
+ * Strides are in bytes, following the NumPy convention. For example, a
+ * Fortran-order (column-major) image uses rowStride = itemsize and
+ * colStride = height * itemsize.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - Buffer containing the pixel data (and possibly other data).
+ * @param width - Image width in pixels.
+ * @param height - Image height in pixels.
+ * @param arrayType - Element type (same forms as setImageData).
+ * @param byteOffset - Offset in bytes of the first pixel.
+ * @param rowStride - Distance in bytes between the starts of consecutive rows.
+ * @param colStride - Distance in bytes between consecutive pixels in a row.
+ * @throws If the viewer is not found, the type is unknown, or the view extends
+ *         past the end of the buffer.
+ */
+export function setImageDataStrided(
+  containerId: string,
+  buffer: ArrayBuffer,
+  width: number,
+  height: number,
+  arrayType: ArrayType | string,
+  byteOffset: number,
+  rowStride: number,
+  colStride: number
+): void;
+
+/**
+ * Overwrite a rectangular region of the current image, e.g. for chunked live readout.
+ * Only the rows touched by the region are rescanned for min/max, and only the region
+ * of the texture is redrawn unless the limits change.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param x - Column of the region's first pixel.
+ * @param y - Row of the region's first pixel.
+ * @param width - Region width in pixels.
+ * @param height - Region height in pixels.
+ * @param buffer - The region's pixels in row-major order.
+ * @param arrayType - Element type of the buffer; must match the current image.
+ * @param keepLimits - If true, keep the current display limits; if false, re-autoscale (default: the limits policy decides).
+ * @throws If the viewer is not found, no 2D scalar image is loaded, the type does not match, or the region does not fit.
+ */
+export function updateRegion(
+  containerId: string,
+  x: number,
+  y: number,
+  width: number,
+  height: number,
+  buffer: ArrayBuffer,
+  arrayType: ArrayType | string,
+  keepLimits?: boolean
+): void;
+
+/**
+ * Set a color (RGB or RGBA) image, displayed directly without stretch or
+ * colormap. Pan, zoom, rotation, hover and click callbacks work as usual.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - The channel data.
+ * @param width - Image width in pixels.
+ * @param height - Image height in pixels.
+ * @param arrayType - Channel type: "u8" (0-255) or a float type such as "f32" (0-1).
+ * @param channels - Number of channels: 3 (RGB) or 4 (RGBA).
+ * @param layout - "interleaved" (RGBRGB...) or "planar" (RRR...GGG...BBB...).
+ * @throws If the viewer is not found, the type or layout is unknown, or the buffer length does not match.
+ */
+export function setRgbData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  width: number,
+  height: number,
+  arrayType: ArrayType | string,
+  channels: 3 | 4,
+  layout: 'interleaved' | 'planar'
+): void;
+
+/**
+ * Set a cube of same-shaped 2D planes, browsed one slice at a time
+ * (slider, step buttons or left/right arrow keys).
+ * The current slice index and display settings are kept.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - Consecutive row-major planes (C order depth x height x width).
+ * @param depth - Number of planes.
+ * @param width - Plane width in pixels.
+ * @param height - Plane height in pixels.
+ * @param arrayType - Element type of the buffer (same forms as setImageData).
+ * @throws If the viewer is not found, the type is unknown, or the buffer size does not match.
+ */
+export function setCubeData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  depth: number,
+  width: number,
+  height: number,
+  arrayType: ArrayType | string
+): void;
+
+/**
+ * Set an N-dimensional array, displayed as 2D planes.
+ * The last two axes are displayed (see setDisplayAxes) and every other axis gets an
+ * index slider. Axis indices and display settings are kept when an array with the
+ * same number of axes is loaded.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - The array in C order (last axis varies fastest).
+ * @param shape - Length of every axis, e.g. [time, wavelength, height, width].
+ * @param arrayType - Element type of the buffer (same forms as setImageData).
+ * @throws If the viewer is not found, the type is unknown, the shape has fewer than 2 axes, or the buffer size does not match.
+ */
+export function setArrayData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  shape: number[] | Uint32Array,
+  arrayType: ArrayType | string
+): void;
+
+/**
+ * Get the index of the displayed cube slice.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The slice index.
+ */
+export function getSlice(containerId: string): number;
+
+/**
+ * Display another cube slice (clamped to the cube depth).
+ * Has no effect unless a cube is loaded.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param index - The slice index.
+ */
+export function setSlice(containerId: string, index: number): void;
+
+/**
+ * Get the shape of the loaded cube or N-D array.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Length of every axis (empty if none is loaded).
+ */
+export function getShape(containerId: string): Uint32Array;
+
+/**
+ * Get the index along every axis of the loaded cube or N-D array.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns One index per axis; entries of the two displayed axes are 0.
+ */
+export function getAxisIndex(containerId: string): Uint32Array;
+
+/**
+ * Move along a non-displayed axis (index clamped to the axis length).
+ * Has no effect for the displayed axes or when no cube is loaded.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param axis - The axis to move along.
+ * @param index - The index along that axis.
+ */
+export function setAxisIndex(
+  containerId: string,
+  axis: number,
+  index: number
+): void;
+
+/**
+ * Get the axes shown along image rows and columns.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns [yAxis, xAxis], or empty if no cube is loaded.
+ */
+export function getDisplayAxes(containerId: string): Uint32Array;
+
+/**
+ * Choose the axes shown along image rows and columns.
+ * Limits are recomputed; pan and mask are reset if the plane shape changes.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param yAxis - Axis shown along image rows.
+ * @param xAxis - Axis shown along image columns.
+ * @throws If the viewer is not found, no cube is loaded, or the axes are equal or out of range.
+ */
+export function setDisplayAxes(
+  containerId: string,
+  yAxis: number,
+  xAxis: number
+): void;
+
+/**
+ * Set one label per axis, shown next to the index sliders and in the hover readout.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param labels - One label per axis, or an empty list to restore the default "axis N" labels.
+ * @throws If the viewer is not found, no cube is loaded, or the number of labels does not match.
+ */
+export function setAxisLabels(containerId: string, labels: string[]): void;
+
+/**
+ * Get the number of cube slices.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The cube depth, or 0 if no cube is loaded.
+ */
+export function getNumSlices(containerId: string): number;
+
+/**
+ * Get where cube scale limits come from.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns 'slice' (the displayed slice) or 'global' (the whole cube).
+ */
+export function getSliceLimits(containerId: string): string;
+
+/**
+ * Set where cube scale limits come from. Limits are recomputed when this changes.
+ * With 'slice', limits follow each slice unless they have been modified.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param mode - 'slice' (the displayed slice) or 'global' (the whole cube).
+ */
+export function setSliceLimits(
+  containerId: string,
+  mode: 'slice' | 'global'
+): void;
+
+/**
+ * Get whether the cube is being played as a movie.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns True while playback is running.
+ */
+export function getPlaying(containerId: string): boolean;
+
+/**
+ * Start or stop cube playback (also toggled with the space key).
+ * Has no effect unless a cube is loaded.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param playing - True to play, false to pause.
+ */
+export function setPlaying(containerId: string, playing: boolean): void;
+
+/**
+ * Get the cube playback rate.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Frames per second.
+ */
+export function getPlaybackFps(containerId: string): number;
+
+/**
+ * Set the cube playback rate.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param fps - Frames per second (clamped to 0.1-60).
+ */
+export function setPlaybackFps(containerId: string, fps: number): void;
+
+/**
+ * Get what playback does at the ends of the cube.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns 'loop' or 'bounce'.
+ */
+export function getPlaybackMode(containerId: string): string;
+
+/**
+ * Set what playback does at the ends of the cube.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param mode - 'loop' (wrap to the first frame) or 'bounce' (reverse direction).
+ */
+export function setPlaybackMode(
+  containerId: string,
+  mode: 'loop' | 'bounce'
+): void;
+
+/**
+ * Set a three-channel composite from separate scalar arrays.
+ * Each channel has its own display limits, stretch and contrast/bias before the
+ * channels are combined into one color image.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param red - Pixel data of the red channel.
+ * @param green - Pixel data of the green channel.
+ * @param blue - Pixel data of the blue channel.
+ * @param width - Image width in pixels.
+ * @param height - Image height in pixels.
+ * @param arrayType - Element type of all three buffers (same forms as setImageData).
+ * @throws If the viewer is not found, the type is unknown, or a buffer size does not match.
+ */
+export function setCompositeData(
+  containerId: string,
+  red: ArrayBuffer,
+  green: ArrayBuffer,
+  blue: ArrayBuffer,
+  width: number,
+  height: number,
+  arrayType: ArrayType | string
+): void;
+
+/**
+ * Get the composite channel that stretch, contrast/bias and limit setters act on.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns 0 (red), 1 (green) or 2 (blue).
+ */
+export function getActiveChannel(containerId: string): number;
+
+/**
+ * Select the composite channel that stretch, contrast/bias and limit setters act on.
+ * Has no effect unless a composite is loaded.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param channel - 0 (red), 1 (green) or 2 (blue).
+ */
+export function setActiveChannel(containerId: string, channel: number): void;
+
+/**
+ * Set image data for a viewer from the contents of a FITS file.
+ *
+ * The HDU must be the primary HDU or an IMAGE extension. BSCALE/BZERO are
+ * applied and BLANK pixels are shown as NaN.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - The complete FITS file.
+ * @param hduIndex - Index of the HDU to display (default 0, the primary HDU).
+ * @throws If the viewer is not found or the HDU cannot be read as an image.
+ */
+export function setFitsData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  hduIndex?: number
+): void;
+
+/**
+ * Set image data for a viewer from a NumPy .npy file or .npz archive.
+ *
+ * The dtype, byte order, memory order and shape are read from the file header.
+ * For arrays with more than two dimensions, the first 2D plane is displayed.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - The complete .npy or .npz file.
+ * @param arrayName - For .npz archives, the array to display (default: the first one).
+ * @throws If the viewer is not found or the file cannot be read.
+ */
+export function setNpyData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  arrayName?: string
 ): void;
 
 /**
@@ -103,7 +497,7 @@ export function setBias(containerId: string, bias: number): void;
  * Get current stretch mode for a viewer.
  *
  * @param containerId - The ID of the container (viewer instance).
- * @returns Stretch mode: "linear", "log", or "symmetric".
+ * @returns Stretch mode: "linear", "log", "sqrt", "squared", "asinh", "power", "sinh", "histeq", or "symmetric".
  */
 export function getStretchMode(containerId: string): string;
 
@@ -111,10 +505,78 @@ export function getStretchMode(containerId: string): string;
  * Set stretch mode for a viewer.
  *
  * @param containerId - The ID of the container (viewer instance).
- * @param mode - Stretch mode: "linear", "log", or "symmetric".
+ * @param mode - Stretch mode: "linear", "log", "sqrt", "squared", "asinh", "power", "sinh", "histeq", or "symmetric".
  */
 export function setStretchMode(containerId: string, mode: string): void;
 
+/**
+ * Get the exponent of the log stretch.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The exponent.
+ */
+export function getLogExponent(containerId: string): number;
+
+/**
+ * Set the exponent of the log stretch. The default 1000 suits optical
+ * images; smaller values suit data with less dynamic range.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param exponent - The exponent (must be greater than 1).
+ */
+export function setLogExponent(containerId: string, exponent: number): void;
+
+/**
+ * Get the softening of the asinh stretch.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The softening.
+ */
+export function getAsinhSoftening(containerId: string): number;
+
+/**
+ * Set the softening of the asinh stretch: values well below it (as a
+ * fraction of the display range) are stretched linearly, values above it
+ * logarithmically. Default 0.1.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param softening - The softening (must be positive).
+ */
+export function setAsinhSoftening(containerId: string, softening: number): void;
+
+/**
+ * Get the exponent of the power stretch.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The exponent.
+ */
+export function getPowerExponent(containerId: string): number;
+
+/**
+ * Set the exponent of the power stretch. Default 3.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param exponent - The exponent (must be positive).
+ */
+export function setPowerExponent(containerId: string, exponent: number): void;
+
+/**
+ * Get which quantity of complex data is displayed.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Component: "magnitude", "phase", "real", "imag", or "logpower".
+ */
+export function getComplexComponent(containerId: string): string;
+
+/**
+ * Set which quantity of complex (c64/c128) data is displayed.
+ * Phase uses a cyclic colormap and a fixed -π..π range.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param component - Component: "magnitude", "phase", "real", "imag", or "logpower".
+ */
+export function setComplexComponent(containerId: string, component: string): void;
+
 /**
  * Get visible image bounds in pixel coordinates.
  *
@@ -148,6 +610,48 @@ export function setViewBounds(
  */
 export function getColormap(containerId: string): string;
 
+/**
+ * Get the kind of the current colormap.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns "sequential", "diverging", "cyclic" or "qualitative".
+ */
+export function getColormapKind(containerId: string): ColormapKind;
+
+/**
+ * Set the colormap by name (ignoring case), built-in or registered with
+ * `registerColormap`.
+ * Diverging colormaps are used in symmetric mode, cyclic ones for complex phase.
+ * Qualitative colormaps (e.g., "Tab10", "Glasbey") color each value as an
+ * integer label, without stretching, and show a legend instead of the colorbar.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param name - Colormap name (e.g., "Viridis", "Balance", "Twilight").
+ * @throws If the viewer is not found, or if the colormap is unknown.
+ */
+export function setColormap(containerId: string, name: string): void;
+
+/**
+ * Register a colormap for all viewers, replacing any registered colormap of
+ * the same name. It shows up in the colormap picker and works with
+ * `setColormap`.
+ *
+ * @param name - Display name; must not be that of a built-in colormap.
+ * @param stops - Colors evenly spaced from 0 to 1 (such as a 256-entry lookup
+ *   table), or control points with positions increasing from 0 to 1.
+ * @param options - `kind` (default "sequential") decides in which modes the
+ *   colormap is offered and how values map to it, with qualitative stops used
+ *   as listed; `diverging: true` is shorthand for `kind: 'diverging'`;
+ *   `colorSpace` (default "srgb") is where colors between stops are
+ *   interpolated.
+ * @throws If the stops, name, kind or color space are invalid.
+ */
+export function registerColormap(
+  name: string,
+  stops: ColormapStop[],
+  options?: { kind?: ColormapKind; diverging?: boolean; colorSpace?: ColorSpace }
+): Promise<void>;
+
 /**
  * Get whether the colormap is reversed.
  *
@@ -156,6 +660,34 @@ export function getColormap(containerId: string): string;
  */
 export function getColormapReversed(containerId: string): boolean;
 
+/**
+ * Set whether the colormap is reversed.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param reversed - True to reverse the colormap.
+ */
+export function setColormapReversed(
+  containerId: string,
+  reversed: boolean
+): void;
+
+/**
+ * Get the number of discrete colormap levels.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Number of levels, or 0 when the colormap is continuous.
+ */
+export function getColormapLevels(containerId: string): number;
+
+/**
+ * Quantize the colormap into flat levels for contour-like displays, or make it
+ * continuous again. Values other than 0 or 2-256 are ignored.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param levels - Number of levels (2-256), or 0 for a continuous colormap.
+ */
+export function setColormapLevels(containerId: string, levels: number): void;
+
 /**
  * Get the image value range (vmin, vmax).
  *
@@ -164,6 +696,94 @@ export function getColormapReversed(containerId: string): boolean;
  */
 export function getValueRange(containerId: string): [number, number];
 
+/**
+ * Set the value range (vmin, vmax) for display scaling.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param vmin - The minimum display value.
+ * @param vmax - The maximum display value.
+ */
+export function setValueRange(containerId: string, vmin: number, vmax: number): void;
+
+/**
+ * Get what happens to the display limits when new data arrives.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns "auto", "keep", "keep-if-modified" or "percentile".
+ */
+export function getLimitsPolicy(containerId: string): LimitsPolicy;
+
+/**
+ * Set what happens to the display limits when new data arrives
+ * (setImageData, setCubeData, setArrayData and updateRegion):
+ * "auto" recomputes them from every frame, "keep" never changes them,
+ * "keep-if-modified" recomputes them unless the user has changed them, and
+ * "percentile" follows the central percentile range of every frame
+ * (see setLimitsPercentile). Invalid policies are ignored.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param policy - The limits policy.
+ */
+export function setLimitsPolicy(
+  containerId: string,
+  policy: LimitsPolicy | string
+): void;
+
+/**
+ * Get how automatic limits are computed.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns "minmax", "percentile", "zscale" or "mad".
+ */
+export function getScaleMode(containerId: string): ScaleMode;
+
+/**
+ * Set how automatic limits are computed: "minmax" (full finite range),
+ * "percentile" (central percentile range, see setLimitsPercentile),
+ * "zscale" (IRAF/DS9 ZScale) or "mad" (median ± k·MAD, see setMadFactor).
+ * Limits are recomputed when this changes. Invalid modes are ignored.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param mode - The scale mode.
+ */
+export function setScaleMode(
+  containerId: string,
+  mode: ScaleMode | string
+): void;
+
+/**
+ * Get the central percentile of the data spanned by percentile limits.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The percentile (0-100).
+ */
+export function getLimitsPercentile(containerId: string): number;
+
+/**
+ * Set the central percentile of the data spanned by percentile limits
+ * (scale mode or limits policy "percentile"). The default 99.5 clips the lowest and highest 0.25% of the values.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param percent - The percentile (0-100).
+ */
+export function setLimitsPercentile(containerId: string, percent: number): void;
+
+/**
+ * Get k of the "mad" scale mode (median ± k·MAD).
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns The factor k.
+ */
+export function getMadFactor(containerId: string): number;
+
+/**
+ * Set k of the "mad" scale mode (median ± k·MAD). Default 3.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param k - The factor k (must be positive).
+ */
+export function setMadFactor(containerId: string, k: number): void;
+
 /**
  * Get current rotation angle in degrees (counter-clockwise).
  *
@@ -213,6 +833,160 @@ export function getShowPivotMarker(containerId: string): boolean;
  */
 export function setShowPivotMarker(containerId: string, show: boolean): void;
 
+/**
+ * Set a mask layer, drawn as a translucent color overlay on the image.
+ * The mask must have the same shape as the image; nonzero values are masked.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param buffer - One byte per pixel (uint8 or bool), row-major.
+ * @param width - Mask width in pixels (must match the image).
+ * @param height - Mask height in pixels (must match the image).
+ * @throws If the viewer is not found, or the mask shape does not match the image.
+ */
+export function setMaskData(
+  containerId: string,
+  buffer: ArrayBuffer,
+  width: number,
+  height: number
+): void;
+
+/**
+ * Remove the mask layer.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ */
+export function clearMask(containerId: string): void;
+
+/**
+ * Get whether the mask overlay is shown.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns True if the mask overlay is shown.
+ */
+export function getShowMask(containerId: string): boolean;
+
+/**
+ * Set whether the mask overlay is shown.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param show - True to show the mask overlay.
+ */
+export function setShowMask(containerId: string, show: boolean): void;
+
+/**
+ * Get the mask overlay color.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Color as [r, g, b] (0-255).
+ */
+export function getMaskColor(containerId: string): Uint8Array;
+
+/**
+ * Set the mask overlay color.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param r - Red (0-255).
+ * @param g - Green (0-255).
+ * @param b - Blue (0-255).
+ */
+export function setMaskColor(containerId: string, r: number, g: number, b: number): void;
+
+/**
+ * Get the mask overlay opacity.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns Opacity (0-1).
+ */
+export function getMaskOpacity(containerId: string): number;
+
+/**
+ * Set the mask overlay opacity.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param opacity - Opacity (0-1).
+ */
+export function setMaskOpacity(containerId: string, opacity: number): void;
+
+/**
+ * Get whether masked pixels are ignored when computing min/max.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @returns True if masked pixels are excluded.
+ */
+export function getMaskExcludesLimits(containerId: string): boolean;
+
+/**
+ * Set whether masked pixels are ignored (like NaN) when computing the
+ * auto-scaling min/max. Limits are recomputed when this changes.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param exclude - True to exclude masked pixels.
+ */
+export function setMaskExcludesLimits(containerId: string, exclude: boolean): void;
+
+/**
+ * Get the color of NaN/Inf ("bad"), below-range ("under") or above-range
+ * ("over") pixels.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param which - Which special color: "bad", "under" or "over".
+ * @returns Color as [r, g, b] (0-255).
+ * @throws If the viewer is not found, or if `which` is unknown.
+ */
+export function getSpecialColor(
+  containerId: string,
+  which: SpecialColor
+): Uint8Array;
+
+/**
+ * Set the color of NaN/Inf ("bad"), below-range ("under") or above-range
+ * ("over") pixels. The color is only used once enabled with
+ * `setSpecialColorEnabled`.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param which - Which special color: "bad", "under" or "over".
+ * @param r - Red (0-255).
+ * @param g - Green (0-255).
+ * @param b - Blue (0-255).
+ * @throws If the viewer is not found, or if `which` is unknown.
+ */
+export function setSpecialColor(
+  containerId: string,
+  which: SpecialColor,
+  r: number,
+  g: number,
+  b: number
+): void;
+
+/**
+ * Get whether a special color is used.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param which - Which special color: "bad", "under" or "over".
+ * @returns True if the special color is used.
+ * @throws If the viewer is not found, or if `which` is unknown.
+ */
+export function getSpecialColorEnabled(
+  containerId: string,
+  which: SpecialColor
+): boolean;
+
+/**
+ * Enable or disable a special color. When disabled, bad pixels take the
+ * bottom colormap color and out-of-range pixels the nearest end of the
+ * colormap. Enabled colors are shown as swatches at the ends of the colorbar.
+ *
+ * @param containerId - The ID of the container (viewer instance).
+ * @param which - Which special color: "bad", "under" or "over".
+ * @param enabled - Whether to use the special color.
+ * @throws If the viewer is not found, or if `which` is unknown.
+ */
+export function setSpecialColorEnabled(
+  containerId: string,
+  which: SpecialColor,
+  enabled: boolean
+): void;
+
 /**
  * State object passed to state change callbacks.
  */
@@ -220,11 +994,39 @@ export interface ViewerState {
   contrast: number;
   bias: number;
   stretchMode: string;
+  /** Exponent of the log stretch */
+  logExponent: number;
+  /** Softening of the asinh stretch */
+  asinhSoftening: number;
+  /** Exponent of the power stretch */
+  powerExponent: number;
   zoom: number;
   colormap: string;
   colormapReversed: boolean;
+  /** Number of discrete colormap levels, or 0 when continuous */
+  colormapLevels: number;
+  complexComponent: string;
+  activeChannel: number;
+  /** Displayed slice, when a cube is loaded */
+  slice?: number;
+  /** Source of cube scale limits: 'slice' or 'global' */
+  sliceLimits?: string;
+  /** Whether cube playback is running, when a cube is loaded */
+  playing?: boolean;
+  /** Index along every axis of a cube or N-D array, null for the displayed axes */
+  axisIndex?: (number | null)[];
+  /** Axes shown along image rows and columns, as [yAxis, xAxis] */
+  displayAxes?: [number, number];
   vmin: number;
   vmax: number;
+  /** What happens to the display limits when new data arrives */
+  limitsPolicy: LimitsPolicy;
+  /** How automatic limits are computed */
+  scaleMode: ScaleMode;
+  /** Central percentile spanned by percentile limits */
+  limitsPercentile: number;
+  /** k of median ± k·MAD limits */
+  madFactor: number;
   xlim?: [number, number];
   ylim?: [number, number];
   rotation: number;
@@ -238,7 +1040,14 @@ export interface ViewerState {
 export interface ClickEvent {
   x: number;
   y: number;
-  value?: number;
+  /** Pixel value ([re, im] for complex data) */
+  value?: number | [number, number];
+  /** Channel values for color images */
+  channels?: number[];
+  /** Displayed slice, when a cube is loaded */
+  slice?: number;
+  /** Full N-D index of the clicked pixel, in axis order, when a cube is loaded */
+  index?: number[];
 }
 
 /**
@@ -277,6 +1086,33 @@ export function clearCallbacks(containerId: string): void;
 declare const viewarr: {
   createViewer: typeof createViewer;
   setImageData: typeof setImageData;
+  setImageDataStrided: typeof setImageDataStrided;
+  updateRegion: typeof updateRegion;
+  setRgbData: typeof setRgbData;
+  setCubeData: typeof setCubeData;
+  setArrayData: typeof setArrayData;
+  getSlice: typeof getSlice;
+  setSlice: typeof setSlice;
+  getShape: typeof getShape;
+  getAxisIndex: typeof getAxisIndex;
+  setAxisIndex: typeof setAxisIndex;
+  getDisplayAxes: typeof getDisplayAxes;
+  setDisplayAxes: typeof setDisplayAxes;
+  setAxisLabels: typeof setAxisLabels;
+  getNumSlices: typeof getNumSlices;
+  getSliceLimits: typeof getSliceLimits;
+  setSliceLimits: typeof setSliceLimits;
+  getPlaying: typeof getPlaying;
+  setPlaying: typeof setPlaying;
+  getPlaybackFps: typeof getPlaybackFps;
+  setPlaybackFps: typeof setPlaybackFps;
+  getPlaybackMode: typeof getPlaybackMode;
+  setPlaybackMode: typeof setPlaybackMode;
+  setCompositeData: typeof setCompositeData;
+  getActiveChannel: typeof getActiveChannel;
+  setActiveChannel: typeof setActiveChannel;
+  setFitsData: typeof setFitsData;
+  setNpyData: typeof setNpyData;
   destroyViewer: typeof destroyViewer;
   hasViewer: typeof hasViewer;
   getActiveViewers: typeof getActiveViewers;
@@ -286,17 +1122,54 @@ declare const viewarr: {
   setBias: typeof setBias;
   getStretchMode: typeof getStretchMode;
   setStretchMode: typeof setStretchMode;
+  getLogExponent: typeof getLogExponent;
+  setLogExponent: typeof setLogExponent;
+  getAsinhSoftening: typeof getAsinhSoftening;
+  setAsinhSoftening: typeof setAsinhSoftening;
+  getPowerExponent: typeof getPowerExponent;
+  setPowerExponent: typeof setPowerExponent;
+  getComplexComponent: typeof getComplexComponent;
+  setComplexComponent: typeof setComplexComponent;
   getViewBounds: typeof getViewBounds;
   setViewBounds: typeof setViewBounds;
   getColormap: typeof getColormap;
+  getColormapKind: typeof getColormapKind;
+  setColormap: typeof setColormap;
+  registerColormap: typeof registerColormap;
   getColormapReversed: typeof getColormapReversed;
+  setColormapReversed: typeof setColormapReversed;
+  getColormapLevels: typeof getColormapLevels;
+  setColormapLevels: typeof setColormapLevels;
   getValueRange: typeof getValueRange;
+  setValueRange: typeof setValueRange;
+  getLimitsPolicy: typeof getLimitsPolicy;
+  setLimitsPolicy: typeof setLimitsPolicy;
+  getScaleMode: typeof getScaleMode;
+  setScaleMode: typeof setScaleMode;
+  getLimitsPercentile: typeof getLimitsPercentile;
+  setLimitsPercentile: typeof setLimitsPercentile;
+  getMadFactor: typeof getMadFactor;
+  setMadFactor: typeof setMadFactor;
   getRotation: typeof getRotation;
   setRotation: typeof setRotation;
   getPivotPoint: typeof getPivotPoint;
   setPivotPoint: typeof setPivotPoint;
   getShowPivotMarker: typeof getShowPivotMarker;
   setShowPivotMarker: typeof setShowPivotMarker;
+  setMaskData: typeof setMaskData;
+  clearMask: typeof clearMask;
+  getShowMask: typeof getShowMask;
+  setShowMask: typeof setShowMask;
+  getMaskColor: typeof getMaskColor;
+  setMaskColor: typeof setMaskColor;
+  getMaskOpacity: typeof getMaskOpacity;
+  setMaskOpacity: typeof setMaskOpacity;
+  getMaskExcludesLimits: typeof getMaskExcludesLimits;
+  setMaskExcludesLimits: typeof setMaskExcludesLimits;
+  getSpecialColor: typeof getSpecialColor;
+  setSpecialColor: typeof setSpecialColor;
+  getSpecialColorEnabled: typeof getSpecialColorEnabled;
+  setSpecialColorEnabled: typeof setSpecialColorEnabled;
   onStateChange: typeof onStateChange;
   onClick: typeof onClick;
   clearCallbacks: typeof clearCallbacks;
@@ -177,8 +177,11 @@ export async function createViewer(containerId) {
  * @param {ArrayBuffer} buffer - The raw pixel data.
  * @param {number} width - Image width in pixels.
  * @param {number} height - Image height in pixels.
- * @param {string} dtype - Data type string (e.g., "f4", "f8", "i2", "u1").
- * @throws {Error} If the viewer is not found or data is invalid.
+ * @param {string} dtype - Element type: Rust-style ("f32", "u16", "f16", "bf16", "c64"), NumPy-style with
+ *   optional byte order ("f4", "<f2", ">i2", "|u1", "uint16", "<c8") or a TypedArray name
+ *   ("Float32Array"). Unknown names are rejected rather than read as float64.
+ * @throws {Error} If the viewer is not found, the type is unknown, or the buffer
+ *   length does not match width * height elements.
  */
 export function setImageData(containerId, buffer, width, height, dtype) {
   const viewer = viewers.get(containerId);
@@ -189,241 +192,1064 @@ export function setImageData(containerId, buffer, width, height, dtype) {
   viewer.handle.setImageData(buffer, width, height, dtype);
 }
 
+/**
+ * Set image data for a viewer from a 2D view into a larger buffer.
+ *
+ * Strides are in bytes, following the NumPy convention. For example, a
+ * Fortran-order (column-major) image uses rowStride = itemsize and
+ * colStride = height * itemsize.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - Buffer containing the pixel data (and possibly other data).
+ * @param {number} width - Image width in pixels.
+ * @param {number} height - Image height in pixels.
+ * @param {string} dtype - Element type (same forms as setImageData).
+ * @param {number} byteOffset - Offset in bytes of the first pixel.
+ * @param {number} rowStride - Distance in bytes between the starts of consecutive rows.
+ * @param {number} colStride - Distance in bytes between consecutive pixels in a row.
+ * @throws {Error} If the viewer is not found, the type is unknown, or the view
+ *   extends past the end of the buffer.
+ */
+export function setImageDataStrided(containerId, buffer, width, height, dtype, byteOffset, rowStride, colStride) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+
+  viewer.handle.setImageDataStrided(buffer, width, height, dtype, byteOffset, rowStride, colStride);
+}
+
+/**
+ * Overwrite a rectangular region of the current image, e.g. for chunked live readout.
+ * Only the rows touched by the region are rescanned for min/max, and only the region
+ * of the texture is redrawn unless the limits change.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} x - Column of the region's first pixel.
+ * @param {number} y - Row of the region's first pixel.
+ * @param {number} width - Region width in pixels.
+ * @param {number} height - Region height in pixels.
+ * @param {ArrayBuffer} buffer - The region's pixels in row-major order.
+ * @param {string} arrayType - Element type of the buffer; must match the current image.
+ * @param {boolean} [keepLimits] - If true, keep the current display limits; if false, re-autoscale (default: the limits policy decides).
+ * @throws {Error} If the viewer is not found, no 2D scalar image is loaded, the type does not match, or the region does not fit.
+ */
+export function updateRegion(containerId, x, y, width, height, buffer, arrayType, keepLimits) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.updateRegion(x, y, width, height, buffer, arrayType, keepLimits);
+}
+
+/**
+ * Set a color (RGB or RGBA) image, displayed directly without stretch or
+ * colormap. Pan, zoom, rotation, hover and click callbacks work as usual.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - The channel data.
+ * @param {number} width - Image width in pixels.
+ * @param {number} height - Image height in pixels.
+ * @param {string} arrayType - Channel type: "u8" (0-255) or a float type such as "f32" (0-1).
+ * @param {number} channels - Number of channels: 3 (RGB) or 4 (RGBA).
+ * @param {string} layout - "interleaved" (RGBRGB...) or "planar" (RRR...GGG...BBB...).
+ * @throws {Error} If the viewer is not found, the type or layout is unknown, or the buffer length does not match.
+ */
+export function setRgbData(containerId, buffer, width, height, arrayType, channels, layout) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setRgbData(buffer, width, height, arrayType, channels, layout);
+}
+
+/**
+ * Set a cube of same-shaped 2D planes, browsed one slice at a time
+ * (slider, step buttons or left/right arrow keys).
+ * The current slice index and display settings are kept.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - Consecutive row-major planes (C order depth x height x width).
+ * @param {number} depth - Number of planes.
+ * @param {number} width - Plane width in pixels.
+ * @param {number} height - Plane height in pixels.
+ * @param {string} arrayType - Element type of the buffer (same forms as setImageData).
+ * @throws {Error} If the viewer is not found, the type is unknown, or the buffer size does not match.
+ */
+export function setCubeData(containerId, buffer, depth, width, height, arrayType) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setCubeData(buffer, depth, width, height, arrayType);
+}
+
+/**
+ * Set an N-dimensional array, displayed as 2D planes.
+ * The last two axes are displayed (see setDisplayAxes) and every other axis gets an
+ * index slider. Axis indices and display settings are kept when an array with the
+ * same number of axes is loaded.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - The array in C order (last axis varies fastest).
+ * @param {number[]} shape - Length of every axis, e.g. [time, wavelength, height, width].
+ * @param {string} arrayType - Element type of the buffer (same forms as setImageData).
+ * @throws {Error} If the viewer is not found, the type is unknown, the shape has fewer than 2 axes, or the buffer size does not match.
+ */
+export function setArrayData(containerId, buffer, shape, arrayType) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setArrayData(buffer, shape, arrayType);
+}
+
+/**
+ * Get the index of the displayed cube slice.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} The slice index.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getSlice(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getSlice();
+}
+
+/**
+ * Display another cube slice (clamped to the cube depth).
+ * Has no effect unless a cube is loaded.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} index - The slice index.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setSlice(containerId, index) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setSlice(index);
+}
+
+/**
+ * Get the shape of the loaded cube or N-D array.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {Uint32Array} Length of every axis (empty if none is loaded).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getShape(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getShape();
+}
+
+/**
+ * Get the index along every axis of the loaded cube or N-D array.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {Uint32Array} One index per axis; entries of the two displayed axes are 0.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getAxisIndex(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getAxisIndex();
+}
+
+/**
+ * Move along a non-displayed axis (index clamped to the axis length).
+ * Has no effect for the displayed axes or when no cube is loaded.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} axis - The axis to move along.
+ * @param {number} index - The index along that axis.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setAxisIndex(containerId, axis, index) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setAxisIndex(axis, index);
+}
+
+/**
+ * Get the axes shown along image rows and columns.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {Uint32Array} [yAxis, xAxis], or empty if no cube is loaded.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getDisplayAxes(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getDisplayAxes();
+}
+
+/**
+ * Choose the axes shown along image rows and columns.
+ * Limits are recomputed; pan and mask are reset if the plane shape changes.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} yAxis - Axis shown along image rows.
+ * @param {number} xAxis - Axis shown along image columns.
+ * @throws {Error} If the viewer is not found, no cube is loaded, or the axes are equal or out of range.
+ */
+export function setDisplayAxes(containerId, yAxis, xAxis) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setDisplayAxes(yAxis, xAxis);
+}
+
+/**
+ * Set one label per axis, shown next to the index sliders and in the hover readout.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string[]} labels - One label per axis, or an empty list to restore the default "axis N" labels.
+ * @throws {Error} If the viewer is not found, no cube is loaded, or the number of labels does not match.
+ */
+export function setAxisLabels(containerId, labels) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setAxisLabels(labels);
+}
+
+/**
+ * Get the number of cube slices.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} The cube depth, or 0 if no cube is loaded.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getNumSlices(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getNumSlices();
+}
+
+/**
+ * Get where cube scale limits come from.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} 'slice' (the displayed slice) or 'global' (the whole cube).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getSliceLimits(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getSliceLimits();
+}
+
+/**
+ * Set where cube scale limits come from. Limits are recomputed when this changes.
+ * With 'slice', limits follow each slice unless they have been modified.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} mode - 'slice' (the displayed slice) or 'global' (the whole cube).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setSliceLimits(containerId, mode) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setSliceLimits(mode);
+}
+
+/**
+ * Get whether the cube is being played as a movie.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {boolean} True while playback is running.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getPlaying(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getPlaying();
+}
+
+/**
+ * Start or stop cube playback (also toggled with the space key).
+ * Has no effect unless a cube is loaded.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {boolean} playing - True to play, false to pause.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setPlaying(containerId, playing) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setPlaying(playing);
+}
+
+/**
+ * Get the cube playback rate.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} Frames per second.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getPlaybackFps(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getPlaybackFps();
+}
+
+/**
+ * Set the cube playback rate.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} fps - Frames per second (clamped to 0.1-60).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setPlaybackFps(containerId, fps) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setPlaybackFps(fps);
+}
+
+/**
+ * Get what playback does at the ends of the cube.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} 'loop' or 'bounce'.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getPlaybackMode(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getPlaybackMode();
+}
+
+/**
+ * Set what playback does at the ends of the cube.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} mode - 'loop' (wrap to the first frame) or 'bounce' (reverse direction).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setPlaybackMode(containerId, mode) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setPlaybackMode(mode);
+}
+
+/**
+ * Set a three-channel composite from separate scalar arrays.
+ * Each channel has its own display limits, stretch and contrast/bias before the
+ * channels are combined into one color image.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} red - Pixel data of the red channel.
+ * @param {ArrayBuffer} green - Pixel data of the green channel.
+ * @param {ArrayBuffer} blue - Pixel data of the blue channel.
+ * @param {number} width - Image width in pixels.
+ * @param {number} height - Image height in pixels.
+ * @param {string} arrayType - Element type of all three buffers (same forms as setImageData).
+ * @throws {Error} If the viewer is not found, the type is unknown, or a buffer size does not match.
+ */
+export function setCompositeData(containerId, red, green, blue, width, height, arrayType) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setCompositeData(red, green, blue, width, height, arrayType);
+}
+
+/**
+ * Get the composite channel that stretch, contrast/bias and limit setters act on.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} 0 (red), 1 (green) or 2 (blue).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getActiveChannel(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getActiveChannel();
+}
+
+/**
+ * Select the composite channel that stretch, contrast/bias and limit setters act on.
+ * Has no effect unless a composite is loaded.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} channel - 0 (red), 1 (green) or 2 (blue).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setActiveChannel(containerId, channel) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setActiveChannel(channel);
+}
+
+/**
+ * Set image data for a viewer from the contents of a FITS file.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - The complete FITS file.
+ * @param {number} [hduIndex=0] - Index of the HDU to display (0 = primary HDU).
+ * @throws {Error} If the viewer is not found or the HDU cannot be read as an image.
+ */
+export function setFitsData(containerId, buffer, hduIndex = 0) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+
+  viewer.handle.setFitsData(buffer, hduIndex);
+}
+
+/**
+ * Set image data for a viewer from a NumPy .npy file or .npz archive.
+ *
+ * The dtype, byte order, memory order and shape are read from the file header.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - The complete .npy or .npz file.
+ * @param {string} [arrayName] - For .npz archives, the array to display (default: the first one).
+ * @throws {Error} If the viewer is not found or the file cannot be read.
+ */
+export function setNpyData(containerId, buffer, arrayName) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+
+  viewer.handle.setNpyData(buffer, arrayName);
+}
+
 /**
  * Destroy a viewer instance and clean up resources.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
  */
-export function destroyViewer(containerId) {
+export function destroyViewer(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    return; // Already destroyed or never created
+  }
+  viewer.handle.destroy();
+
+  // Stop observing mutations
+  if (viewer.mutationObserver) {
+    viewer.mutationObserver.disconnect();
+  }
+
+  // Clear the container
+  viewer.container.innerHTML = '';
+
+  // Remove from map
+  viewers.delete(containerId);
+}
+
+/**
+ * Check if a viewer exists for a given container.
+ *
+ * @param {string} containerId - The ID of the container.
+ * @returns {boolean} True if a viewer exists.
+ */
+export function hasViewer(containerId) {
+  return viewers.has(containerId);
+}
+
+/**
+ * Get all active viewer IDs.
+ *
+ * @returns {string[]} Array of container IDs with active viewers.
+ */
+export function getActiveViewers() {
+  return Array.from(viewers.keys());
+}
+
+// =========================================================================
+// Contrast/Bias/Stretch getters and setters
+// =========================================================================
+
+/**
+ * Get current contrast value for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} Contrast value (0.0 to 10.0, default 1.0).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getContrast(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
-    return; // Already destroyed or never created
+    throw new Error(`No viewer found for container "${containerId}"`);
   }
-  viewer.handle.destroy();
+  return viewer.handle.getContrast();
+}
 
-  // Stop observing mutations
-  if (viewer.mutationObserver) {
-    viewer.mutationObserver.disconnect();
+/**
+ * Set contrast value for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} contrast - Contrast value (0.0 to 10.0).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setContrast(containerId, contrast) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setContrast(contrast);
+}
+
+/**
+ * Get current bias value for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} Bias value (0.0 to 1.0, default 0.5).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getBias(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getBias();
+}
+
+/**
+ * Set bias value for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} bias - Bias value (0.0 to 1.0).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setBias(containerId, bias) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setBias(bias);
+}
+
+/**
+ * Get current stretch mode for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} Stretch mode: "linear", "log", "sqrt", "squared", "asinh", "power", "sinh", "histeq", or "symmetric".
+ * @throws {Error} If the viewer is not found.
+ */
+export function getStretchMode(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getStretchMode();
+}
+
+/**
+ * Set stretch mode for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} mode - Stretch mode: "linear", "log", "sqrt", "squared", "asinh", "power", "sinh", "histeq", or "symmetric".
+ * @throws {Error} If the viewer is not found.
+ */
+export function setStretchMode(containerId, mode) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setStretchMode(mode);
+}
+
+/**
+ * Get the exponent of the log stretch.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} The exponent.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getLogExponent(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getLogExponent();
+}
+
+/**
+ * Set the exponent of the log stretch. The default 1000 suits optical
+ * images; smaller values suit data with less dynamic range.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} exponent - The exponent (must be greater than 1).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setLogExponent(containerId, exponent) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setLogExponent(exponent);
+}
+
+/**
+ * Get the softening of the asinh stretch.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} The softening.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getAsinhSoftening(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getAsinhSoftening();
+}
+
+/**
+ * Set the softening of the asinh stretch: values well below it (as a
+ * fraction of the display range) are stretched linearly, values above it
+ * logarithmically. Default 0.1.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} softening - The softening (must be positive).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setAsinhSoftening(containerId, softening) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setAsinhSoftening(softening);
+}
+
+/**
+ * Get the exponent of the power stretch.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} The exponent.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getPowerExponent(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getPowerExponent();
+}
+
+/**
+ * Set the exponent of the power stretch. Default 3.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} exponent - The exponent (must be positive).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setPowerExponent(containerId, exponent) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setPowerExponent(exponent);
+}
+
+/**
+ * Get which quantity of complex data is displayed.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} Component: "magnitude", "phase", "real", "imag", or "logpower".
+ * @throws {Error} If the viewer is not found.
+ */
+export function getComplexComponent(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getComplexComponent();
+}
+
+/**
+ * Set which quantity of complex (c64/c128) data is displayed.
+ * Phase uses a cyclic colormap and a fixed -π..π range.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} component - Component: "magnitude", "phase", "real", "imag", or "logpower".
+ * @throws {Error} If the viewer is not found.
+ */
+export function setComplexComponent(containerId, component) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setComplexComponent(component);
+}
+
+/**
+ * Get visible image bounds in pixel coordinates.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number[]} Array [xmin, xmax, ymin, ymax] in pixel coordinates.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getViewBounds(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  // Get viewport dimensions from the container
+  const rect = viewer.container.getBoundingClientRect();
+  const bounds = viewer.handle.getViewBounds(rect.width, rect.height);
+  return Array.from(bounds);
+}
+
+/**
+ * Set view to show specific image bounds.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} xmin - Minimum x coordinate in pixels.
+ * @param {number} xmax - Maximum x coordinate in pixels.
+ * @param {number} ymin - Minimum y coordinate in pixels.
+ * @param {number} ymax - Maximum y coordinate in pixels.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setViewBounds(containerId, xmin, xmax, ymin, ymax) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  // Get viewport dimensions from the container
+  const rect = viewer.container.getBoundingClientRect();
+  viewer.handle.setViewBounds(xmin, xmax, ymin, ymax, rect.width, rect.height);
+}
+
+/**
+ * Get the colormap name for a viewer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} Colormap name (e.g., "Gray", "Inferno", "Magma", "RdBu").
+ * @throws {Error} If the viewer is not found.
+ */
+export function getColormap(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getColormap();
+}
+
+/**
+ * Get the kind of the current colormap.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {string} "sequential", "diverging", "cyclic" or "qualitative".
+ * @throws {Error} If the viewer is not found.
+ */
+export function getColormapKind(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getColormapKind();
+}
+
+/**
+ * Set the colormap by name (ignoring case), built-in or registered with
+ * `registerColormap`.
+ * Diverging colormaps are used in symmetric mode, cyclic ones for complex phase.
+ * Qualitative colormaps (e.g., "Tab10", "Glasbey") color each value as an
+ * integer label, without stretching, and show a legend instead of the colorbar.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} name - Colormap name (e.g., "Viridis", "Balance", "Twilight").
+ * @throws {Error} If the viewer is not found, or if the colormap is unknown.
+ */
+export function setColormap(containerId, name) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
   }
-
-  // Clear the container
-  viewer.container.innerHTML = '';
-
-  // Remove from map
-  viewers.delete(containerId);
+  viewer.handle.setColormap(name);
 }
 
 /**
- * Check if a viewer exists for a given container.
+ * Register a colormap for all viewers, replacing any registered colormap of
+ * the same name. It shows up in the colormap picker and works with
+ * `setColormap`.
  *
- * @param {string} containerId - The ID of the container.
- * @returns {boolean} True if a viewer exists.
+ * @param {string} name - Display name; must not be that of a built-in colormap.
+ * @param {Array} stops - `[r, g, b]` colors (0-255) evenly spaced from 0 to 1,
+ *   such as a 256-entry lookup table, or `[position, [r, g, b]]` control points
+ *   with positions increasing from 0 to 1.
+ * @param {Object} [options]
+ * @param {string} [options.kind='sequential'] - "sequential", "diverging"
+ *   (offered in symmetric mode), "cyclic" (offered for complex phase; values
+ *   wrap around) or "qualitative" (one color per integer label, stops used as
+ *   listed).
+ * @param {boolean} [options.diverging=false] - Shorthand for
+ *   `kind: 'diverging'`.
+ * @param {string} [options.colorSpace='srgb'] - Where colors between stops are
+ *   interpolated: "srgb", "linear" or "oklab".
+ * @returns {Promise<void>} Resolves once the colormap is registered.
+ * @throws {Error} If the stops, name, kind or color space are invalid.
  */
-export function hasViewer(containerId) {
-  return viewers.has(containerId);
+export async function registerColormap(name, stops, options = {}) {
+  await initWasm();
+  const { diverging = false, kind = diverging ? 'diverging' : 'sequential', colorSpace = 'srgb' } = options;
+  wasmModule.registerColormap(name, stops, kind, colorSpace);
 }
 
 /**
- * Get all active viewer IDs.
+ * Get whether the colormap is reversed.
  *
- * @returns {string[]} Array of container IDs with active viewers.
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {boolean} True if the colormap is reversed.
+ * @throws {Error} If the viewer is not found.
  */
-export function getActiveViewers() {
-  return Array.from(viewers.keys());
+export function getColormapReversed(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getColormapReversed();
 }
 
-// =========================================================================
-// Contrast/Bias/Stretch getters and setters
-// =========================================================================
+/**
+ * Set whether the colormap is reversed.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {boolean} reversed - True to reverse the colormap.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setColormapReversed(containerId, reversed) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setColormapReversed(reversed);
+}
 
 /**
- * Get current contrast value for a viewer.
+ * Get the number of discrete colormap levels.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {number} Contrast value (0.0 to 10.0, default 1.0).
+ * @returns {number} Number of levels, or 0 when the colormap is continuous.
  * @throws {Error} If the viewer is not found.
  */
-export function getContrast(containerId) {
+export function getColormapLevels(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  return viewer.handle.getContrast();
+  return viewer.handle.getColormapLevels();
 }
 
 /**
- * Set contrast value for a viewer.
+ * Quantize the colormap into flat levels for contour-like displays, or make it
+ * continuous again. Values other than 0 or 2-256 are ignored.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @param {number} contrast - Contrast value (0.0 to 10.0).
+ * @param {number} levels - Number of levels (2-256), or 0 for a continuous colormap.
  * @throws {Error} If the viewer is not found.
  */
-export function setContrast(containerId, contrast) {
+export function setColormapLevels(containerId, levels) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  viewer.handle.setContrast(contrast);
+  viewer.handle.setColormapLevels(levels);
 }
 
 /**
- * Get current bias value for a viewer.
+ * Get the image value range (vmin, vmax).
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {number} Bias value (0.0 to 1.0, default 0.5).
+ * @returns {number[]} Array [vmin, vmax].
  * @throws {Error} If the viewer is not found.
  */
-export function getBias(containerId) {
+export function getValueRange(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  return viewer.handle.getBias();
+  const range = viewer.handle.getValueRange();
+  return Array.from(range);
 }
 
 /**
- * Set bias value for a viewer.
+ * Set the value range (vmin, vmax) for display scaling.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @param {number} bias - Bias value (0.0 to 1.0).
+ * @param {number} vmin - The minimum display value.
+ * @param {number} vmax - The maximum display value.
  * @throws {Error} If the viewer is not found.
  */
-export function setBias(containerId, bias) {
+export function setValueRange(containerId, vmin, vmax) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  viewer.handle.setBias(bias);
+  viewer.handle.setValueRange(vmin, vmax);
 }
 
 /**
- * Get current stretch mode for a viewer.
+ * Get what happens to the display limits when new data arrives.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {string} Stretch mode: "linear", "log", or "symmetric".
+ * @returns {string} "auto", "keep", "keep-if-modified" or "percentile".
  * @throws {Error} If the viewer is not found.
  */
-export function getStretchMode(containerId) {
+export function getLimitsPolicy(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  return viewer.handle.getStretchMode();
+  return viewer.handle.getLimitsPolicy();
 }
 
 /**
- * Set stretch mode for a viewer.
+ * Set what happens to the display limits when new data arrives
+ * (setImageData, setCubeData, setArrayData and updateRegion):
+ * "auto" recomputes them from every frame, "keep" never changes them,
+ * "keep-if-modified" recomputes them unless the user has changed them, and
+ * "percentile" follows the central percentile range of every frame
+ * (see setLimitsPercentile). Invalid policies are ignored.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @param {string} mode - Stretch mode: "linear", "log", or "symmetric".
+ * @param {string} policy - The limits policy.
  * @throws {Error} If the viewer is not found.
  */
-export function setStretchMode(containerId, mode) {
+export function setLimitsPolicy(containerId, policy) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  viewer.handle.setStretchMode(mode);
+  viewer.handle.setLimitsPolicy(policy);
 }
 
 /**
- * Get visible image bounds in pixel coordinates.
+ * Get how automatic limits are computed.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {number[]} Array [xmin, xmax, ymin, ymax] in pixel coordinates.
+ * @returns {string} "minmax", "percentile", "zscale" or "mad".
  * @throws {Error} If the viewer is not found.
  */
-export function getViewBounds(containerId) {
+export function getScaleMode(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  // Get viewport dimensions from the container
-  const rect = viewer.container.getBoundingClientRect();
-  const bounds = viewer.handle.getViewBounds(rect.width, rect.height);
-  return Array.from(bounds);
+  return viewer.handle.getScaleMode();
 }
 
 /**
- * Set view to show specific image bounds.
+ * Set how automatic limits are computed: "minmax" (full finite range),
+ * "percentile" (central percentile range, see setLimitsPercentile),
+ * "zscale" (IRAF/DS9 ZScale) or "mad" (median ± k·MAD, see setMadFactor).
+ * Limits are recomputed when this changes. Invalid modes are ignored.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @param {number} xmin - Minimum x coordinate in pixels.
- * @param {number} xmax - Maximum x coordinate in pixels.
- * @param {number} ymin - Minimum y coordinate in pixels.
- * @param {number} ymax - Maximum y coordinate in pixels.
+ * @param {string} mode - The scale mode.
  * @throws {Error} If the viewer is not found.
  */
-export function setViewBounds(containerId, xmin, xmax, ymin, ymax) {
+export function setScaleMode(containerId, mode) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  // Get viewport dimensions from the container
-  const rect = viewer.container.getBoundingClientRect();
-  viewer.handle.setViewBounds(xmin, xmax, ymin, ymax, rect.width, rect.height);
+  viewer.handle.setScaleMode(mode);
 }
 
 /**
- * Get the colormap name for a viewer.
+ * Get the central percentile of the data spanned by percentile limits.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {string} Colormap name (e.g., "Gray", "Inferno", "Magma", "RdBu").
+ * @returns {number} The percentile (0-100).
  * @throws {Error} If the viewer is not found.
  */
-export function getColormap(containerId) {
+export function getLimitsPercentile(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  return viewer.handle.getColormap();
+  return viewer.handle.getLimitsPercentile();
 }
 
 /**
- * Get whether the colormap is reversed.
+ * Set the central percentile of the data spanned by percentile limits
+ * (scale mode or limits policy "percentile"). The default 99.5 clips the lowest and highest 0.25% of the values.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {boolean} True if the colormap is reversed.
+ * @param {number} percent - The percentile (0-100).
  * @throws {Error} If the viewer is not found.
  */
-export function getColormapReversed(containerId) {
+export function setLimitsPercentile(containerId, percent) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  return viewer.handle.getColormapReversed();
+  viewer.handle.setLimitsPercentile(percent);
 }
 
 /**
- * Get the image value range (vmin, vmax).
+ * Get k of the "mad" scale mode (median ± k·MAD).
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @returns {number[]} Array [vmin, vmax].
+ * @returns {number} The factor k.
  * @throws {Error} If the viewer is not found.
  */
-export function getValueRange(containerId) {
+export function getMadFactor(containerId) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  const range = viewer.handle.getValueRange();
-  return Array.from(range);
+  return viewer.handle.getMadFactor();
 }
 
 /**
- * Set the value range (vmin, vmax) for display scaling.
+ * Set k of the "mad" scale mode (median ± k·MAD). Default 3.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
- * @param {number} vmin - The minimum display value.
- * @param {number} vmax - The maximum display value.
+ * @param {number} k - The factor k (must be positive).
  * @throws {Error} If the viewer is not found.
  */
-export function setValueRange(containerId, vmin, vmax) {
+export function setMadFactor(containerId, k) {
   const viewer = viewers.get(containerId);
   if (!viewer) {
     throw new Error(`No viewer found for container "${containerId}"`);
   }
-  viewer.handle.setValueRange(vmin, vmax);
+  viewer.handle.setMadFactor(k);
 }
 
 // =========================================================================
@@ -522,11 +1348,237 @@ export function setShowPivotMarker(containerId, show) {
   viewer.handle.setShowPivotMarker(show);
 }
 
+/**
+ * Set a mask layer, drawn as a translucent color overlay on the image.
+ * The mask must have the same shape as the image; nonzero values are masked.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {ArrayBuffer} buffer - One byte per pixel (uint8 or bool), row-major.
+ * @param {number} width - Mask width in pixels (must match the image).
+ * @param {number} height - Mask height in pixels (must match the image).
+ * @throws {Error} If the viewer is not found, or the mask shape does not match the image.
+ */
+export function setMaskData(containerId, buffer, width, height) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setMaskData(buffer, width, height);
+}
+
+/**
+ * Remove the mask layer.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @throws {Error} If the viewer is not found.
+ */
+export function clearMask(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.clearMask();
+}
+
+/**
+ * Get whether the mask overlay is shown.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {boolean} True if the mask overlay is shown.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getShowMask(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getShowMask();
+}
+
+/**
+ * Set whether the mask overlay is shown.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {boolean} show - True to show the mask overlay.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setShowMask(containerId, show) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setShowMask(show);
+}
+
+/**
+ * Get the mask overlay color.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {Uint8Array} Color as [r, g, b] (0-255).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getMaskColor(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getMaskColor();
+}
+
+/**
+ * Set the mask overlay color.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} r - Red (0-255).
+ * @param {number} g - Green (0-255).
+ * @param {number} b - Blue (0-255).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setMaskColor(containerId, r, g, b) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setMaskColor(r, g, b);
+}
+
+/**
+ * Get the mask overlay opacity.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {number} Opacity (0-1).
+ * @throws {Error} If the viewer is not found.
+ */
+export function getMaskOpacity(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getMaskOpacity();
+}
+
+/**
+ * Set the mask overlay opacity.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {number} opacity - Opacity (0-1).
+ * @throws {Error} If the viewer is not found.
+ */
+export function setMaskOpacity(containerId, opacity) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setMaskOpacity(opacity);
+}
+
+/**
+ * Get whether masked pixels are ignored when computing min/max.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @returns {boolean} True if masked pixels are excluded.
+ * @throws {Error} If the viewer is not found.
+ */
+export function getMaskExcludesLimits(containerId) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getMaskExcludesLimits();
+}
+
+/**
+ * Set whether masked pixels are ignored (like NaN) when computing the
+ * auto-scaling min/max. Limits are recomputed when this changes.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {boolean} exclude - True to exclude masked pixels.
+ * @throws {Error} If the viewer is not found.
+ */
+export function setMaskExcludesLimits(containerId, exclude) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setMaskExcludesLimits(exclude);
+}
+
+/**
+ * Get the color of NaN/Inf ("bad"), below-range ("under") or above-range
+ * ("over") pixels.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} which - Which special color: "bad", "under" or "over".
+ * @returns {Uint8Array} Color as [r, g, b] (0-255).
+ * @throws {Error} If the viewer is not found, or if `which` is unknown.
+ */
+export function getSpecialColor(containerId, which) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getSpecialColor(which);
+}
+
+/**
+ * Set the color of NaN/Inf ("bad"), below-range ("under") or above-range
+ * ("over") pixels. The color is only used once enabled with
+ * `setSpecialColorEnabled`.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} which - Which special color: "bad", "under" or "over".
+ * @param {number} r - Red (0-255).
+ * @param {number} g - Green (0-255).
+ * @param {number} b - Blue (0-255).
+ * @throws {Error} If the viewer is not found, or if `which` is unknown.
+ */
+export function setSpecialColor(containerId, which, r, g, b) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setSpecialColor(which, r, g, b);
+}
+
+/**
+ * Get whether a special color is used.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} which - Which special color: "bad", "under" or "over".
+ * @returns {boolean} True if the special color is used.
+ * @throws {Error} If the viewer is not found, or if `which` is unknown.
+ */
+export function getSpecialColorEnabled(containerId, which) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  return viewer.handle.getSpecialColorEnabled(which);
+}
+
+/**
+ * Enable or disable a special color. When disabled, bad pixels take the
+ * bottom colormap color and out-of-range pixels the nearest end of the
+ * colormap. Enabled colors are shown as swatches at the ends of the colorbar.
+ *
+ * @param {string} containerId - The ID of the container (viewer instance).
+ * @param {string} which - Which special color: "bad", "under" or "over".
+ * @param {boolean} enabled - Whether to use the special color.
+ * @throws {Error} If the viewer is not found, or if `which` is unknown.
+ */
+export function setSpecialColorEnabled(containerId, which, enabled) {
+  const viewer = viewers.get(containerId);
+  if (!viewer) {
+    throw new Error(`No viewer found for container "${containerId}"`);
+  }
+  viewer.handle.setSpecialColorEnabled(which, enabled);
+}
+
 /**
  * Register a callback to be called when the viewer state changes.
  *
  * The callback receives an object with the current state:
- * { contrast, bias, stretchMode, zoom, colormap, colormapReversed, vmin, vmax, xlim, ylim }
+ * { contrast, bias, stretchMode, zoom, colormap, colormapReversed, complexComponent, vmin, vmax, xlim, ylim }
  *
  * @param {string} containerId - The ID of the container (viewer instance).
  * @param {Function} callback - Callback function to receive state updates.
@@ -544,6 +1596,7 @@ export function onStateChange(containerId, callback) {
  * Register a callback to be called when the user clicks in the viewer.
  *
  * The callback receives the click coordinates in data space: { x, y, value }
+ * (value is [re, im] for complex data). Color images report { x, y, channels }.
  *
  * @param {string} containerId - The ID of the container (viewer instance).
  * @param {Function} callback - Callback function to receive click events.
@@ -574,6 +1627,33 @@ export function clearCallbacks(containerId) {
 window.viewarr = {
   createViewer,
   setImageData,
+  setImageDataStrided,
+  updateRegion,
+  setRgbData,
+  setCubeData,
+  setArrayData,
+  getSlice,
+  setSlice,
+  getShape,
+  getAxisIndex,
+  setAxisIndex,
+  getDisplayAxes,
+  setDisplayAxes,
+  setAxisLabels,
+  getNumSlices,
+  getSliceLimits,
+  setSliceLimits,
+  getPlaying,
+  setPlaying,
+  getPlaybackFps,
+  setPlaybackFps,
+  getPlaybackMode,
+  setPlaybackMode,
+  setCompositeData,
+  getActiveChannel,
+  setActiveChannel,
+  setFitsData,
+  setNpyData,
   destroyViewer,
   hasViewer,
   getActiveViewers,
@@ -583,18 +1663,54 @@ window.viewarr = {
   setBias,
   getStretchMode,
   setStretchMode,
+  getLogExponent,
+  setLogExponent,
+  getAsinhSoftening,
+  setAsinhSoftening,
+  getPowerExponent,
+  setPowerExponent,
+  getComplexComponent,
+  setComplexComponent,
   getViewBounds,
   setViewBounds,
   getColormap,
+  getColormapKind,
+  setColormap,
+  registerColormap,
   getColormapReversed,
+  setColormapReversed,
+  getColormapLevels,
+  setColormapLevels,
   getValueRange,
   setValueRange,
+  getLimitsPolicy,
+  setLimitsPolicy,
+  getScaleMode,
+  setScaleMode,
+  getLimitsPercentile,
+  setLimitsPercentile,
+  getMadFactor,
+  setMadFactor,
   getRotation,
   setRotation,
   getPivotPoint,
   setPivotPoint,
   getShowPivotMarker,
   setShowPivotMarker,
+  setMaskData,
+  clearMask,
+  getShowMask,
+  setShowMask,
+  getMaskColor,
+  setMaskColor,
+  getMaskOpacity,
+  setMaskOpacity,
+  getMaskExcludesLimits,
+  setMaskExcludesLimits,
+  getSpecialColor,
+  setSpecialColor,
+  getSpecialColorEnabled,
+  setSpecialColorEnabled,
   onStateChange,
   onClick,
   clearCallbacks
@@ -604,6 +1720,33 @@ window.viewarr = {
 export default {
   createViewer,
   setImageData,
+  setImageDataStrided,
+  updateRegion,
+  setRgbData,
+  setCubeData,
+  setArrayData,
+  getSlice,
+  setSlice,
+  getShape,
+  getAxisIndex,
+  setAxisIndex,
+  getDisplayAxes,
+  setDisplayAxes,
+  setAxisLabels,
+  getNumSlices,
+  getSliceLimits,
+  setSliceLimits,
+  getPlaying,
+  setPlaying,
+  getPlaybackFps,
+  setPlaybackFps,
+  getPlaybackMode,
+  setPlaybackMode,
+  setCompositeData,
+  getActiveChannel,
+  setActiveChannel,
+  setFitsData,
+  setNpyData,
   destroyViewer,
   hasViewer,
   getActiveViewers,
@@ -613,18 +1756,54 @@ export default {
   setBias,
   getStretchMode,
   setStretchMode,
+  getLogExponent,
+  setLogExponent,
+  getAsinhSoftening,
+  setAsinhSoftening,
+  getPowerExponent,
+  setPowerExponent,
+  getComplexComponent,
+  setComplexComponent,
   getViewBounds,
   setViewBounds,
   getColormap,
+  getColormapKind,
+  setColormap,
+  registerColormap,
   getColormapReversed,
+  setColormapReversed,
+  getColormapLevels,
+  setColormapLevels,
   getValueRange,
   setValueRange,
+  getLimitsPolicy,
+  setLimitsPolicy,
+  getScaleMode,
+  setScaleMode,
+  getLimitsPercentile,
+  setLimitsPercentile,
+  getMadFactor,
+  setMadFactor,
   getRotation,
   setRotation,
   getPivotPoint,
   setPivotPoint,
   getShowPivotMarker,
   setShowPivotMarker,
+  setMaskData,
+  clearMask,
+  getShowMask,
+  setShowMask,
+  getMaskColor,
+  setMaskColor,
+  getMaskOpacity,
+  setMaskOpacity,
+  getMaskExcludesLimits,
+  setMaskExcludesLimits,
+  getSpecialColor,
+  setSpecialColor,
+  getSpecialColorEnabled,
+  setSpecialColorEnabled,
   onStateChange,
   onClick,
   clearCallbacks
@@ -12,6 +12,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::pixels::PixelValue;
 use crate::widget::ArrayViewerWidget;
 use crate::ViewerCallbacks;
 use wasm_bindgen::JsValue;
@@ -25,12 +26,27 @@ struct CachedState {
     contrast: f64,
     bias: f64,
     stretch_mode: String,
+    log_exponent: f64,
+    asinh_softening: f64,
+    power_exponent: f64,
     zoom: f32,
     symmetric: bool,
     colormap: String,
     colormap_reversed: bool,
+    colormap_levels: u32,
+    complex_component: String,
+    active_channel: usize,
+    slice: Option<usize>,
+    slice_limits: String,
+    playing: bool,
+    axis_index: Vec<usize>,
+    display_axes: Option<(usize, usize)>,
     vmin: f64,
     vmax: f64,
+    limits_policy: String,
+    scale_mode: String,
+    limits_percentile: f64,
+    mad_factor: f64,
     pan_x: f32,
     pan_y: f32,
     rotation: f32,
@@ -51,17 +67,29 @@ impl CachedState {
             stretch_mode: if widget.is_symmetric() {
                 "symmetric".to_string()
             } else {
-                match widget.stretch_type() {
-                    crate::widget::StretchType::Linear => "linear".to_string(),
-                    crate::widget::StretchType::Log => "log".to_string(),
-                }
+                widget.stretch_type().name().to_string()
             },
+            log_exponent: widget.stretch_params().log_exponent,
+            asinh_softening: widget.stretch_params().asinh_softening,
+            power_exponent: widget.stretch_params().power_exponent,
             zoom: widget.zoom_level(),
             symmetric: widget.is_symmetric(),
             colormap: widget.colormap().name().to_string(),
             colormap_reversed: widget.is_reversed(),
+            colormap_levels: widget.colormap_levels().unwrap_or(0),
+            complex_component: widget.complex_component().name().to_string(),
+            active_channel: widget.active_channel(),
+            slice: widget.is_cube().then(|| widget.slice_index()),
+            slice_limits: widget.slice_limits().name().to_string(),
+            playing: widget.is_playing(),
+            axis_index: widget.axis_index().to_vec(),
+            display_axes: widget.display_axes(),
             vmin,
             vmax,
+            limits_policy: widget.limits_policy().name().to_string(),
+            scale_mode: widget.scale_mode().name().to_string(),
+            limits_percentile: widget.limits_percentile(),
+            mad_factor: widget.mad_factor(),
             pan_x: transform.pan_offset.x,
             pan_y: transform.pan_offset.y,
             rotation: widget.rotation(),
@@ -75,12 +103,27 @@ impl CachedState {
         (self.contrast - other.contrast).abs() > 0.001
             || (self.bias - other.bias).abs() > 0.001
             || self.stretch_mode != other.stretch_mode
+            || self.log_exponent != other.log_exponent
+            || self.asinh_softening != other.asinh_softening
+            || self.power_exponent != other.power_exponent
             || (self.zoom - other.zoom).abs() > 0.001
             || self.symmetric != other.symmetric
             || self.colormap != other.colormap
             || self.colormap_reversed != other.colormap_reversed
+            || self.colormap_levels != other.colormap_levels
+            || self.complex_component != other.complex_component
+            || self.active_channel != other.active_channel
+            || self.slice != other.slice
+            || self.slice_limits != other.slice_limits
+            || self.playing != other.playing
+            || self.axis_index != other.axis_index
+            || self.display_axes != other.display_axes
             || (self.vmin - other.vmin).abs() > 1e-10
             || (self.vmax - other.vmax).abs() > 1e-10
+            || self.limits_policy != other.limits_policy
+            || self.scale_mode != other.scale_mode
+            || self.limits_percentile != other.limits_percentile
+            || self.mad_factor != other.mad_factor
             || (self.pan_x - other.pan_x).abs() > 0.5
             || (self.pan_y - other.pan_y).abs() > 0.5
             || (self.rotation - other.rotation).abs() > 0.01
@@ -187,11 +230,40 @@ impl ViewerApp {
                 js_sys::Reflect::set(&state, &"contrast".into(), &current_state.contrast.into()).ok();
                 js_sys::Reflect::set(&state, &"bias".into(), &current_state.bias.into()).ok();
                 js_sys::Reflect::set(&state, &"stretchMode".into(), &current_state.stretch_mode.clone().into()).ok();
+                js_sys::Reflect::set(&state, &"logExponent".into(), &current_state.log_exponent.into()).ok();
+                js_sys::Reflect::set(&state, &"asinhSoftening".into(), &current_state.asinh_softening.into()).ok();
+                js_sys::Reflect::set(&state, &"powerExponent".into(), &current_state.power_exponent.into()).ok();
                 js_sys::Reflect::set(&state, &"zoom".into(), &(current_state.zoom as f64).into()).ok();
                 js_sys::Reflect::set(&state, &"colormap".into(), &current_state.colormap.clone().into()).ok();
                 js_sys::Reflect::set(&state, &"colormapReversed".into(), &current_state.colormap_reversed.into()).ok();
+                js_sys::Reflect::set(&state, &"colormapLevels".into(), &current_state.colormap_levels.into()).ok();
+                js_sys::Reflect::set(&state, &"complexComponent".into(), &current_state.complex_component.clone().into()).ok();
+                js_sys::Reflect::set(&state, &"activeChannel".into(), &(current_state.active_channel as u32).into()).ok();
                 js_sys::Reflect::set(&state, &"vmin".into(), &current_state.vmin.into()).ok();
                 js_sys::Reflect::set(&state, &"vmax".into(), &current_state.vmax.into()).ok();
+                js_sys::Reflect::set(&state, &"limitsPolicy".into(), &current_state.limits_policy.clone().into()).ok();
+                js_sys::Reflect::set(&state, &"scaleMode".into(), &current_state.scale_mode.clone().into()).ok();
+                js_sys::Reflect::set(&state, &"limitsPercentile".into(), &current_state.limits_percentile.into()).ok();
+                js_sys::Reflect::set(&state, &"madFactor".into(), &current_state.mad_factor.into()).ok();
+
+                // Include the cube slice if a cube is loaded
+                if let Some(slice) = current_state.slice {
+                    js_sys::Reflect::set(&state, &"slice".into(), &(slice as u32).into()).ok();
+                    js_sys::Reflect::set(&state, &"sliceLimits".into(), &current_state.slice_limits.clone().into()).ok();
+                    js_sys::Reflect::set(&state, &"playing".into(), &current_state.playing.into()).ok();
+                }
+                if let Some((y_axis, x_axis)) = current_state.display_axes {
+                    // Index along every axis, null for the displayed ones
+                    let index: js_sys::Array = current_state
+                        .axis_index
+                        .iter()
+                        .enumerate()
+                        .map(|(axis, &i)| if axis == y_axis || axis == x_axis { JsValue::NULL } else { JsValue::from(i as u32) })
+                        .collect();
+                    js_sys::Reflect::set(&state, &"axisIndex".into(), &index).ok();
+                    let display_axes = js_sys::Array::of2(&(y_axis as u32).into(), &(x_axis as u32).into());
+                    js_sys::Reflect::set(&state, &"displayAxes".into(), &display_axes).ok();
+                }
                 
                 // Include rotation state
                 js_sys::Reflect::set(&state, &"rotation".into(), &(current_state.rotation as f64).into()).ok();
@@ -225,6 +297,56 @@ impl ViewerApp {
     }
 }
 
+impl ViewerApp {
+    /// Call the click callback if the user clicked on the image this frame
+    fn notify_click(&mut self) {
+        // Take the click while holding the borrow, then drop it before calling JS
+        let Some((x, y, values)) = self.widget.borrow_mut().take_click() else {
+            return;
+        };
+        let is_rgb = self.widget.borrow().is_rgb();
+        let slice = self.widget.borrow().is_cube().then(|| self.widget.borrow().slice_index());
+        let index = self.widget.borrow().cube_index_at(x, y);
+
+        if let Some(ref callback) = self.callbacks.borrow().on_click {
+            let event = js_sys::Object::new();
+            js_sys::Reflect::set(&event, &"x".into(), &x.into()).ok();
+            js_sys::Reflect::set(&event, &"y".into(), &y.into()).ok();
+            if let Some(slice) = slice {
+                js_sys::Reflect::set(&event, &"slice".into(), &(slice as u32).into()).ok();
+            }
+            if let Some(index) = index {
+                let index: js_sys::Array = index.iter().map(|&i| JsValue::from(i as u32)).collect();
+                js_sys::Reflect::set(&event, &"index".into(), &index).ok();
+            }
+            if is_rgb {
+                let channels: js_sys::Array = values.iter().map(|&v| pixel_value_to_js(v)).collect();
+                js_sys::Reflect::set(&event, &"channels".into(), &channels).ok();
+            } else if let Some(&value) = values.first() {
+                js_sys::Reflect::set(&event, &"value".into(), &pixel_value_to_js(value)).ok();
+            }
+
+            let this = JsValue::NULL;
+            let _ = callback.call1(&this, &event);
+        }
+    }
+}
+
+/// Convert a pixel value to a JS number ([re, im] for complex values)
+fn pixel_value_to_js(value: PixelValue) -> JsValue {
+    match value {
+        PixelValue::Int(x) => (x as f64).into(),
+        PixelValue::UInt(x) => (x as f64).into(),
+        PixelValue::Float(x) => x.into(),
+        PixelValue::Complex(re, im) => {
+            let pair = js_sys::Array::new();
+            pair.push(&re.into());
+            pair.push(&im.into());
+            pair.into()
+        }
+    }
+}
+
 impl eframe::App for ViewerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Use a CentralPanel with no margin/padding
@@ -239,7 +361,8 @@ impl eframe::App for ViewerApp {
             widget.show(ui, container_size);
         });
 
-        // Check for state changes and notify JavaScript
+        // Check for clicks and state changes and notify JavaScript
+        self.notify_click();
         self.check_and_notify_state_change();
 
         // Request continuous repaints for smooth updates
@@ -1,23 +1,62 @@
 //! Colormap definitions and utilities
 //!
-//! Contains matplotlib-compatible colormaps exported as lookup tables.
+//! Contains matplotlib, cmocean and DS9 colormaps as lookup tables, generated
+//! at build time from the data files in `colormaps/`, plus colormaps registered
+//! at runtime from control points.
+
+use std::cell::RefCell;
 
 use egui::Color32;
 
 // Import generated lookup tables
-use crate::colormap_luts::{INFERNO_LUT, MAGMA_LUT, RDBU_LUT, RDYLBU_LUT};
+use crate::colormap_luts::*;
 
 /// Available colormap types
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum Colormap {
     #[default]
     Grayscale,
+    Viridis,
+    Plasma,
     Inferno,
     Magma,
-    /// Diverging colormap, only available in symmetric mode
+    Cividis,
+    Turbo,
+    Cubehelix,
+    /// cmocean's thermal
+    Thermal,
+    /// DS9's heat
+    Heat,
+    /// DS9's cool
+    Cool,
+    /// DS9's sls
+    Sls,
+    /// DS9's bb
+    Bb,
+    /// DS9's a
+    A,
+    /// DS9's b
+    B,
+    /// Diverging colormap, suited to symmetric mode
     RdBu,
-    /// Diverging colormap, only available in symmetric mode
+    /// Diverging colormap, suited to symmetric mode
     RdYlBu,
+    /// cmocean's balance, suited to symmetric mode
+    Balance,
+    /// cmocean's curl, suited to symmetric mode
+    Curl,
+    /// Cyclic hue wheel, used for phase/angle data
+    Hsv,
+    /// Cyclic, used for phase/angle data
+    Twilight,
+    /// Qualitative, for label images
+    Tab10,
+    /// Qualitative, for label images
+    Tab20,
+    /// Qualitative with 256 distinct colors, for label images
+    Glasbey,
+    /// Registered at runtime with `register_colormap`
+    Custom(&'static CustomColormap),
 }
 
 impl Colormap {
@@ -25,48 +64,567 @@ impl Colormap {
     pub fn name(&self) -> &'static str {
         match self {
             Colormap::Grayscale => "Gray",
+            Colormap::Viridis => "Viridis",
+            Colormap::Plasma => "Plasma",
             Colormap::Inferno => "Inferno",
             Colormap::Magma => "Magma",
+            Colormap::Cividis => "Cividis",
+            Colormap::Turbo => "Turbo",
+            Colormap::Cubehelix => "Cubehelix",
+            Colormap::Thermal => "Thermal",
+            Colormap::Heat => "Heat",
+            Colormap::Cool => "Cool",
+            Colormap::Sls => "SLS",
+            Colormap::Bb => "BB",
+            Colormap::A => "A",
+            Colormap::B => "B",
             Colormap::RdBu => "RdBu",
             Colormap::RdYlBu => "RdYlBu",
+            Colormap::Balance => "Balance",
+            Colormap::Curl => "Curl",
+            Colormap::Hsv => "HSV",
+            Colormap::Twilight => "Twilight",
+            Colormap::Tab10 => "Tab10",
+            Colormap::Tab20 => "Tab20",
+            Colormap::Glasbey => "Glasbey",
+            Colormap::Custom(custom) => &custom.name,
+        }
+    }
+
+    /// Get the kind of colormap, which decides how values are mapped to it
+    pub fn kind(&self) -> ColormapKind {
+        match self {
+            Colormap::RdBu | Colormap::RdYlBu | Colormap::Balance | Colormap::Curl => ColormapKind::Diverging,
+            Colormap::Hsv | Colormap::Twilight => ColormapKind::Cyclic,
+            Colormap::Tab10 | Colormap::Tab20 | Colormap::Glasbey => ColormapKind::Qualitative,
+            Colormap::Custom(custom) => custom.kind,
+            _ => ColormapKind::Sequential,
         }
     }
 
-    /// Check if this is a diverging colormap (requires symmetric mode)
-    pub fn is_diverging(&self) -> bool {
-        matches!(self, Colormap::RdBu | Colormap::RdYlBu)
+    /// Check if this is a cyclic colormap (first and last colors match)
+    pub fn is_cyclic(&self) -> bool {
+        self.kind() == ColormapKind::Cyclic
+    }
+
+    /// Check if this is a qualitative colormap (distinct colors for integer labels)
+    pub fn is_qualitative(&self) -> bool {
+        self.kind() == ColormapKind::Qualitative
     }
 
-    /// Get all non-diverging colormaps
+    /// Get sequential colormaps
     pub fn standard_colormaps() -> &'static [Colormap] {
-        &[Colormap::Grayscale, Colormap::Inferno, Colormap::Magma]
+        &[
+            Colormap::Grayscale,
+            Colormap::Viridis,
+            Colormap::Plasma,
+            Colormap::Inferno,
+            Colormap::Magma,
+            Colormap::Cividis,
+            Colormap::Turbo,
+            Colormap::Cubehelix,
+            Colormap::Thermal,
+            Colormap::Heat,
+            Colormap::Cool,
+            Colormap::Sls,
+            Colormap::Bb,
+            Colormap::A,
+            Colormap::B,
+        ]
     }
 
     /// Get diverging colormaps (for symmetric mode)
     pub fn diverging_colormaps() -> &'static [Colormap] {
-        &[Colormap::RdBu, Colormap::RdYlBu]
+        &[Colormap::RdBu, Colormap::RdYlBu, Colormap::Balance, Colormap::Curl]
+    }
+
+    /// Get cyclic colormaps (for phase display)
+    pub fn cyclic_colormaps() -> &'static [Colormap] {
+        &[Colormap::Hsv, Colormap::Twilight]
+    }
+
+    /// Get qualitative colormaps (for label images)
+    pub fn qualitative_colormaps() -> &'static [Colormap] {
+        &[Colormap::Tab10, Colormap::Tab20, Colormap::Glasbey]
+    }
+
+    /// Get registered colormaps, in registration order
+    pub fn custom_colormaps() -> Vec<Colormap> {
+        REGISTRY.with(|registry| registry.borrow().iter().map(|&custom| Colormap::Custom(custom)).collect())
+    }
+
+    /// Get all built-in colormaps followed by the registered ones
+    pub fn all() -> Vec<Colormap> {
+        let builtin = [
+            Colormap::standard_colormaps(),
+            Colormap::diverging_colormaps(),
+            Colormap::cyclic_colormaps(),
+            Colormap::qualitative_colormaps(),
+        ];
+        builtin.into_iter().flatten().copied().chain(Colormap::custom_colormaps()).collect()
     }
 
-    /// Map a normalized value (0-1) to a color
+    /// Find a built-in or registered colormap by its display name, ignoring case
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        Colormap::all().into_iter().find(|cmap| cmap.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Like `from_name`, with an error listing the known colormaps
+    pub fn parse(name: &str) -> Result<Colormap, String> {
+        Colormap::from_name(name).ok_or_else(|| {
+            let names: Vec<&str> = Colormap::all().iter().map(|cmap| cmap.name()).collect();
+            format!("Unknown colormap \"{}\". Expected one of: {}", name, names.join(", "))
+        })
+    }
+
+    /// Map a normalized value (0-1) to a color. Cyclic colormaps wrap values
+    /// outside 0-1 around, the others clamp them; qualitative colormaps are
+    /// split into equal bands, one per color.
     pub fn map(&self, t: f64) -> Color32 {
-        let t = t.clamp(0.0, 1.0);
+        if let Some(colors) = self.qualitative_colors() {
+            let idx = (t.clamp(0.0, 1.0) * colors.len() as f64) as usize;
+            return rgb_color(colors[idx.min(colors.len() - 1)]);
+        }
+        let t = if self.is_cyclic() { t.rem_euclid(1.0) } else { t.clamp(0.0, 1.0) };
         match self {
             Colormap::Grayscale => {
-                let v = (t * 255.0) as u8;
+                let v = (t * 255.0).round() as u8;
                 Color32::from_rgb(v, v, v)
             }
+            Colormap::Viridis => sample_lut(&VIRIDIS_LUT, t),
+            Colormap::Plasma => sample_lut(&PLASMA_LUT, t),
             Colormap::Inferno => sample_lut(&INFERNO_LUT, t),
             Colormap::Magma => sample_lut(&MAGMA_LUT, t),
+            Colormap::Cividis => sample_lut(&CIVIDIS_LUT, t),
+            Colormap::Turbo => sample_lut(&TURBO_LUT, t),
+            Colormap::Cubehelix => sample_lut(&CUBEHELIX_LUT, t),
+            Colormap::Thermal => sample_lut(&THERMAL_LUT, t),
+            Colormap::Heat => sample_lut(&HEAT_LUT, t),
+            Colormap::Cool => sample_lut(&COOL_LUT, t),
+            Colormap::Sls => sample_lut(&SLS_LUT, t),
+            Colormap::Bb => sample_lut(&BB_LUT, t),
+            Colormap::A => sample_lut(&A_LUT, t),
+            Colormap::B => sample_lut(&B_LUT, t),
             Colormap::RdBu => sample_lut(&RDBU_LUT, t),
             Colormap::RdYlBu => sample_lut(&RDYLBU_LUT, t),
+            Colormap::Balance => sample_lut(&BALANCE_LUT, t),
+            Colormap::Curl => sample_lut(&CURL_LUT, t),
+            Colormap::Hsv => hue_wheel(t),
+            Colormap::Twilight => sample_lut(&TWILIGHT_LUT, t),
+            Colormap::Tab10 | Colormap::Tab20 | Colormap::Glasbey => unreachable!("qualitative colormap"),
+            Colormap::Custom(custom) => sample_lut(&custom.lut, t),
+        }
+    }
+
+    /// Color of an integer label. Qualitative colormaps cycle through their
+    /// colors; the others treat the label as a normalized value.
+    pub fn label_color(&self, label: i64) -> Color32 {
+        match self.qualitative_colors() {
+            Some(colors) => rgb_color(colors[label.rem_euclid(colors.len() as i64) as usize]),
+            None => self.map(label as f64),
+        }
+    }
+
+    /// The distinct colors of a qualitative colormap
+    fn qualitative_colors(&self) -> Option<&'static [[u8; 3]]> {
+        match self {
+            Colormap::Tab10 => Some(&TAB10_LUT),
+            Colormap::Tab20 => Some(&TAB20_LUT),
+            Colormap::Glasbey => Some(&GLASBEY_LUT),
+            Colormap::Custom(custom) if custom.kind == ColormapKind::Qualitative => Some(&custom.lut),
+            _ => None,
+        }
+    }
+}
+
+/// How values are mapped to a colormap
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColormapKind {
+    /// Ordered colors for values between the limits
+    #[default]
+    Sequential,
+    /// Ordered colors diverging from a midpoint, for symmetric mode
+    Diverging,
+    /// Ordered colors whose ends meet, for phase/angle data; values wrap around
+    Cyclic,
+    /// Unordered distinct colors, one per integer label, without stretching
+    Qualitative,
+}
+
+impl ColormapKind {
+    /// Get the kind name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColormapKind::Sequential => "sequential",
+            ColormapKind::Diverging => "diverging",
+            ColormapKind::Cyclic => "cyclic",
+            ColormapKind::Qualitative => "qualitative",
+        }
+    }
+
+    /// Parse a kind name: "sequential", "diverging", "cyclic" or "qualitative"
+    pub fn parse(name: &str) -> Result<ColormapKind, String> {
+        match name {
+            "sequential" => Ok(ColormapKind::Sequential),
+            "diverging" => Ok(ColormapKind::Diverging),
+            "cyclic" => Ok(ColormapKind::Cyclic),
+            "qualitative" => Ok(ColormapKind::Qualitative),
+            _ => Err(format!(
+                "Unknown colormap kind \"{}\". Expected \"sequential\", \"diverging\", \"cyclic\" or \"qualitative\"",
+                name
+            )),
+        }
+    }
+}
+
+/// Default number of levels when a colormap is made discrete
+pub const DEFAULT_COLORMAP_LEVELS: u32 = 8;
+/// Maximum number of discrete colormap levels
+pub const MAX_COLORMAP_LEVELS: u32 = 256;
+
+/// Snap a normalized value (0-1) to the nearest lower of `levels` evenly
+/// spaced levels, spanning the whole colormap from 0 to 1
+pub fn quantize(t: f64, levels: u32) -> f64 {
+    let top = (levels - 1) as f64;
+    (t * levels as f64).floor().clamp(0.0, top) / top
+}
+
+/// A colormap registered at runtime
+#[derive(Debug, PartialEq, Eq)]
+pub struct CustomColormap {
+    name: String,
+    /// 256 interpolated entries, or the listed colors of a qualitative colormap
+    lut: Vec<[u8; 3]>,
+    kind: ColormapKind,
+}
+
+thread_local! {
+    /// Registered colormaps. Each lives for the rest of the program (like the
+    /// built-in tables) so `Colormap` stays `Copy`; registering a name again
+    /// replaces it in the picker, while viewers already using the old table
+    /// keep it until another colormap is chosen.
+    static REGISTRY: RefCell<Vec<&'static CustomColormap>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Color space in which colors between control points are interpolated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// Straight between gamma-encoded sRGB values
+    #[default]
+    Srgb,
+    /// Between linear-light RGB values, which keeps blends bright
+    LinearRgb,
+    /// In the perceptual OKLab space, which keeps lightness steps even
+    Oklab,
+}
+
+impl ColorSpace {
+    /// Parse a color space name: "srgb", "linear" or "oklab"
+    pub fn parse(name: &str) -> Result<ColorSpace, String> {
+        match name {
+            "srgb" => Ok(ColorSpace::Srgb),
+            "linear" => Ok(ColorSpace::LinearRgb),
+            "oklab" => Ok(ColorSpace::Oklab),
+            _ => Err(format!(
+                "Unknown color space \"{}\". Expected \"srgb\", \"linear\" or \"oklab\"",
+                name
+            )),
+        }
+    }
+
+    /// Convert an sRGB color (0-1 per channel) into this space
+    fn encode(self, rgb: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Srgb => rgb,
+            ColorSpace::LinearRgb => rgb.map(srgb_to_linear),
+            ColorSpace::Oklab => linear_to_oklab(rgb.map(srgb_to_linear)),
+        }
+    }
+
+    /// Convert a color in this space back to sRGB (0-1 per channel)
+    fn decode(self, color: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Srgb => color,
+            ColorSpace::LinearRgb => color.map(linear_to_srgb),
+            ColorSpace::Oklab => oklab_to_linear(color).map(linear_to_srgb),
         }
     }
 }
 
-/// Sample a 256-entry lookup table
-fn sample_lut(lut: &[[u8; 3]; 256], t: f64) -> Color32 {
-    let idx = (t * 255.0) as usize;
-    let idx = idx.min(255);
-    let rgb = lut[idx];
-    Color32::from_rgb(rgb[0], rgb[1], rgb[2])
+/// Register a colormap under `name` from control points (position 0-1, sRGB
+/// color 0-255) with positions increasing from 0 to 1. Colors in between are
+/// interpolated in `space`, except for qualitative colormaps, which use the
+/// colors as listed. `kind` decides where the colormap is listed in the picker.
+/// A registered colormap whose name differs only in case is replaced.
+pub fn register_colormap(
+    name: &str,
+    points: &[(f64, [f64; 3])],
+    space: ColorSpace,
+    kind: ColormapKind,
+) -> Result<Colormap, String> {
+    if name.is_empty() {
+        return Err("Colormap name must not be empty".to_string());
+    }
+    let is_builtin = Colormap::from_name(name).is_some_and(|cmap| !matches!(cmap, Colormap::Custom(_)));
+    if is_builtin {
+        return Err(format!("\"{}\" is a built-in colormap", name));
+    }
+    let lut = build_lut(points, space, kind)?;
+
+    let custom: &'static CustomColormap = Box::leak(Box::new(CustomColormap { name: name.to_string(), lut, kind }));
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        match registry.iter_mut().find(|existing| existing.name.eq_ignore_ascii_case(name)) {
+            Some(existing) => *existing = custom,
+            None => registry.push(custom),
+        }
+    });
+    Ok(Colormap::Custom(custom))
+}
+
+/// Build a 256-entry lookup table from control points, interpolating in `space`
+/// (or just the listed colors for a qualitative colormap)
+fn build_lut(points: &[(f64, [f64; 3])], space: ColorSpace, kind: ColormapKind) -> Result<Vec<[u8; 3]>, String> {
+    if points.len() < 2 {
+        return Err(format!("A colormap needs at least 2 colors, got {}", points.len()));
+    }
+    let in_order = points.windows(2).all(|w| w[0].0 <= w[1].0);
+    if !in_order || points[0].0 != 0.0 || points[points.len() - 1].0 != 1.0 {
+        return Err("Control point positions must increase from 0 to 1".to_string());
+    }
+    if points.iter().flat_map(|(_, rgb)| rgb).any(|v| !(0.0..=255.0).contains(v)) {
+        return Err("Colormap colors must be 0-255 per channel".to_string());
+    }
+    if kind == ColormapKind::Qualitative {
+        return Ok(points.iter().map(|(_, rgb)| rgb.map(|v| v.round() as u8)).collect());
+    }
+
+    let converted: Vec<(f64, [f64; 3])> =
+        points.iter().map(|&(x, rgb)| (x, space.encode(rgb.map(|v| v / 255.0)))).collect();
+    let mut lut = vec![[0u8; 3]; 256];
+    for (i, entry) in lut.iter_mut().enumerate() {
+        let t = i as f64 / 255.0;
+        let k = converted[1..].iter().position(|&(x, _)| x >= t).unwrap_or(converted.len() - 2);
+        let ((x0, c0), (x1, c1)) = (converted[k], converted[k + 1]);
+        let f = if x1 > x0 { ((t - x0) / (x1 - x0)).clamp(0.0, 1.0) } else { 1.0 };
+        let color = space.decode([0, 1, 2].map(|c| c0[c] + (c1[c] - c0[c]) * f));
+        *entry = color.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+    Ok(lut)
+}
+
+/// Decode the sRGB transfer function (0-1)
+fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encode the sRGB transfer function (0-1)
+fn linear_to_srgb(c: f64) -> f64 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Linear RGB to OKLab (Björn Ottosson, 2020)
+fn linear_to_oklab([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// OKLab to linear RGB
+fn oklab_to_linear([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+/// Fully saturated hue at angle `t` (0-1) around the color wheel
+fn hue_wheel(t: f64) -> Color32 {
+    let h = (t * 6.0) % 6.0;
+    let x = 1.0 - ((h % 2.0) - 1.0).abs();
+    let (r, g, b) = match h as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    Color32::from_rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
+}
+
+/// Convert a lookup table entry to a color
+fn rgb_color([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+/// Sample a lookup table, interpolating linearly between entries
+fn sample_lut(lut: &[[u8; 3]], t: f64) -> Color32 {
+    let x = t * (lut.len() - 1) as f64;
+    let idx = (x as usize).min(lut.len() - 2);
+    let f = x - idx as f64;
+    let [a, b] = [lut[idx], lut[idx + 1]];
+    let lerp = |c: usize| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * f).round() as u8;
+    Color32::from_rgb(lerp(0), lerp(1), lerp(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_luts() {
+        // Full tables are copied as-is, control points land on their entries
+        assert_eq!(INFERNO_LUT[0], [0, 0, 4]);
+        assert_eq!(INFERNO_LUT[255], [252, 255, 164]);
+        assert_eq!(VIRIDIS_LUT[0], [68, 1, 84]);
+        assert_eq!(VIRIDIS_LUT[255], [253, 231, 37]);
+        // Published tables are kept whole, not just their ends
+        assert_eq!(VIRIDIS_LUT[128], [33, 145, 140]);
+        assert_eq!(PLASMA_LUT[128], [204, 71, 120]);
+        assert_eq!(INFERNO_LUT[128], [188, 55, 84]);
+        assert_eq!(MAGMA_LUT[128], [183, 55, 121]);
+        assert_eq!(CIVIDIS_LUT[128], [125, 124, 120]);
+        assert_eq!(TURBO_LUT[128], [164, 252, 60]);
+        assert_eq!(RDBU_LUT[128], [246, 247, 247]);
+        assert_eq!(RDYLBU_LUT[128], [254, 255, 192]);
+        // DS9 heat: red saturates at 0.34 while green ramps linearly
+        assert_eq!(Colormap::Heat.map(0.5), Color32::from_rgb(255, 128, 0));
+        // Cyclic maps end where they start
+        for &cmap in Colormap::cyclic_colormaps() {
+            assert_eq!(cmap.map(0.0), cmap.map(1.0), "{}", cmap.name());
+        }
+    }
+
+    #[test]
+    fn test_lut_sampling() {
+        // Half-way between two entries blends them instead of truncating
+        let lut = [[0, 0, 0], [100, 200, 50], [255, 255, 255]];
+        assert_eq!(sample_lut(&lut, 0.25), Color32::from_rgb(50, 100, 25));
+        assert_eq!(sample_lut(&lut, 0.5), Color32::from_rgb(100, 200, 50));
+        assert_eq!(sample_lut(&lut, 1.0), Color32::WHITE);
+        // Formula-generated maps get high-resolution tables
+        assert_eq!(CUBEHELIX_LUT.len(), 4096);
+        assert_eq!(Colormap::Cubehelix.map(0.0), Color32::BLACK);
+        assert_eq!(Colormap::Cubehelix.map(1.0), Color32::WHITE);
+    }
+
+    #[test]
+    fn test_quantize() {
+        assert_eq!(quantize(0.0, 4), 0.0);
+        assert_eq!(quantize(0.24, 4), 0.0);
+        assert_eq!(quantize(0.26, 4), 1.0 / 3.0);
+        assert_eq!(quantize(0.99, 4), 1.0);
+        assert_eq!(quantize(1.0, 4), 1.0);
+        assert_eq!(quantize(0.5, 2), 1.0);
+    }
+
+    #[test]
+    fn test_register_colormap() {
+        let points = [(0.0, [0.0, 0.0, 0.0]), (1.0, [255.0, 255.0, 255.0])];
+        let gray = register_colormap("house gray", &points, ColorSpace::Srgb, ColormapKind::Sequential).unwrap();
+        assert_eq!(Colormap::from_name("house gray"), Some(gray));
+        assert_eq!(gray.map(0.5), Color32::from_rgb(128, 128, 128));
+        assert_eq!(gray.kind(), ColormapKind::Sequential);
+
+        // Linear-light blends of black and white are brighter mid-way
+        let linear = register_colormap("house gray", &points, ColorSpace::LinearRgb, ColormapKind::Diverging).unwrap();
+        assert!(linear.map(0.5).r() > 180);
+        assert_eq!(linear.kind(), ColormapKind::Diverging);
+        assert_eq!(Colormap::from_name("house gray"), Some(linear));
+        assert_eq!(Colormap::custom_colormaps().iter().filter(|c| c.name() == "house gray").count(), 1);
+
+        // OKLab round-trips its end points and keeps gray neutral
+        let oklab = register_colormap("oklab gray", &points, ColorSpace::Oklab, ColormapKind::Sequential).unwrap();
+        assert_eq!(oklab.map(0.0), Color32::BLACK);
+        assert_eq!(oklab.map(1.0), Color32::WHITE);
+        let mid = oklab.map(0.5);
+        assert!(mid.r().abs_diff(mid.g()) <= 1 && mid.g().abs_diff(mid.b()) <= 1);
+    }
+
+    #[test]
+    fn test_register_colormap_errors() {
+        let register = |name, points: &[_]| register_colormap(name, points, ColorSpace::Srgb, ColormapKind::Sequential);
+        let points = [(0.0, [0.0, 0.0, 0.0]), (1.0, [255.0, 255.0, 255.0])];
+        assert!(register("Viridis", &points).unwrap_err().contains("built-in"));
+        assert!(register("", &points).is_err());
+        assert!(register("x", &points[..1]).unwrap_err().contains("at least 2"));
+        let reversed = [points[1], points[0]];
+        assert!(register("x", &reversed).unwrap_err().contains("increase"));
+        let bright = [(0.0, [0.0, 0.0, 0.0]), (1.0, [256.0, 0.0, 0.0])];
+        assert!(register("x", &bright).unwrap_err().contains("0-255"));
+        assert!(ColorSpace::parse("hsl").is_err());
+        assert!(ColormapKind::parse("categorical").is_err());
+        assert_eq!(Colormap::from_name("x"), None);
+    }
+
+    #[test]
+    fn test_colormap_kinds() {
+        assert_eq!(Colormap::Viridis.kind(), ColormapKind::Sequential);
+        assert_eq!(Colormap::RdBu.kind(), ColormapKind::Diverging);
+        assert_eq!(Colormap::Twilight.kind(), ColormapKind::Cyclic);
+        assert_eq!(Colormap::Tab10.kind(), ColormapKind::Qualitative);
+        assert_eq!(ColormapKind::parse(ColormapKind::Cyclic.name()), Ok(ColormapKind::Cyclic));
+
+        // Cyclic maps wrap around, the others clamp
+        assert_eq!(Colormap::Hsv.map(1.25), Colormap::Hsv.map(0.25));
+        assert_eq!(Colormap::Twilight.map(-0.25), Colormap::Twilight.map(0.75));
+        assert_eq!(Colormap::Viridis.map(1.25), Colormap::Viridis.map(1.0));
+
+        // Labels cycle through the distinct colors, normalized values pick bands
+        let [r, g, b] = TAB10_LUT[3];
+        assert_eq!(Colormap::Tab10.label_color(3), Color32::from_rgb(r, g, b));
+        assert_eq!(Colormap::Tab10.label_color(13), Colormap::Tab10.label_color(3));
+        assert_eq!(Colormap::Tab10.label_color(-7), Colormap::Tab10.label_color(3));
+        assert_eq!(Colormap::Tab10.map(0.35), Colormap::Tab10.label_color(3));
+        assert_eq!(Colormap::Tab10.map(1.0), Colormap::Tab10.label_color(9));
+        assert_eq!(GLASBEY_LUT.len(), 256);
+        for (i, a) in GLASBEY_LUT.iter().enumerate() {
+            assert!(!GLASBEY_LUT[i + 1..].contains(a));
+        }
+
+        // Qualitative custom maps keep their colors as listed
+        let points = [(0.0, [255.0, 0.0, 0.0]), (0.5, [0.0, 255.0, 0.0]), (1.0, [0.0, 0.0, 255.0])];
+        let rgb = register_colormap("rgb labels", &points, ColorSpace::Oklab, ColormapKind::Qualitative).unwrap();
+        assert!(rgb.is_qualitative());
+        assert_eq!(rgb.label_color(4), Color32::from_rgb(0, 255, 0));
+        assert_eq!(rgb.map(0.1), Color32::RED);
+    }
+
+    #[test]
+    fn test_colormap_lookup() {
+        assert_eq!(Colormap::from_name("viridis"), Some(Colormap::Viridis));
+        assert_eq!(Colormap::parse("RDBU"), Ok(Colormap::RdBu));
+        assert_eq!(Colormap::parse("gray"), Ok(Colormap::Grayscale));
+        for cmap in Colormap::all() {
+            assert_eq!(Colormap::parse(cmap.name()), Ok(cmap));
+        }
+        let err = Colormap::parse("jet").unwrap_err();
+        assert!(err.contains("Unknown colormap \"jet\"") && err.contains("Viridis, Plasma"));
+
+        // Registered colormaps are found the same way, and case-only renames replace them
+        let points = [(0.0, [0.0, 0.0, 0.0]), (1.0, [0.0, 0.0, 255.0])];
+        register_colormap("Deep Blue", &points, ColorSpace::Srgb, ColormapKind::Sequential).unwrap();
+        let replaced = register_colormap("deep blue", &points, ColorSpace::Srgb, ColormapKind::Sequential).unwrap();
+        assert_eq!(Colormap::parse("DEEP BLUE"), Ok(replaced));
+        assert!(register_colormap("VIRIDIS", &points, ColorSpace::Srgb, ColormapKind::Sequential).is_err());
+    }
 }
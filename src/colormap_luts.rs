@@ -1,1041 +1,5 @@
-// Auto-generated by generate_colormaps.py
-// Do not edit manually - regenerate with:
-//   .venv/bin/python viewarr/generate_colormaps.py
+//! Colormap lookup tables, generated by build.rs from `colormaps/*.txt`
 
 #![allow(clippy::unreadable_literal)]
 
-pub const INFERNO_LUT: [[u8; 3]; 256] = [
-    [  0,   0,   4],
-    [  1,   0,   5],
-    [  1,   1,   6],
-    [  1,   1,   8],
-    [  2,   1,  10],
-    [  2,   2,  12],
-    [  2,   2,  14],
-    [  3,   2,  16],
-    [  4,   3,  18],
-    [  4,   3,  20],
-    [  5,   4,  23],
-    [  6,   4,  25],
-    [  7,   5,  27],
-    [  8,   5,  29],
-    [  9,   6,  31],
-    [ 10,   7,  34],
-    [ 11,   7,  36],
-    [ 12,   8,  38],
-    [ 13,   8,  41],
-    [ 14,   9,  43],
-    [ 16,   9,  45],
-    [ 17,  10,  48],
-    [ 18,  10,  50],
-    [ 20,  11,  52],
-    [ 21,  11,  55],
-    [ 22,  11,  57],
-    [ 24,  12,  60],
-    [ 25,  12,  62],
-    [ 27,  12,  65],
-    [ 28,  12,  67],
-    [ 30,  12,  69],
-    [ 31,  12,  72],
-    [ 33,  12,  74],
-    [ 35,  12,  76],
-    [ 36,  12,  79],
-    [ 38,  12,  81],
-    [ 40,  11,  83],
-    [ 41,  11,  85],
-    [ 43,  11,  87],
-    [ 45,  11,  89],
-    [ 47,  10,  91],
-    [ 49,  10,  92],
-    [ 50,  10,  94],
-    [ 52,  10,  95],
-    [ 54,   9,  97],
-    [ 56,   9,  98],
-    [ 57,   9,  99],
-    [ 59,   9, 100],
-    [ 61,   9, 101],
-    [ 62,   9, 102],
-    [ 64,  10, 103],
-    [ 66,  10, 104],
-    [ 68,  10, 104],
-    [ 69,  10, 105],
-    [ 71,  11, 106],
-    [ 73,  11, 106],
-    [ 74,  12, 107],
-    [ 76,  12, 107],
-    [ 77,  13, 108],
-    [ 79,  13, 108],
-    [ 81,  14, 108],
-    [ 82,  14, 109],
-    [ 84,  15, 109],
-    [ 85,  15, 109],
-    [ 87,  16, 110],
-    [ 89,  16, 110],
-    [ 90,  17, 110],
-    [ 92,  18, 110],
-    [ 93,  18, 110],
-    [ 95,  19, 110],
-    [ 97,  19, 110],
-    [ 98,  20, 110],
-    [100,  21, 110],
-    [101,  21, 110],
-    [103,  22, 110],
-    [105,  22, 110],
-    [106,  23, 110],
-    [108,  24, 110],
-    [109,  24, 110],
-    [111,  25, 110],
-    [113,  25, 110],
-    [114,  26, 110],
-    [116,  26, 110],
-    [117,  27, 110],
-    [119,  28, 109],
-    [120,  28, 109],
-    [122,  29, 109],
-    [124,  29, 109],
-    [125,  30, 109],
-    [127,  30, 108],
-    [128,  31, 108],
-    [130,  32, 108],
-    [132,  32, 107],
-    [133,  33, 107],
-    [135,  33, 107],
-    [136,  34, 106],
-    [138,  34, 106],
-    [140,  35, 105],
-    [141,  35, 105],
-    [143,  36, 105],
-    [144,  37, 104],
-    [146,  37, 104],
-    [147,  38, 103],
-    [149,  38, 103],
-    [151,  39, 102],
-    [152,  39, 102],
-    [154,  40, 101],
-    [155,  41, 100],
-    [157,  41, 100],
-    [159,  42,  99],
-    [160,  42,  99],
-    [162,  43,  98],
-    [163,  44,  97],
-    [165,  44,  96],
-    [166,  45,  96],
-    [168,  46,  95],
-    [169,  46,  94],
-    [171,  47,  94],
-    [173,  48,  93],
-    [174,  48,  92],
-    [176,  49,  91],
-    [177,  50,  90],
-    [179,  50,  90],
-    [180,  51,  89],
-    [182,  52,  88],
-    [183,  53,  87],
-    [185,  53,  86],
-    [186,  54,  85],
-    [188,  55,  84],
-    [189,  56,  83],
-    [191,  57,  82],
-    [192,  58,  81],
-    [193,  58,  80],
-    [195,  59,  79],
-    [196,  60,  78],
-    [198,  61,  77],
-    [199,  62,  76],
-    [200,  63,  75],
-    [202,  64,  74],
-    [203,  65,  73],
-    [204,  66,  72],
-    [206,  67,  71],
-    [207,  68,  70],
-    [208,  69,  69],
-    [210,  70,  68],
-    [211,  71,  67],
-    [212,  72,  66],
-    [213,  74,  65],
-    [215,  75,  63],
-    [216,  76,  62],
-    [217,  77,  61],
-    [218,  78,  60],
-    [219,  80,  59],
-    [221,  81,  58],
-    [222,  82,  56],
-    [223,  83,  55],
-    [224,  85,  54],
-    [225,  86,  53],
-    [226,  87,  52],
-    [227,  89,  51],
-    [228,  90,  49],
-    [229,  92,  48],
-    [230,  93,  47],
-    [231,  94,  46],
-    [232,  96,  45],
-    [233,  97,  43],
-    [234,  99,  42],
-    [235, 100,  41],
-    [235, 102,  40],
-    [236, 103,  38],
-    [237, 105,  37],
-    [238, 106,  36],
-    [239, 108,  35],
-    [239, 110,  33],
-    [240, 111,  32],
-    [241, 113,  31],
-    [241, 115,  29],
-    [242, 116,  28],
-    [243, 118,  27],
-    [243, 120,  25],
-    [244, 121,  24],
-    [245, 123,  23],
-    [245, 125,  21],
-    [246, 126,  20],
-    [246, 128,  19],
-    [247, 130,  18],
-    [247, 132,  16],
-    [248, 133,  15],
-    [248, 135,  14],
-    [248, 137,  12],
-    [249, 139,  11],
-    [249, 140,  10],
-    [249, 142,   9],
-    [250, 144,   8],
-    [250, 146,   7],
-    [250, 148,   7],
-    [251, 150,   6],
-    [251, 151,   6],
-    [251, 153,   6],
-    [251, 155,   6],
-    [251, 157,   7],
-    [252, 159,   7],
-    [252, 161,   8],
-    [252, 163,   9],
-    [252, 165,  10],
-    [252, 166,  12],
-    [252, 168,  13],
-    [252, 170,  15],
-    [252, 172,  17],
-    [252, 174,  18],
-    [252, 176,  20],
-    [252, 178,  22],
-    [252, 180,  24],
-    [251, 182,  26],
-    [251, 184,  29],
-    [251, 186,  31],
-    [251, 188,  33],
-    [251, 190,  35],
-    [250, 192,  38],
-    [250, 194,  40],
-    [250, 196,  42],
-    [250, 198,  45],
-    [249, 199,  47],
-    [249, 201,  50],
-    [249, 203,  53],
-    [248, 205,  55],
-    [248, 207,  58],
-    [247, 209,  61],
-    [247, 211,  64],
-    [246, 213,  67],
-    [246, 215,  70],
-    [245, 217,  73],
-    [245, 219,  76],
-    [244, 221,  79],
-    [244, 223,  83],
-    [244, 225,  86],
-    [243, 227,  90],
-    [243, 229,  93],
-    [242, 230,  97],
-    [242, 232, 101],
-    [242, 234, 105],
-    [241, 236, 109],
-    [241, 237, 113],
-    [241, 239, 117],
-    [241, 241, 121],
-    [242, 242, 125],
-    [242, 244, 130],
-    [243, 245, 134],
-    [243, 246, 138],
-    [244, 248, 142],
-    [245, 249, 146],
-    [246, 250, 150],
-    [248, 251, 154],
-    [249, 252, 157],
-    [250, 253, 161],
-    [252, 255, 164],
-];
-
-pub const MAGMA_LUT: [[u8; 3]; 256] = [
-    [  0,   0,   4],
-    [  1,   0,   5],
-    [  1,   1,   6],
-    [  1,   1,   8],
-    [  2,   1,   9],
-    [  2,   2,  11],
-    [  2,   2,  13],
-    [  3,   3,  15],
-    [  3,   3,  18],
-    [  4,   4,  20],
-    [  5,   4,  22],
-    [  6,   5,  24],
-    [  6,   5,  26],
-    [  7,   6,  28],
-    [  8,   7,  30],
-    [  9,   7,  32],
-    [ 10,   8,  34],
-    [ 11,   9,  36],
-    [ 12,   9,  38],
-    [ 13,  10,  41],
-    [ 14,  11,  43],
-    [ 16,  11,  45],
-    [ 17,  12,  47],
-    [ 18,  13,  49],
-    [ 19,  13,  52],
-    [ 20,  14,  54],
-    [ 21,  14,  56],
-    [ 22,  15,  59],
-    [ 24,  15,  61],
-    [ 25,  16,  63],
-    [ 26,  16,  66],
-    [ 28,  16,  68],
-    [ 29,  17,  71],
-    [ 30,  17,  73],
-    [ 32,  17,  75],
-    [ 33,  17,  78],
-    [ 34,  17,  80],
-    [ 36,  18,  83],
-    [ 37,  18,  85],
-    [ 39,  18,  88],
-    [ 41,  17,  90],
-    [ 42,  17,  92],
-    [ 44,  17,  95],
-    [ 45,  17,  97],
-    [ 47,  17,  99],
-    [ 49,  17, 101],
-    [ 51,  16, 103],
-    [ 52,  16, 105],
-    [ 54,  16, 107],
-    [ 56,  16, 108],
-    [ 57,  15, 110],
-    [ 59,  15, 112],
-    [ 61,  15, 113],
-    [ 63,  15, 114],
-    [ 64,  15, 116],
-    [ 66,  15, 117],
-    [ 68,  15, 118],
-    [ 69,  16, 119],
-    [ 71,  16, 120],
-    [ 73,  16, 120],
-    [ 74,  16, 121],
-    [ 76,  17, 122],
-    [ 78,  17, 123],
-    [ 79,  18, 123],
-    [ 81,  18, 124],
-    [ 82,  19, 124],
-    [ 84,  19, 125],
-    [ 86,  20, 125],
-    [ 87,  21, 126],
-    [ 89,  21, 126],
-    [ 90,  22, 126],
-    [ 92,  22, 127],
-    [ 93,  23, 127],
-    [ 95,  24, 127],
-    [ 96,  24, 128],
-    [ 98,  25, 128],
-    [100,  26, 128],
-    [101,  26, 128],
-    [103,  27, 128],
-    [104,  28, 129],
-    [106,  28, 129],
-    [107,  29, 129],
-    [109,  29, 129],
-    [110,  30, 129],
-    [112,  31, 129],
-    [114,  31, 129],
-    [115,  32, 129],
-    [117,  33, 129],
-    [118,  33, 129],
-    [120,  34, 129],
-    [121,  34, 130],
-    [123,  35, 130],
-    [124,  35, 130],
-    [126,  36, 130],
-    [128,  37, 130],
-    [129,  37, 129],
-    [131,  38, 129],
-    [132,  38, 129],
-    [134,  39, 129],
-    [136,  39, 129],
-    [137,  40, 129],
-    [139,  41, 129],
-    [140,  41, 129],
-    [142,  42, 129],
-    [144,  42, 129],
-    [145,  43, 129],
-    [147,  43, 128],
-    [148,  44, 128],
-    [150,  44, 128],
-    [152,  45, 128],
-    [153,  45, 128],
-    [155,  46, 127],
-    [156,  46, 127],
-    [158,  47, 127],
-    [160,  47, 127],
-    [161,  48, 126],
-    [163,  48, 126],
-    [165,  49, 126],
-    [166,  49, 125],
-    [168,  50, 125],
-    [170,  51, 125],
-    [171,  51, 124],
-    [173,  52, 124],
-    [174,  52, 123],
-    [176,  53, 123],
-    [178,  53, 123],
-    [179,  54, 122],
-    [181,  54, 122],
-    [183,  55, 121],
-    [184,  55, 121],
-    [186,  56, 120],
-    [188,  57, 120],
-    [189,  57, 119],
-    [191,  58, 119],
-    [192,  58, 118],
-    [194,  59, 117],
-    [196,  60, 117],
-    [197,  60, 116],
-    [199,  61, 115],
-    [200,  62, 115],
-    [202,  62, 114],
-    [204,  63, 113],
-    [205,  64, 113],
-    [207,  64, 112],
-    [208,  65, 111],
-    [210,  66, 111],
-    [211,  67, 110],
-    [213,  68, 109],
-    [214,  69, 108],
-    [216,  69, 108],
-    [217,  70, 107],
-    [219,  71, 106],
-    [220,  72, 105],
-    [222,  73, 104],
-    [223,  74, 104],
-    [224,  76, 103],
-    [226,  77, 102],
-    [227,  78, 101],
-    [228,  79, 100],
-    [229,  80, 100],
-    [231,  82,  99],
-    [232,  83,  98],
-    [233,  84,  98],
-    [234,  86,  97],
-    [235,  87,  96],
-    [236,  88,  96],
-    [237,  90,  95],
-    [238,  91,  94],
-    [239,  93,  94],
-    [240,  95,  94],
-    [241,  96,  93],
-    [242,  98,  93],
-    [242, 100,  92],
-    [243, 101,  92],
-    [244, 103,  92],
-    [244, 105,  92],
-    [245, 107,  92],
-    [246, 108,  92],
-    [246, 110,  92],
-    [247, 112,  92],
-    [247, 114,  92],
-    [248, 116,  92],
-    [248, 118,  92],
-    [249, 120,  93],
-    [249, 121,  93],
-    [249, 123,  93],
-    [250, 125,  94],
-    [250, 127,  94],
-    [250, 129,  95],
-    [251, 131,  95],
-    [251, 133,  96],
-    [251, 135,  97],
-    [252, 137,  97],
-    [252, 138,  98],
-    [252, 140,  99],
-    [252, 142, 100],
-    [252, 144, 101],
-    [253, 146, 102],
-    [253, 148, 103],
-    [253, 150, 104],
-    [253, 152, 105],
-    [253, 154, 106],
-    [253, 155, 107],
-    [254, 157, 108],
-    [254, 159, 109],
-    [254, 161, 110],
-    [254, 163, 111],
-    [254, 165, 113],
-    [254, 167, 114],
-    [254, 169, 115],
-    [254, 170, 116],
-    [254, 172, 118],
-    [254, 174, 119],
-    [254, 176, 120],
-    [254, 178, 122],
-    [254, 180, 123],
-    [254, 182, 124],
-    [254, 183, 126],
-    [254, 185, 127],
-    [254, 187, 129],
-    [254, 189, 130],
-    [254, 191, 132],
-    [254, 193, 133],
-    [254, 194, 135],
-    [254, 196, 136],
-    [254, 198, 138],
-    [254, 200, 140],
-    [254, 202, 141],
-    [254, 204, 143],
-    [254, 205, 144],
-    [254, 207, 146],
-    [254, 209, 148],
-    [254, 211, 149],
-    [254, 213, 151],
-    [254, 215, 153],
-    [254, 216, 154],
-    [253, 218, 156],
-    [253, 220, 158],
-    [253, 222, 160],
-    [253, 224, 161],
-    [253, 226, 163],
-    [253, 227, 165],
-    [253, 229, 167],
-    [253, 231, 169],
-    [253, 233, 170],
-    [253, 235, 172],
-    [252, 236, 174],
-    [252, 238, 176],
-    [252, 240, 178],
-    [252, 242, 180],
-    [252, 244, 182],
-    [252, 246, 184],
-    [252, 247, 185],
-    [252, 249, 187],
-    [252, 251, 189],
-    [252, 253, 191],
-];
-
-pub const RDBU_LUT: [[u8; 3]; 256] = [
-    [103,   0,  31],
-    [106,   1,  31],
-    [109,   2,  32],
-    [112,   3,  32],
-    [115,   4,  33],
-    [118,   5,  33],
-    [121,   6,  34],
-    [124,   7,  34],
-    [127,   8,  35],
-    [129,   8,  35],
-    [132,   9,  36],
-    [135,  10,  36],
-    [138,  11,  37],
-    [141,  12,  37],
-    [144,  13,  38],
-    [147,  14,  38],
-    [150,  15,  39],
-    [153,  16,  39],
-    [156,  17,  39],
-    [159,  18,  40],
-    [162,  19,  40],
-    [165,  20,  41],
-    [168,  21,  41],
-    [171,  22,  42],
-    [174,  23,  42],
-    [177,  24,  43],
-    [179,  25,  44],
-    [180,  28,  45],
-    [182,  31,  46],
-    [183,  34,  48],
-    [184,  37,  49],
-    [186,  40,  50],
-    [187,  42,  52],
-    [189,  45,  53],
-    [190,  48,  54],
-    [191,  51,  56],
-    [193,  54,  57],
-    [194,  56,  58],
-    [196,  59,  60],
-    [197,  62,  61],
-    [198,  65,  62],
-    [200,  68,  64],
-    [201,  71,  65],
-    [203,  73,  66],
-    [204,  76,  68],
-    [206,  79,  69],
-    [207,  82,  70],
-    [208,  85,  72],
-    [210,  88,  73],
-    [211,  90,  74],
-    [213,  93,  76],
-    [214,  96,  77],
-    [215,  99,  79],
-    [216, 101,  81],
-    [218, 104,  83],
-    [219, 107,  85],
-    [220, 110,  87],
-    [221, 112,  89],
-    [222, 115,  92],
-    [223, 118,  94],
-    [225, 120,  96],
-    [226, 123,  98],
-    [227, 126, 100],
-    [228, 128, 102],
-    [229, 131, 104],
-    [230, 134, 106],
-    [232, 137, 108],
-    [233, 139, 110],
-    [234, 142, 112],
-    [235, 145, 114],
-    [236, 147, 116],
-    [238, 150, 119],
-    [239, 153, 121],
-    [240, 156, 123],
-    [241, 158, 125],
-    [242, 161, 127],
-    [243, 164, 129],
-    [244, 166, 131],
-    [245, 168, 134],
-    [245, 170, 137],
-    [245, 172, 139],
-    [246, 175, 142],
-    [246, 177, 145],
-    [246, 179, 148],
-    [247, 181, 150],
-    [247, 183, 153],
-    [247, 185, 156],
-    [248, 187, 158],
-    [248, 189, 161],
-    [248, 191, 164],
-    [249, 194, 167],
-    [249, 196, 169],
-    [249, 198, 172],
-    [250, 200, 175],
-    [250, 202, 177],
-    [251, 204, 180],
-    [251, 206, 183],
-    [251, 208, 185],
-    [252, 211, 188],
-    [252, 213, 191],
-    [252, 215, 194],
-    [253, 217, 196],
-    [253, 219, 199],
-    [253, 220, 201],
-    [253, 221, 203],
-    [252, 222, 205],
-    [252, 223, 207],
-    [252, 224, 208],
-    [252, 226, 210],
-    [251, 227, 212],
-    [251, 228, 214],
-    [251, 229, 216],
-    [251, 230, 218],
-    [250, 231, 220],
-    [250, 232, 222],
-    [250, 233, 223],
-    [250, 234, 225],
-    [249, 235, 227],
-    [249, 237, 229],
-    [249, 238, 231],
-    [249, 239, 233],
-    [249, 240, 235],
-    [248, 241, 237],
-    [248, 242, 239],
-    [248, 243, 240],
-    [248, 244, 242],
-    [247, 245, 244],
-    [247, 246, 246],
-    [246, 247, 247],
-    [245, 246, 247],
-    [243, 245, 246],
-    [242, 245, 246],
-    [240, 244, 246],
-    [239, 243, 245],
-    [237, 242, 245],
-    [236, 242, 245],
-    [234, 241, 245],
-    [233, 240, 244],
-    [231, 240, 244],
-    [230, 239, 244],
-    [228, 238, 244],
-    [227, 237, 243],
-    [225, 237, 243],
-    [224, 236, 243],
-    [222, 235, 242],
-    [221, 235, 242],
-    [219, 234, 242],
-    [218, 233, 242],
-    [216, 233, 241],
-    [215, 232, 241],
-    [213, 231, 241],
-    [212, 230, 241],
-    [210, 230, 240],
-    [209, 229, 240],
-    [207, 228, 239],
-    [204, 226, 239],
-    [202, 225, 238],
-    [199, 224, 237],
-    [197, 223, 236],
-    [194, 221, 236],
-    [192, 220, 235],
-    [189, 219, 234],
-    [187, 218, 234],
-    [184, 216, 233],
-    [182, 215, 232],
-    [179, 214, 232],
-    [177, 213, 231],
-    [174, 211, 230],
-    [172, 210, 229],
-    [169, 209, 229],
-    [167, 208, 228],
-    [165, 206, 227],
-    [162, 205, 227],
-    [160, 204, 226],
-    [157, 203, 225],
-    [155, 201, 224],
-    [152, 200, 224],
-    [150, 199, 223],
-    [147, 198, 222],
-    [144, 196, 221],
-    [141, 194, 220],
-    [138, 192, 219],
-    [135, 190, 218],
-    [132, 188, 217],
-    [129, 186, 216],
-    [126, 184, 215],
-    [123, 182, 214],
-    [120, 180, 213],
-    [117, 178, 212],
-    [113, 176, 211],
-    [110, 174, 210],
-    [107, 172, 209],
-    [104, 171, 208],
-    [101, 169, 207],
-    [ 98, 167, 206],
-    [ 95, 165, 205],
-    [ 92, 163, 203],
-    [ 89, 161, 202],
-    [ 86, 159, 201],
-    [ 82, 157, 200],
-    [ 79, 155, 199],
-    [ 76, 153, 198],
-    [ 73, 151, 197],
-    [ 70, 149, 196],
-    [ 67, 147, 195],
-    [ 66, 145, 194],
-    [ 64, 143, 193],
-    [ 63, 142, 192],
-    [ 62, 140, 191],
-    [ 60, 138, 190],
-    [ 59, 136, 190],
-    [ 58, 135, 189],
-    [ 56, 133, 188],
-    [ 55, 131, 187],
-    [ 54, 129, 186],
-    [ 52, 128, 185],
-    [ 51, 126, 184],
-    [ 50, 124, 183],
-    [ 48, 122, 182],
-    [ 47, 121, 181],
-    [ 46, 119, 181],
-    [ 44, 117, 180],
-    [ 43, 115, 179],
-    [ 42, 113, 178],
-    [ 40, 112, 177],
-    [ 39, 110, 176],
-    [ 38, 108, 175],
-    [ 36, 106, 174],
-    [ 35, 105, 173],
-    [ 34, 103, 172],
-    [ 32, 101, 171],
-    [ 31,  99, 168],
-    [ 30,  97, 165],
-    [ 29,  95, 162],
-    [ 28,  92, 159],
-    [ 27,  90, 156],
-    [ 26,  88, 153],
-    [ 25,  86, 150],
-    [ 24,  84, 147],
-    [ 23,  82, 144],
-    [ 21,  80, 141],
-    [ 20,  78, 138],
-    [ 19,  76, 135],
-    [ 18,  73, 132],
-    [ 17,  71, 129],
-    [ 16,  69, 126],
-    [ 15,  67, 123],
-    [ 14,  65, 121],
-    [ 13,  63, 118],
-    [ 12,  61, 115],
-    [ 10,  59, 112],
-    [  9,  56, 109],
-    [  8,  54, 106],
-    [  7,  52, 103],
-    [  6,  50, 100],
-    [  5,  48,  97],
-];
-
-pub const RDYLBU_LUT: [[u8; 3]; 256] = [
-    [165,   0,  38],
-    [167,   2,  38],
-    [169,   4,  38],
-    [171,   6,  38],
-    [173,   8,  38],
-    [175,   9,  38],
-    [177,  11,  38],
-    [179,  13,  38],
-    [181,  15,  38],
-    [183,  17,  38],
-    [185,  19,  38],
-    [187,  21,  38],
-    [189,  23,  38],
-    [190,  24,  39],
-    [192,  26,  39],
-    [194,  28,  39],
-    [196,  30,  39],
-    [198,  32,  39],
-    [200,  34,  39],
-    [202,  36,  39],
-    [204,  38,  39],
-    [206,  40,  39],
-    [208,  41,  39],
-    [210,  43,  39],
-    [212,  45,  39],
-    [214,  47,  39],
-    [216,  49,  40],
-    [217,  52,  41],
-    [218,  54,  42],
-    [219,  56,  43],
-    [220,  59,  44],
-    [221,  61,  45],
-    [222,  64,  46],
-    [224,  66,  47],
-    [225,  68,  48],
-    [226,  71,  49],
-    [227,  73,  51],
-    [228,  76,  52],
-    [229,  78,  53],
-    [230,  80,  54],
-    [231,  83,  55],
-    [233,  85,  56],
-    [234,  87,  57],
-    [235,  90,  58],
-    [236,  92,  59],
-    [237,  95,  60],
-    [238,  97,  62],
-    [239,  99,  63],
-    [241, 102,  64],
-    [242, 104,  65],
-    [243, 107,  66],
-    [244, 109,  67],
-    [244, 112,  68],
-    [245, 114,  69],
-    [245, 117,  71],
-    [245, 119,  72],
-    [246, 122,  73],
-    [246, 124,  74],
-    [246, 127,  75],
-    [247, 129,  76],
-    [247, 132,  78],
-    [248, 134,  79],
-    [248, 137,  80],
-    [248, 140,  81],
-    [249, 142,  82],
-    [249, 145,  83],
-    [249, 147,  85],
-    [250, 150,  86],
-    [250, 152,  87],
-    [250, 155,  88],
-    [251, 157,  89],
-    [251, 160,  91],
-    [251, 163,  92],
-    [252, 165,  93],
-    [252, 168,  94],
-    [252, 170,  95],
-    [253, 173,  96],
-    [253, 175,  98],
-    [253, 177, 100],
-    [253, 179, 102],
-    [253, 181, 103],
-    [253, 183, 105],
-    [253, 185, 107],
-    [253, 187, 109],
-    [253, 189, 111],
-    [253, 191, 113],
-    [253, 193, 115],
-    [253, 195, 116],
-    [253, 197, 118],
-    [253, 199, 120],
-    [254, 200, 122],
-    [254, 202, 124],
-    [254, 204, 126],
-    [254, 206, 127],
-    [254, 208, 129],
-    [254, 210, 131],
-    [254, 212, 133],
-    [254, 214, 135],
-    [254, 216, 137],
-    [254, 218, 138],
-    [254, 220, 140],
-    [254, 222, 142],
-    [254, 224, 144],
-    [254, 225, 146],
-    [254, 226, 148],
-    [254, 228, 150],
-    [254, 229, 151],
-    [254, 230, 153],
-    [254, 231, 155],
-    [254, 233, 157],
-    [254, 234, 159],
-    [254, 235, 161],
-    [254, 236, 162],
-    [254, 237, 164],
-    [254, 239, 166],
-    [255, 240, 168],
-    [255, 241, 170],
-    [255, 242, 172],
-    [255, 243, 173],
-    [255, 245, 175],
-    [255, 246, 177],
-    [255, 247, 179],
-    [255, 248, 181],
-    [255, 250, 183],
-    [255, 251, 185],
-    [255, 252, 186],
-    [255, 253, 188],
-    [255, 254, 190],
-    [254, 255, 192],
-    [253, 254, 194],
-    [252, 254, 197],
-    [251, 253, 199],
-    [250, 253, 201],
-    [248, 252, 203],
-    [247, 252, 206],
-    [246, 251, 208],
-    [245, 251, 210],
-    [243, 251, 212],
-    [242, 250, 214],
-    [241, 250, 217],
-    [240, 249, 219],
-    [239, 249, 221],
-    [237, 248, 223],
-    [236, 248, 226],
-    [235, 247, 228],
-    [234, 247, 230],
-    [233, 246, 232],
-    [231, 246, 235],
-    [230, 245, 237],
-    [229, 245, 239],
-    [228, 244, 241],
-    [226, 244, 244],
-    [225, 243, 246],
-    [224, 243, 248],
-    [222, 242, 247],
-    [220, 241, 247],
-    [218, 240, 246],
-    [216, 239, 246],
-    [214, 238, 245],
-    [212, 237, 244],
-    [209, 236, 244],
-    [207, 235, 243],
-    [205, 234, 243],
-    [203, 233, 242],
-    [201, 232, 242],
-    [199, 231, 241],
-    [197, 230, 240],
-    [195, 229, 240],
-    [193, 228, 239],
-    [191, 227, 239],
-    [189, 226, 238],
-    [187, 225, 237],
-    [185, 224, 237],
-    [182, 223, 236],
-    [180, 222, 236],
-    [178, 221, 235],
-    [176, 220, 234],
-    [174, 219, 234],
-    [172, 218, 233],
-    [170, 216, 233],
-    [168, 214, 232],
-    [166, 213, 231],
-    [163, 211, 230],
-    [161, 209, 229],
-    [159, 208, 228],
-    [157, 206, 227],
-    [155, 204, 226],
-    [153, 202, 225],
-    [151, 201, 224],
-    [148, 199, 223],
-    [146, 197, 222],
-    [144, 195, 221],
-    [142, 194, 220],
-    [140, 192, 219],
-    [138, 190, 218],
-    [135, 189, 217],
-    [133, 187, 217],
-    [131, 185, 216],
-    [129, 183, 215],
-    [127, 182, 214],
-    [125, 180, 213],
-    [122, 178, 212],
-    [120, 176, 211],
-    [118, 175, 210],
-    [116, 173, 209],
-    [114, 171, 208],
-    [112, 169, 207],
-    [110, 166, 206],
-    [109, 164, 204],
-    [107, 162, 203],
-    [105, 160, 202],
-    [103, 158, 201],
-    [101, 155, 200],
-    [ 99, 153, 199],
-    [ 98, 151, 198],
-    [ 96, 149, 196],
-    [ 94, 147, 195],
-    [ 92, 144, 194],
-    [ 90, 142, 193],
-    [ 88, 140, 192],
-    [ 87, 138, 191],
-    [ 85, 136, 190],
-    [ 83, 133, 189],
-    [ 81, 131, 187],
-    [ 79, 129, 186],
-    [ 77, 127, 185],
-    [ 75, 125, 184],
-    [ 74, 122, 183],
-    [ 72, 120, 182],
-    [ 70, 118, 181],
-    [ 69, 116, 179],
-    [ 68, 113, 178],
-    [ 67, 111, 177],
-    [ 66, 108, 176],
-    [ 65, 106, 175],
-    [ 65, 103, 173],
-    [ 64, 101, 172],
-    [ 63,  98, 171],
-    [ 62,  96, 170],
-    [ 62,  94, 168],
-    [ 61,  91, 167],
-    [ 60,  89, 166],
-    [ 59,  86, 165],
-    [ 58,  84, 164],
-    [ 58,  81, 162],
-    [ 57,  79, 161],
-    [ 56,  76, 160],
-    [ 55,  74, 159],
-    [ 54,  71, 158],
-    [ 54,  69, 156],
-    [ 53,  66, 155],
-    [ 52,  64, 154],
-    [ 51,  61, 153],
-    [ 51,  59, 151],
-    [ 50,  56, 150],
-    [ 49,  54, 149],
-];
+include!(concat!(env!("OUT_DIR"), "/colormap_luts.rs"));
@@ -0,0 +1,338 @@
+//! Image cubes and N-dimensional arrays
+//!
+//! A cube is an N-D array (N >= 2) loaded once and browsed as 2D planes: two
+//! axes are displayed and every other axis is fixed at an index, so a
+//! depth x height x width stack is the 3-axis case. Only the displayed plane
+//! is copied out for stretching; the full array stays in its native type.
+
+use crate::pixels::{ComplexComponent, PixelData};
+
+/// Which pixels the auto-scaling limits of a cube are computed from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SliceLimits {
+    /// Limits follow the min/max of the displayed slice
+    #[default]
+    PerSlice,
+    /// Limits span the min/max of the whole cube, so slices compare directly
+    Global,
+}
+
+impl SliceLimits {
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            SliceLimits::PerSlice => "slice",
+            SliceLimits::Global => "global",
+        }
+    }
+
+    /// Look up a mode by its JS API name
+    pub fn from_name(name: &str) -> Option<SliceLimits> {
+        match name {
+            "slice" => Some(SliceLimits::PerSlice),
+            "global" => Some(SliceLimits::Global),
+            _ => None,
+        }
+    }
+}
+
+/// How cube playback continues past the last frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PlaybackMode {
+    /// Wrap around to the first frame
+    #[default]
+    Loop,
+    /// Reverse direction at either end
+    Bounce,
+}
+
+impl PlaybackMode {
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlaybackMode::Loop => "loop",
+            PlaybackMode::Bounce => "bounce",
+        }
+    }
+
+    /// Look up a mode by its JS API name
+    pub fn from_name(name: &str) -> Option<PlaybackMode> {
+        match name {
+            "loop" => Some(PlaybackMode::Loop),
+            "bounce" => Some(PlaybackMode::Bounce),
+            _ => None,
+        }
+    }
+
+    /// The frame after `index` in a cube of `depth` frames when playing
+    /// forward (or backward), and the direction to continue in
+    pub fn advance(&self, index: usize, depth: usize, forward: bool) -> (usize, bool) {
+        if depth <= 1 {
+            return (0, forward);
+        }
+        match self {
+            PlaybackMode::Loop => ((index + 1) % depth, true),
+            PlaybackMode::Bounce if forward && index + 1 < depth => (index + 1, true),
+            PlaybackMode::Bounce if forward => (index - 1, false),
+            PlaybackMode::Bounce if index > 0 => (index - 1, false),
+            PlaybackMode::Bounce => (1, true),
+        }
+    }
+}
+
+/// An N-D array in C order, shown as planes spanned by two display axes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cube {
+    data: PixelData,
+    shape: Vec<usize>,
+    /// Distance in elements between consecutive indices along each axis
+    strides: Vec<usize>,
+    /// Axis shown along image rows
+    y_axis: usize,
+    /// Axis shown along image columns
+    x_axis: usize,
+    /// Axis names supplied by the host, if any
+    labels: Option<Vec<String>>,
+}
+
+impl Cube {
+    /// Wrap C-order `data` of the given `shape`, displaying the last two axes
+    pub fn new(data: PixelData, shape: Vec<usize>) -> Result<Cube, String> {
+        if shape.len() < 2 {
+            return Err(format!("Arrays need at least 2 dimensions, got shape {:?}", shape));
+        }
+        if shape.contains(&0) {
+            return Err(format!("Array dimensions must be nonzero, got shape {:?}", shape));
+        }
+        let total = shape
+            .iter()
+            .try_fold(1usize, |acc, &n| acc.checked_mul(n))
+            .ok_or_else(|| format!("Array shape {:?} is too large", shape))?;
+        if data.len() != total {
+            return Err(format!(
+                "Array size mismatch: shape {:?} needs {} pixels, got {}",
+                shape,
+                total,
+                data.len()
+            ));
+        }
+        let mut strides = vec![1; shape.len()];
+        for axis in (0..shape.len() - 1).rev() {
+            strides[axis] = strides[axis + 1] * shape[axis + 1];
+        }
+        let ndim = shape.len();
+        Ok(Cube { data, shape, strides, y_axis: ndim - 2, x_axis: ndim - 1, labels: None })
+    }
+
+    /// Number of axes
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Length of every axis
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Axes shown along image rows and columns, as (y, x)
+    pub fn display_axes(&self) -> (usize, usize) {
+        (self.y_axis, self.x_axis)
+    }
+
+    /// Choose the axes shown along image rows (`y_axis`) and columns (`x_axis`)
+    pub fn set_display_axes(&mut self, y_axis: usize, x_axis: usize) -> Result<(), String> {
+        if y_axis >= self.ndim() || x_axis >= self.ndim() || y_axis == x_axis {
+            return Err(format!(
+                "Display axes must be two different axes below {}, got y={} x={}",
+                self.ndim(),
+                y_axis,
+                x_axis
+            ));
+        }
+        self.y_axis = y_axis;
+        self.x_axis = x_axis;
+        Ok(())
+    }
+
+    /// Width of the displayed planes
+    pub fn width(&self) -> usize {
+        self.shape[self.x_axis]
+    }
+
+    /// Height of the displayed planes
+    pub fn height(&self) -> usize {
+        self.shape[self.y_axis]
+    }
+
+    /// Axes that are not displayed, in order; each has an index slider
+    pub fn slice_axes(&self) -> Vec<usize> {
+        (0..self.ndim()).filter(|&axis| axis != self.y_axis && axis != self.x_axis).collect()
+    }
+
+    /// Name of an axis: the host-supplied label, or "axis N"
+    pub fn label(&self, axis: usize) -> String {
+        match &self.labels {
+            Some(labels) => labels[axis].clone(),
+            None => format!("axis {}", axis),
+        }
+    }
+
+    /// Whether the host supplied axis labels
+    pub fn has_labels(&self) -> bool {
+        self.labels.is_some()
+    }
+
+    /// Set one label per axis (or clear them with an empty list)
+    pub fn set_labels(&mut self, labels: Vec<String>) -> Result<(), String> {
+        if labels.is_empty() {
+            self.labels = None;
+        } else if labels.len() != self.ndim() {
+            return Err(format!("Expected {} axis labels, got {}", self.ndim(), labels.len()));
+        } else {
+            self.labels = Some(labels);
+        }
+        Ok(())
+    }
+
+    /// Copy of the plane through `index` (one entry per axis; the entries of
+    /// the display axes are ignored and the others are clamped to the shape)
+    pub fn plane(&self, index: &[usize]) -> PixelData {
+        let base: usize = self
+            .slice_axes()
+            .into_iter()
+            .map(|axis| index[axis].min(self.shape[axis] - 1) * self.strides[axis])
+            .sum();
+        let (width, height) = (self.width(), self.height());
+        let (row_stride, col_stride) = (self.strides[self.y_axis], self.strides[self.x_axis]);
+
+        // Planes of the last two axes are contiguous
+        if col_stride == 1 && row_stride == width {
+            return self.data.slice(base..base + width * height);
+        }
+        let offsets: Vec<usize> = (0..height)
+            .flat_map(|row| (0..width).map(move |col| base + row * row_stride + col * col_stride))
+            .collect();
+        self.data.gather(&offsets)
+    }
+
+    /// Minimum and maximum of the finite values over all planes, or `None` if
+    /// there are none. `exclude` is a per-plane mask applied to every plane.
+    pub fn finite_range(&self, component: ComplexComponent, exclude: Option<&[u8]>) -> Option<(f64, f64)> {
+        self.planes()
+            .filter_map(|plane| plane.finite_range(component, exclude))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+    }
+
+    /// Finite values of every `step`-th pixel of every plane, for robust
+    /// limits over the whole cube. `exclude` is applied to every plane.
+    pub fn finite_values(&self, component: ComplexComponent, exclude: Option<&[u8]>, step: usize) -> Vec<f64> {
+        self.planes().flat_map(|plane| plane.finite_values(component, exclude, step)).collect()
+    }
+
+    /// Total number of values (the product of the shape)
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Every displayable plane, in C order of the slice axes
+    fn planes(&self) -> impl Iterator<Item = PixelData> + '_ {
+        let slice_axes = self.slice_axes();
+        let num_planes: usize = slice_axes.iter().map(|&axis| self.shape[axis]).product();
+        let mut index = vec![0; self.ndim()];
+        (0..num_planes).map(move |plane| {
+            // Decompose the plane number into indices along the slice axes
+            let mut rest = plane;
+            for &axis in slice_axes.iter().rev() {
+                index[axis] = rest % self.shape[axis];
+                rest /= self.shape[axis];
+            }
+            self.plane(&index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixels::PixelValue;
+
+    #[test]
+    fn test_planes() {
+        // 3 planes of 2x1
+        let cube = Cube::new(PixelData::from(vec![1i16, 2, 3, 4, 5, 6]), vec![3, 1, 2]).unwrap();
+        assert_eq!(cube.slice_axes(), vec![0]);
+        assert_eq!((cube.width(), cube.height()), (2, 1));
+        assert_eq!(cube.plane(&[1, 0, 0]), PixelData::from(vec![3i16, 4]));
+        assert_eq!(cube.plane(&[9, 0, 0]).get(0), Some(PixelValue::Int(5)));
+    }
+
+    #[test]
+    fn test_display_axes() {
+        // shape (t=2, y=2, x=3), values are their own flat index
+        let mut cube = Cube::new(PixelData::from((0u8..12).collect::<Vec<_>>()), vec![2, 2, 3]).unwrap();
+        // Show t along y and x along x, fixing y=1
+        cube.set_display_axes(0, 2).unwrap();
+        assert_eq!(cube.slice_axes(), vec![1]);
+        assert_eq!(cube.plane(&[0, 1, 0]), PixelData::from(vec![3u8, 4, 5, 9, 10, 11]));
+        // Transposed: x along rows, t along columns, fixing y=0
+        cube.set_display_axes(2, 0).unwrap();
+        assert_eq!((cube.width(), cube.height()), (2, 3));
+        assert_eq!(cube.plane(&[0, 0, 0]), PixelData::from(vec![0u8, 6, 1, 7, 2, 8]));
+        assert!(cube.set_display_axes(1, 1).is_err());
+        assert!(cube.set_display_axes(0, 3).is_err());
+    }
+
+    #[test]
+    fn test_global_range() {
+        let data = PixelData::from(vec![f32::NAN, 2.0, -1.0, 9.0]);
+        let cube = Cube::new(data, vec![2, 1, 2]).unwrap();
+        let any = ComplexComponent::default();
+        assert_eq!(cube.finite_range(any, None), Some((-1.0, 9.0)));
+        // The mask applies to every plane
+        assert_eq!(cube.finite_range(any, Some(&[0, 1])), Some((-1.0, -1.0)));
+        assert_eq!(cube.finite_values(any, Some(&[0, 1]), 1), vec![-1.0]);
+
+        // 4D: every combination of the two slice axes is visited
+        let data = PixelData::from((0..16).map(f64::from).collect::<Vec<_>>());
+        let cube = Cube::new(data, vec![2, 2, 2, 2]).unwrap();
+        assert_eq!(cube.finite_range(any, None), Some((0.0, 15.0)));
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut cube = Cube::new(PixelData::from(vec![0u8; 8]), vec![2, 2, 2]).unwrap();
+        assert_eq!(cube.label(0), "axis 0");
+        assert!(cube.set_labels(vec!["t".into()]).is_err());
+        cube.set_labels(vec!["t".into(), "y".into(), "x".into()]).unwrap();
+        assert!(cube.has_labels());
+        assert_eq!(cube.label(0), "t");
+    }
+
+    #[test]
+    fn test_playback_order() {
+        let frames = |mode: PlaybackMode, depth: usize| {
+            let (mut index, mut forward) = (0, true);
+            let mut order = vec![index];
+            for _ in 0..6 {
+                (index, forward) = mode.advance(index, depth, forward);
+                order.push(index);
+            }
+            order
+        };
+        assert_eq!(frames(PlaybackMode::Loop, 3), vec![0, 1, 2, 0, 1, 2, 0]);
+        assert_eq!(frames(PlaybackMode::Bounce, 3), vec![0, 1, 2, 1, 0, 1, 2]);
+        assert_eq!(frames(PlaybackMode::Bounce, 1), vec![0; 7]);
+        assert_eq!(PlaybackMode::from_name("bounce"), Some(PlaybackMode::Bounce));
+    }
+
+    #[test]
+    fn test_cube_errors() {
+        let data = PixelData::from(vec![0u8; 5]);
+        assert!(Cube::new(data.clone(), vec![2, 1, 2]).unwrap_err().contains("size mismatch"));
+        assert!(Cube::new(data.clone(), vec![0, 5, 1]).is_err());
+        assert!(Cube::new(data.clone(), vec![usize::MAX, 2, 2]).unwrap_err().contains("too large"));
+        assert!(Cube::new(data, vec![5]).is_err());
+        assert_eq!(SliceLimits::from_name("global"), Some(SliceLimits::Global));
+        assert_eq!(SliceLimits::from_name("cube"), None);
+    }
+}
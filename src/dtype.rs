@@ -0,0 +1,438 @@
+//! Element data types for raw pixel buffers
+//!
+//! Parses the type names accepted from JavaScript (Rust-style, NumPy-style and
+//! TypedArray names) into a `DType` plus byte order, and decodes raw bytes
+//! into typed pixel storage.
+
+use std::fmt;
+
+use crate::pixels::PixelData;
+
+/// Numeric element type of a raw pixel buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    /// IEEE 754 half precision, widened to f32 when decoded
+    F16,
+    /// bfloat16 (truncated f32), widened to f32 when decoded
+    BF16,
+    F32,
+    F64,
+    /// Complex with f32 real and imaginary parts (NumPy complex64)
+    C64,
+    /// Complex with f64 real and imaginary parts (NumPy complex128)
+    C128,
+}
+
+/// Byte order of multi-byte elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// Little-endian (JavaScript TypedArrays on all common platforms)
+    #[default]
+    Little,
+    /// Big-endian (FITS, many instrument formats)
+    Big,
+}
+
+impl DType {
+    /// Parse a type name into a dtype and byte order.
+    ///
+    /// Accepted forms:
+    /// - Rust-style: `"i8"`, `"u16"`, `"f32"`, `"f16"`, `"bf16"`, ...
+    /// - NumPy-style: `"<f4"`, `">i2"`, `"|u1"`, `"uint16"`, `"float32"`, `"bool"`, ...
+    /// - TypedArray names: `"Int8Array"`, `"Float16Array"`, `"BigUint64Array"`, ...
+    ///
+    /// A leading `<` or `>` selects little- or big-endian; `|` and `=` (or no
+    /// prefix) mean native order, which is little-endian for WASM. Because
+    /// NumPy's `i8`/`u8` are 64-bit while Rust's are 8-bit, a kind+size code
+    /// such as `i8` is read NumPy-style only when it has a byte-order prefix.
+    pub fn parse(name: &str) -> Result<(DType, ByteOrder), String> {
+        let trimmed = name.trim();
+        let (order, body, has_prefix) = match trimmed.chars().next() {
+            Some('<') => (ByteOrder::Little, &trimmed[1..], true),
+            Some('>') => (ByteOrder::Big, &trimmed[1..], true),
+            Some('|' | '=') => (ByteOrder::Little, &trimmed[1..], true),
+            _ => (ByteOrder::Little, trimmed, false),
+        };
+
+        if has_prefix {
+            if let Some(dtype) = Self::from_numpy_code(body) {
+                return Ok((dtype, order));
+            }
+        }
+
+        let dtype = match body {
+            "i8" | "int8" | "Int8Array" => DType::I8,
+            "u8" | "uint8" | "bool" | "Uint8Array" | "Uint8ClampedArray" => DType::U8,
+            "i16" | "int16" | "Int16Array" => DType::I16,
+            "u16" | "uint16" | "Uint16Array" => DType::U16,
+            "i32" | "int32" | "Int32Array" => DType::I32,
+            "u32" | "uint32" | "Uint32Array" => DType::U32,
+            "i64" | "int64" | "BigInt64Array" => DType::I64,
+            "u64" | "uint64" | "BigUint64Array" => DType::U64,
+            "f16" | "float16" | "half" | "Float16Array" => DType::F16,
+            "bf16" | "bfloat16" => DType::BF16,
+            "f32" | "float32" | "Float32Array" => DType::F32,
+            "f64" | "float64" | "Float64Array" => DType::F64,
+            "c64" | "complex64" => DType::C64,
+            "c128" | "complex128" => DType::C128,
+            // Unambiguous NumPy codes are also accepted without a prefix
+            _ => match Self::from_numpy_code(body) {
+                Some(dtype) if !matches!(body, "i8" | "u8") => dtype,
+                _ => {
+                    return Err(format!(
+                        "Unknown array type \"{}\". Expected a Rust-style type (i8, u8, i16, u16, i32, u32, \
+                         i64, u64, f16, bf16, f32, f64, c64, c128), a NumPy dtype (e.g. \"<f4\", \">i2\", \"uint16\") or a \
+                         TypedArray name (e.g. \"Float32Array\")",
+                        name
+                    ))
+                }
+            },
+        };
+        Ok((dtype, order))
+    }
+
+    /// Parse a NumPy kind+size code such as `f4`, `i2` or `b1` (without byte order)
+    fn from_numpy_code(code: &str) -> Option<DType> {
+        match code {
+            "i1" => Some(DType::I8),
+            "u1" | "b1" | "?" => Some(DType::U8),
+            "i2" => Some(DType::I16),
+            "u2" => Some(DType::U16),
+            "i4" => Some(DType::I32),
+            "u4" => Some(DType::U32),
+            "i8" => Some(DType::I64),
+            "u8" => Some(DType::U64),
+            "f2" => Some(DType::F16),
+            "f4" => Some(DType::F32),
+            "f8" => Some(DType::F64),
+            "c8" => Some(DType::C64),
+            "c16" => Some(DType::C128),
+            _ => None,
+        }
+    }
+
+    /// Size of one element in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            DType::I8 | DType::U8 => 1,
+            DType::I16 | DType::U16 | DType::F16 | DType::BF16 => 2,
+            DType::I32 | DType::U32 | DType::F32 => 4,
+            DType::I64 | DType::U64 | DType::F64 | DType::C64 => 8,
+            DType::C128 => 16,
+        }
+    }
+
+    /// Whether this is an integer type (for display formatting)
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, DType::F16 | DType::BF16 | DType::F32 | DType::F64 | DType::C64 | DType::C128)
+    }
+
+    /// Whether this is a complex type
+    pub fn is_complex(&self) -> bool {
+        matches!(self, DType::C64 | DType::C128)
+    }
+
+    /// Decode a contiguous buffer of elements, keeping their native type
+    pub fn decode(&self, bytes: &[u8], order: ByteOrder) -> Result<PixelData, String> {
+        let size = self.size();
+        if !bytes.len().is_multiple_of(size) {
+            return Err(format!(
+                "Buffer length {} is not a multiple of the {} element size ({} bytes)",
+                bytes.len(),
+                self,
+                size
+            ));
+        }
+        let len = bytes.len() / size;
+        self.decode_strided(bytes, order, len, 1, &StridedLayout::row_major(len, size))
+    }
+
+    /// Decode a `width` x `height` image laid out in `bytes` according to `layout`,
+    /// keeping the native element type.
+    ///
+    /// Pixels are returned in row-major order regardless of the source layout.
+    pub fn decode_strided(
+        &self,
+        bytes: &[u8],
+        order: ByteOrder,
+        width: usize,
+        height: usize,
+        layout: &StridedLayout,
+    ) -> Result<PixelData, String> {
+        let size = self.size();
+        let required = layout.required_len(width, height, size).ok_or_else(|| {
+            format!("Strided layout overflows: {}x{} image with {:?}", width, height, layout)
+        })?;
+        if bytes.len() < required {
+            return Err(format!(
+                "Buffer too small for strided layout: {}x{} {} image with byte offset {}, row stride {} \
+                 and column stride {} needs {} bytes, got {}",
+                width, height, self, layout.byte_offset, layout.row_stride, layout.col_stride, required,
+                bytes.len()
+            ));
+        }
+
+        // Elements are read as `$t` and passed through `$convert` (used to widen
+        // half-precision floats to f32)
+        macro_rules! collect {
+            ($t:ty) => {
+                collect!($t, std::convert::identity)
+            };
+            ($t:ty, $convert:expr) => {{
+                let mut values = Vec::with_capacity(width * height);
+                for row in 0..height {
+                    let row_start = layout.byte_offset + row * layout.row_stride;
+                    for col in 0..width {
+                        let start = row_start + col * layout.col_stride;
+                        let arr = bytes[start..start + size].try_into().expect("element byte length matches dtype size");
+                        values.push($convert(match order {
+                            ByteOrder::Little => <$t>::from_le_bytes(arr),
+                            ByteOrder::Big => <$t>::from_be_bytes(arr),
+                        }));
+                    }
+                }
+                PixelData::from(values)
+            }};
+        }
+        // Complex elements are a real part followed by an imaginary part, each
+        // in the same byte order
+        macro_rules! collect_complex {
+            ($t:ty) => {{
+                let half = size / 2;
+                let mut values: Vec<[$t; 2]> = Vec::with_capacity(width * height);
+                for row in 0..height {
+                    let row_start = layout.byte_offset + row * layout.row_stride;
+                    for col in 0..width {
+                        let start = row_start + col * layout.col_stride;
+                        let part = |offset: usize| {
+                            let arr = bytes[offset..offset + half].try_into().expect("part byte length matches dtype size");
+                            match order {
+                                ByteOrder::Little => <$t>::from_le_bytes(arr),
+                                ByteOrder::Big => <$t>::from_be_bytes(arr),
+                            }
+                        };
+                        values.push([part(start), part(start + half)]);
+                    }
+                }
+                PixelData::from(values)
+            }};
+        }
+        Ok(match self {
+            DType::I8 => collect!(i8),
+            DType::U8 => collect!(u8),
+            DType::I16 => collect!(i16),
+            DType::U16 => collect!(u16),
+            DType::I32 => collect!(i32),
+            DType::U32 => collect!(u32),
+            DType::I64 => collect!(i64),
+            DType::U64 => collect!(u64),
+            DType::F16 => collect!(u16, f16_to_f32),
+            DType::BF16 => collect!(u16, bf16_to_f32),
+            DType::F32 => collect!(f32),
+            DType::F64 => collect!(f64),
+            DType::C64 => collect_complex!(f32),
+            DType::C128 => collect_complex!(f64),
+        })
+    }
+}
+
+/// Widen IEEE 754 half-precision bits to f32 (exact, including NaN/Inf)
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    match exponent {
+        // Zero and subnormals: mantissa * 2^-24
+        0 => {
+            let magnitude = mantissa as f32 * f32::from_bits(0x3380_0000);
+            f32::from_bits(sign | magnitude.to_bits())
+        }
+        // Inf and NaN
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        // Normal numbers: rebias exponent from 15 to 127
+        _ => f32::from_bits(sign | ((exponent + 112) << 23) | (mantissa << 13)),
+    }
+}
+
+/// Widen bfloat16 bits to f32 (bfloat16 is the upper half of an f32)
+fn bf16_to_f32(bits: u16) -> f32 {
+    f32::from_bits((bits as u32) << 16)
+}
+
+/// Position of a 2D image inside a larger buffer, with all values in bytes
+/// (the same convention as NumPy strides)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StridedLayout {
+    /// Offset of the first pixel (row 0, column 0)
+    pub byte_offset: usize,
+    /// Distance between the starts of consecutive rows
+    pub row_stride: usize,
+    /// Distance between consecutive pixels within a row
+    pub col_stride: usize,
+}
+
+impl StridedLayout {
+    /// Contiguous row-major (C order) layout
+    pub fn row_major(width: usize, itemsize: usize) -> Self {
+        Self { byte_offset: 0, row_stride: width * itemsize, col_stride: itemsize }
+    }
+
+    /// Number of bytes from the start of the buffer to the end of the last pixel,
+    /// or `None` if the computation overflows
+    pub fn required_len(&self, width: usize, height: usize, itemsize: usize) -> Option<usize> {
+        if width == 0 || height == 0 {
+            return Some(self.byte_offset);
+        }
+        let last_row = (height - 1).checked_mul(self.row_stride)?;
+        let last_col = (width - 1).checked_mul(self.col_stride)?;
+        self.byte_offset
+            .checked_add(last_row)?
+            .checked_add(last_col)?
+            .checked_add(itemsize)
+    }
+}
+
+impl fmt::Display for DType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DType::I8 => "i8",
+            DType::U8 => "u8",
+            DType::I16 => "i16",
+            DType::U16 => "u16",
+            DType::I32 => "i32",
+            DType::U32 => "u32",
+            DType::I64 => "i64",
+            DType::U64 => "u64",
+            DType::F16 => "f16",
+            DType::BF16 => "bf16",
+            DType::F32 => "f32",
+            DType::F64 => "f64",
+            DType::C64 => "c64",
+            DType::C128 => "c128",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_name_styles() {
+        assert_eq!(DType::parse("f32").unwrap(), (DType::F32, ByteOrder::Little));
+        assert_eq!(DType::parse("<f4").unwrap(), (DType::F32, ByteOrder::Little));
+        assert_eq!(DType::parse("Float32Array").unwrap(), (DType::F32, ByteOrder::Little));
+        assert_eq!(DType::parse(">i2").unwrap(), (DType::I16, ByteOrder::Big));
+        assert_eq!(DType::parse("uint16").unwrap(), (DType::U16, ByteOrder::Little));
+        assert_eq!(DType::parse("|u1").unwrap(), (DType::U8, ByteOrder::Little));
+        assert_eq!(DType::parse("|b1").unwrap(), (DType::U8, ByteOrder::Little));
+        assert_eq!(DType::parse("BigInt64Array").unwrap(), (DType::I64, ByteOrder::Little));
+        assert_eq!(DType::parse(">f8").unwrap(), (DType::F64, ByteOrder::Big));
+        assert_eq!(DType::parse("f4").unwrap(), (DType::F32, ByteOrder::Little));
+    }
+
+    #[test]
+    fn test_parse_numpy_vs_rust_8() {
+        // Without a prefix, i8/u8 are the Rust 8-bit types...
+        assert_eq!(DType::parse("i8").unwrap().0, DType::I8);
+        assert_eq!(DType::parse("u8").unwrap().0, DType::U8);
+        // ...with one, they are NumPy's 8-byte integers
+        assert_eq!(DType::parse("<i8").unwrap().0, DType::I64);
+        assert_eq!(DType::parse(">u8").unwrap(), (DType::U64, ByteOrder::Big));
+    }
+
+    #[test]
+    fn test_parse_unknown_is_error() {
+        for name in ["float", "<m8", "Float128Array", "", "f128", "i3"] {
+            let err = DType::parse(name).unwrap_err();
+            assert!(err.contains("Unknown array type"), "{}: {}", name, err);
+        }
+    }
+
+    #[test]
+    fn test_decode_byte_order() {
+        let bytes = [0x01, 0x02, 0xff, 0xfe];
+        assert_eq!(DType::U16.decode(&bytes, ByteOrder::Little).unwrap(), PixelData::U16(vec![513, 65279]));
+        assert_eq!(DType::U16.decode(&bytes, ByteOrder::Big).unwrap(), PixelData::U16(vec![258, 65534]));
+        assert_eq!(DType::I16.decode(&bytes, ByteOrder::Big).unwrap(), PixelData::I16(vec![258, -2]));
+
+        let be: Vec<u8> = [1.5f32, -2.25].iter().flat_map(|v| v.to_be_bytes()).collect();
+        assert_eq!(DType::F32.decode(&be, ByteOrder::Big).unwrap(), PixelData::F32(vec![1.5, -2.25]));
+    }
+
+    #[test]
+    fn test_decode_complex() {
+        assert_eq!(DType::parse("<c8").unwrap(), (DType::C64, ByteOrder::Little));
+        assert_eq!(DType::parse("complex128").unwrap(), (DType::C128, ByteOrder::Little));
+
+        let be: Vec<u8> = [1.5f64, -2.0, 0.0, 3.0].iter().flat_map(|v| v.to_be_bytes()).collect();
+        assert_eq!(
+            DType::C128.decode(&be, ByteOrder::Big).unwrap(),
+            PixelData::C128(vec![[1.5, -2.0], [0.0, 3.0]])
+        );
+    }
+
+    #[test]
+    fn test_decode_half_precision() {
+        assert_eq!(DType::parse("<f2").unwrap(), (DType::F16, ByteOrder::Little));
+        assert_eq!(DType::parse("Float16Array").unwrap().0, DType::F16);
+        assert_eq!(DType::parse("bfloat16").unwrap().0, DType::BF16);
+
+        // 1.0, -2.0, 65504 (max), smallest subnormal, +Inf, NaN
+        let bits: [u16; 6] = [0x3c00, 0xc000, 0x7bff, 0x0001, 0x7c00, 0x7e00];
+        let bytes: Vec<u8> = bits.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let PixelData::F32(values) = DType::F16.decode(&bytes, ByteOrder::Little).unwrap() else {
+            panic!("f16 should decode to f32");
+        };
+        assert_eq!(&values[..4], &[1.0, -2.0, 65504.0, 2.0f32.powi(-24)]);
+        assert_eq!(values[4], f32::INFINITY);
+        assert!(values[5].is_nan());
+
+        // bfloat16 1.5 and -Inf, big-endian
+        let pixels = DType::BF16.decode(&[0x3f, 0xc0, 0xff, 0x80], ByteOrder::Big).unwrap();
+        assert_eq!(pixels, PixelData::F32(vec![1.5, f32::NEG_INFINITY]));
+    }
+
+    #[test]
+    fn test_decode_length_mismatch() {
+        let err = DType::F32.decode(&[0; 6], ByteOrder::Little).unwrap_err();
+        assert!(err.contains("not a multiple"));
+    }
+
+    #[test]
+    fn test_decode_strided_sub_region() {
+        // 4-byte header followed by a 3x4 u8 image; take the 2x2 block at (row 1, col 1)
+        let mut bytes = vec![0xaa; 4];
+        bytes.extend(0..12u8);
+        let layout = StridedLayout { byte_offset: 4 + 4 + 1, row_stride: 4, col_stride: 1 };
+        let pixels = DType::U8.decode_strided(&bytes, ByteOrder::Little, 2, 2, &layout).unwrap();
+        assert_eq!(pixels, PixelData::U8(vec![5, 6, 9, 10]));
+    }
+
+    #[test]
+    fn test_decode_strided_column_major() {
+        // 2 rows x 3 columns stored column by column as big-endian i16
+        let values: [i16; 6] = [1, 4, 2, 5, 3, 6];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+        let layout = StridedLayout { byte_offset: 0, row_stride: 2, col_stride: 4 };
+        let pixels = DType::I16.decode_strided(&bytes, ByteOrder::Big, 3, 2, &layout).unwrap();
+        assert_eq!(pixels, PixelData::I16(vec![1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn test_decode_strided_too_small() {
+        let layout = StridedLayout { byte_offset: 8, ..StridedLayout::row_major(4, 4) };
+        assert_eq!(layout.required_len(4, 4, 4), Some(72));
+        let err = DType::F32.decode_strided(&[0; 64], ByteOrder::Little, 4, 4, &layout).unwrap_err();
+        assert!(err.contains("needs 72 bytes"), "{}", err);
+    }
+}
@@ -0,0 +1,575 @@
+//! Minimal FITS reader for image HDUs
+//!
+//! Parses the primary HDU and IMAGE extensions of a FITS file held in memory
+//! and converts the first 2D plane to physical values (BSCALE/BZERO applied,
+//! BLANK mapped to NaN). Tables and other extension types can be skipped over
+//! but not displayed.
+
+use crate::dtype::{ByteOrder, DType};
+use crate::pixels::PixelData;
+
+/// FITS files are organized in blocks of this many bytes
+const BLOCK_SIZE: usize = 2880;
+/// Each header card (keyword record) is this many bytes
+const CARD_SIZE: usize = 80;
+
+/// A 2D image decoded from a FITS HDU
+#[derive(Debug)]
+pub struct FitsImage {
+    /// Physical pixel values in row-major order (row 0 is the first FITS row).
+    /// Unscaled integer and float data keeps its native type; scaled or
+    /// blanked data is converted to f64.
+    pub pixels: PixelData,
+    /// Image width (NAXIS1)
+    pub width: u32,
+    /// Image height (NAXIS2)
+    pub height: u32,
+}
+
+/// A parsed header value
+#[derive(Clone, Debug, PartialEq)]
+enum HeaderValue {
+    Logical(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+/// The subset of an HDU header needed to locate and decode its data
+#[derive(Debug)]
+struct HduHeader {
+    /// "PRIMARY" for the first HDU, otherwise the XTENSION value
+    extension: String,
+    bitpix: i64,
+    naxes: Vec<usize>,
+    pcount: usize,
+    gcount: usize,
+    bscale: f64,
+    bzero: f64,
+    blank: Option<i64>,
+}
+
+impl HduHeader {
+    /// Size of the data unit in bytes, not including padding
+    fn data_size(&self) -> Result<usize, String> {
+        if self.naxes.is_empty() {
+            return Ok(0);
+        }
+        let elements = self.naxes.iter().try_fold(1usize, |acc, &n| acc.checked_mul(n));
+        elements
+            .and_then(|elements| elements.checked_add(self.pcount))
+            .and_then(|n| n.checked_mul(self.gcount))
+            .and_then(|n| n.checked_mul(self.bitpix.unsigned_abs() as usize / 8))
+            .ok_or_else(|| "Invalid FITS header: data unit is too large".to_string())
+    }
+}
+
+/// Read the image stored in HDU `hdu_index` (0 = primary HDU).
+///
+/// Only the first 2D plane of images with more than two axes is returned.
+pub fn read_image(data: &[u8], hdu_index: usize) -> Result<FitsImage, String> {
+    let mut offset = 0;
+    let mut index = 0;
+
+    loop {
+        if offset >= data.len() {
+            return Err(format!(
+                "HDU {} not found: file contains {} HDU(s)",
+                hdu_index, index
+            ));
+        }
+
+        let (header, header_len) = parse_header(&data[offset..], index == 0)?;
+        let data_start = offset + header_len;
+        let data_size = header.data_size()?;
+        let data_end = data_start
+            .checked_add(data_size)
+            .ok_or_else(|| "Invalid FITS header: data unit is too large".to_string())?;
+
+        if index == hdu_index {
+            if data_end > data.len() {
+                return Err(format!(
+                    "Truncated FITS file: HDU {} needs {} data bytes, only {} available",
+                    hdu_index,
+                    data_size,
+                    data.len().saturating_sub(data_start)
+                ));
+            }
+            return decode_image(&header, &data[data_start..data_end], hdu_index);
+        }
+
+        // The data unit starts on a block boundary, so padding its end skips the padding too
+        offset = data_end
+            .checked_next_multiple_of(BLOCK_SIZE)
+            .ok_or_else(|| "Invalid FITS header: data unit is too large".to_string())?;
+        index += 1;
+    }
+}
+
+/// Round a byte count up to a whole number of FITS blocks
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(BLOCK_SIZE) * BLOCK_SIZE
+}
+
+/// Parse the header starting at the beginning of `data`.
+/// Returns the header and its length in bytes (including padding).
+fn parse_header(data: &[u8], is_primary: bool) -> Result<(HduHeader, usize), String> {
+    let mut header = HduHeader {
+        extension: String::new(),
+        bitpix: 0,
+        naxes: Vec::new(),
+        pcount: 0,
+        gcount: 1,
+        bscale: 1.0,
+        bzero: 0.0,
+        blank: None,
+    };
+    let mut naxis = None;
+    let mut axis_lengths: Vec<Option<usize>> = Vec::new();
+
+    let mut card_index = 0;
+    loop {
+        let start = card_index * CARD_SIZE;
+        let end = start + CARD_SIZE;
+        if end > data.len() {
+            return Err("Invalid FITS header: missing END card".to_string());
+        }
+        // Cards are sliced at fixed byte columns, so multi-byte characters are rejected up front
+        let bytes = &data[start..end];
+        if !bytes.is_ascii() {
+            return Err("Invalid FITS header: non-ASCII characters".to_string());
+        }
+        let card = std::str::from_utf8(bytes).expect("ASCII is valid UTF-8");
+        let keyword = card[..8].trim_end();
+
+        if card_index == 0 {
+            let expected = if is_primary { "SIMPLE" } else { "XTENSION" };
+            if keyword != expected {
+                return Err(format!(
+                    "Invalid FITS header: expected {} card, found \"{}\"",
+                    expected, keyword
+                ));
+            }
+        }
+
+        if keyword == "END" {
+            break;
+        }
+
+        if let Some(value) = parse_value(card) {
+            match keyword {
+                "SIMPLE" => header.extension = "PRIMARY".to_string(),
+                "XTENSION" => {
+                    if let HeaderValue::String(s) = value {
+                        header.extension = s;
+                    }
+                }
+                "BITPIX" => header.bitpix = expect_integer(keyword, &value)?,
+                "NAXIS" => {
+                    let n = expect_integer(keyword, &value)?;
+                    if !(0..=999).contains(&n) {
+                        return Err(format!("Invalid NAXIS value: {}", n));
+                    }
+                    naxis = Some(n as usize);
+                    axis_lengths = vec![None; n as usize];
+                }
+                "PCOUNT" => header.pcount = expect_count(keyword, &value)?,
+                "GCOUNT" => header.gcount = expect_count(keyword, &value)?,
+                "BSCALE" => header.bscale = expect_number(keyword, &value)?,
+                "BZERO" => header.bzero = expect_number(keyword, &value)?,
+                "BLANK" => header.blank = Some(expect_integer(keyword, &value)?),
+                _ => {
+                    if let Some(axis) = keyword.strip_prefix("NAXIS").and_then(|n| n.parse::<usize>().ok()) {
+                        if axis >= 1 && axis <= axis_lengths.len() {
+                            axis_lengths[axis - 1] = Some(expect_count(keyword, &value)?);
+                        }
+                    }
+                }
+            }
+        }
+
+        card_index += 1;
+    }
+
+    if naxis.is_none() {
+        return Err("Invalid FITS header: missing NAXIS keyword".to_string());
+    }
+    if !matches!(header.bitpix, 8 | 16 | 32 | 64 | -32 | -64) {
+        return Err(format!("Unsupported BITPIX value: {}", header.bitpix));
+    }
+    for (i, len) in axis_lengths.iter().enumerate() {
+        match len {
+            Some(len) => header.naxes.push(*len),
+            None => return Err(format!("Invalid FITS header: missing NAXIS{} keyword", i + 1)),
+        }
+    }
+
+    let header_len = padded_len((card_index + 1) * CARD_SIZE);
+    Ok((header, header_len))
+}
+
+/// Parse the value field of a header card, if it has one
+fn parse_value(card: &str) -> Option<HeaderValue> {
+    if card.get(8..10) != Some("= ") {
+        return None;
+    }
+    let field = card[10..].trim_start();
+
+    if let Some(rest) = field.strip_prefix('\'') {
+        // String value: '' is an escaped quote, trailing spaces are not significant
+        let mut value = String::new();
+        let mut chars = rest.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    value.push('\'');
+                } else {
+                    break;
+                }
+            } else {
+                value.push(c);
+            }
+        }
+        return Some(HeaderValue::String(value.trim_end().to_string()));
+    }
+
+    let value = field.split('/').next().unwrap_or("").trim();
+    match value {
+        "" => None,
+        "T" => Some(HeaderValue::Logical(true)),
+        "F" => Some(HeaderValue::Logical(false)),
+        _ => {
+            if let Ok(i) = value.parse::<i64>() {
+                Some(HeaderValue::Integer(i))
+            } else {
+                // FORTRAN-style D exponents are allowed in FITS
+                value.replace(['D', 'd'], "E").parse::<f64>().ok().map(HeaderValue::Float)
+            }
+        }
+    }
+}
+
+fn expect_integer(keyword: &str, value: &HeaderValue) -> Result<i64, String> {
+    match value {
+        HeaderValue::Integer(i) => Ok(*i),
+        _ => Err(format!("Invalid FITS header: {} must be an integer", keyword)),
+    }
+}
+
+fn expect_count(keyword: &str, value: &HeaderValue) -> Result<usize, String> {
+    let i = expect_integer(keyword, value)?;
+    usize::try_from(i).map_err(|_| format!("Invalid FITS header: {} must not be negative", keyword))
+}
+
+fn expect_number(keyword: &str, value: &HeaderValue) -> Result<f64, String> {
+    match value {
+        HeaderValue::Integer(i) => Ok(*i as f64),
+        HeaderValue::Float(f) => Ok(*f),
+        _ => Err(format!("Invalid FITS header: {} must be a number", keyword)),
+    }
+}
+
+/// Decode the first 2D plane of an image HDU's data unit to physical values
+fn decode_image(header: &HduHeader, data: &[u8], hdu_index: usize) -> Result<FitsImage, String> {
+    if header.extension != "PRIMARY" && header.extension != "IMAGE" {
+        return Err(format!(
+            "HDU {} is a {} extension, not an image",
+            hdu_index, header.extension
+        ));
+    }
+    if header.naxes.len() < 2 {
+        return Err(format!(
+            "HDU {} has no 2D image data (NAXIS = {})",
+            hdu_index,
+            header.naxes.len()
+        ));
+    }
+
+    let width = header.naxes[0];
+    let height = header.naxes[1];
+    let bytes_per_value = header.bitpix.unsigned_abs() as usize / 8;
+    let plane_bytes = width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(bytes_per_value))
+        .filter(|&n| n <= data.len())
+        .ok_or_else(|| format!("HDU {} image plane is larger than its data unit", hdu_index))?;
+    let plane = &data[..plane_bytes];
+
+    let bscale = header.bscale;
+    let bzero = header.bzero;
+    let pixels = if header.bitpix < 0 && bscale == 1.0 && bzero == 0.0 {
+        // Unscaled floating point data is kept as-is
+        let dtype = if header.bitpix == -32 { DType::F32 } else { DType::F64 };
+        dtype.decode(plane, ByteOrder::Big)?
+    } else if header.bitpix > 0 && bscale == 1.0 && !contains_blank(header, plane) {
+        decode_unscaled_integers(header.bitpix, bzero, plane)
+            .unwrap_or_else(|| PixelData::F64(scale_to_physical(header, plane)))
+    } else {
+        PixelData::F64(scale_to_physical(header, plane))
+    };
+
+    Ok(FitsImage {
+        pixels,
+        width: width as u32,
+        height: height as u32,
+    })
+}
+
+/// Raw (unscaled) integer values of a BITPIX > 0 data plane
+fn raw_integers(bitpix: i64, plane: &[u8]) -> impl Iterator<Item = i64> + '_ {
+    let size = bitpix as usize / 8;
+    plane.chunks_exact(size).map(move |c| match bitpix {
+        8 => c[0] as i64,
+        16 => i16::from_be_bytes([c[0], c[1]]) as i64,
+        32 => i32::from_be_bytes(c.try_into().unwrap()) as i64,
+        _ => i64::from_be_bytes(c.try_into().unwrap()),
+    })
+}
+
+/// Whether an integer data plane contains any BLANK (undefined) values
+fn contains_blank(header: &HduHeader, plane: &[u8]) -> bool {
+    match header.blank {
+        Some(blank) if header.bitpix > 0 => raw_integers(header.bitpix, plane).any(|v| v == blank),
+        _ => false,
+    }
+}
+
+/// Decode integer data with BSCALE = 1 into an exact integer type.
+///
+/// The standard unsigned conventions (e.g. BITPIX = 16 with BZERO = 32768)
+/// map to the matching unsigned type; other integral offsets widen to i64.
+/// Returns `None` if BZERO is not an integer or the result would overflow.
+fn decode_unscaled_integers(bitpix: i64, bzero: f64, plane: &[u8]) -> Option<PixelData> {
+    let order = ByteOrder::Big;
+    let decoded = match (bitpix, bzero) {
+        (8, 0.0) => DType::U8.decode(plane, order),
+        (8, -128.0) => Ok(PixelData::I8(plane.iter().map(|&b| (b ^ 0x80) as i8).collect())),
+        (16, 0.0) => DType::I16.decode(plane, order),
+        (16, 32768.0) => Ok(PixelData::U16(
+            plane.chunks_exact(2).map(|c| u16::from_be_bytes([c[0] ^ 0x80, c[1]])).collect(),
+        )),
+        (32, 0.0) => DType::I32.decode(plane, order),
+        (32, 2147483648.0) => Ok(PixelData::U32(
+            plane.chunks_exact(4).map(|c| u32::from_be_bytes(c.try_into().unwrap()) ^ (1 << 31)).collect(),
+        )),
+        (64, 0.0) => DType::I64.decode(plane, order),
+        (64, 9223372036854775808.0) => Ok(PixelData::U64(
+            plane.chunks_exact(8).map(|c| u64::from_be_bytes(c.try_into().unwrap()) ^ (1 << 63)).collect(),
+        )),
+        _ => {
+            if bzero.fract() != 0.0 || bzero.abs() >= i64::MAX as f64 {
+                return None;
+            }
+            let offset = bzero as i64;
+            let values: Option<Vec<i64>> = raw_integers(bitpix, plane).map(|v| v.checked_add(offset)).collect();
+            return values.map(PixelData::I64);
+        }
+    };
+    decoded.ok()
+}
+
+/// Convert a data plane to physical values (BSCALE/BZERO applied, BLANK as NaN)
+fn scale_to_physical(header: &HduHeader, plane: &[u8]) -> Vec<f64> {
+    let bscale = header.bscale;
+    let bzero = header.bzero;
+    match header.bitpix {
+        -32 => plane
+            .chunks_exact(4)
+            .map(|c| bzero + bscale * f32::from_be_bytes(c.try_into().unwrap()) as f64)
+            .collect(),
+        -64 => plane
+            .chunks_exact(8)
+            .map(|c| bzero + bscale * f64::from_be_bytes(c.try_into().unwrap()))
+            .collect(),
+        bitpix => raw_integers(bitpix, plane)
+            .map(|raw| {
+                if Some(raw) == header.blank {
+                    f64::NAN
+                } else {
+                    bzero + bscale * raw as f64
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a header block from (keyword, value) pairs, appending END and padding
+    fn header_block(cards: &[(&str, &str)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (keyword, value) in cards {
+            out.extend_from_slice(format!("{:<8}= {:>20}", keyword, value).as_bytes());
+            out.resize(out.len().div_ceil(CARD_SIZE) * CARD_SIZE, b' ');
+        }
+        out.extend_from_slice(format!("{:<80}", "END").as_bytes());
+        out.resize(padded_len(out.len()), b' ');
+        out
+    }
+
+    fn pad_data(mut data: Vec<u8>) -> Vec<u8> {
+        data.resize(padded_len(data.len()), 0);
+        data
+    }
+
+    #[test]
+    fn test_read_int16_primary_with_bzero() {
+        let mut file = header_block(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "16"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "3"),
+            ("NAXIS2", "2"),
+            ("BZERO", "32768"),
+        ]);
+        let raw: [i16; 6] = [-32768, 0, 1, 2, 3, 32767];
+        file.extend(pad_data(raw.iter().flat_map(|v| v.to_be_bytes()).collect()));
+
+        let image = read_image(&file, 0).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.pixels, PixelData::U16(vec![0, 32768, 32769, 32770, 32771, 65535]));
+        assert!(image.pixels.is_integer());
+    }
+
+    #[test]
+    fn test_read_float32_image_extension() {
+        let mut file = header_block(&[("SIMPLE", "T"), ("BITPIX", "8"), ("NAXIS", "0"), ("EXTEND", "T")]);
+        file.extend(header_block(&[
+            ("XTENSION", "'IMAGE   '"),
+            ("BITPIX", "-32"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "2"),
+            ("NAXIS2", "2"),
+            ("PCOUNT", "0"),
+            ("GCOUNT", "1"),
+            ("BSCALE", "2.0D0"),
+        ]));
+        let raw: [f32; 4] = [1.5, -2.0, f32::NAN, 4.0];
+        file.extend(pad_data(raw.iter().flat_map(|v| v.to_be_bytes()).collect()));
+
+        let image = read_image(&file, 1).unwrap();
+        assert_eq!((image.width, image.height), (2, 2));
+        let values = image.pixels.map_f64(Default::default(), |v| v);
+        assert_eq!(values[0], 3.0);
+        assert_eq!(values[1], -4.0);
+        assert!(values[2].is_nan());
+        assert_eq!(values[3], 8.0);
+        assert!(!image.pixels.is_integer());
+    }
+
+    #[test]
+    fn test_blank_maps_to_nan() {
+        let mut file = header_block(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "32"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "2"),
+            ("NAXIS2", "1"),
+            ("BLANK", "-999"),
+        ]);
+        let raw: [i32; 2] = [-999, 7];
+        file.extend(pad_data(raw.iter().flat_map(|v| v.to_be_bytes()).collect()));
+
+        let image = read_image(&file, 0).unwrap();
+        let values = image.pixels.map_f64(Default::default(), |v| v);
+        assert!(values[0].is_nan());
+        assert_eq!(values[1], 7.0);
+        assert!(!image.pixels.is_integer());
+    }
+
+    #[test]
+    fn test_unscaled_integers_stay_exact() {
+        let header = |bitpix: &'static str, bzero: &'static str| {
+            header_block(&[
+                ("SIMPLE", "T"),
+                ("BITPIX", bitpix),
+                ("NAXIS", "2"),
+                ("NAXIS1", "2"),
+                ("NAXIS2", "1"),
+                ("BZERO", bzero),
+                ("BLANK", "5"),
+            ])
+        };
+
+        // Unsigned 64-bit convention keeps full precision
+        let mut file = header("64", "9223372036854775808");
+        let raw: [i64; 2] = [i64::MAX, i64::MIN];
+        file.extend(pad_data(raw.iter().flat_map(|v| v.to_be_bytes()).collect()));
+        assert_eq!(read_image(&file, 0).unwrap().pixels, PixelData::U64(vec![u64::MAX, 0]));
+
+        // A non-standard integral offset widens to i64; BLANK is declared but unused
+        let mut file = header("16", "-10");
+        let raw: [i16; 2] = [3, -32768];
+        file.extend(pad_data(raw.iter().flat_map(|v| v.to_be_bytes()).collect()));
+        assert_eq!(read_image(&file, 0).unwrap().pixels, PixelData::I64(vec![-7, -32778]));
+    }
+
+    #[test]
+    fn test_first_plane_of_cube() {
+        let mut file = header_block(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "8"),
+            ("NAXIS", "3"),
+            ("NAXIS1", "2"),
+            ("NAXIS2", "2"),
+            ("NAXIS3", "2"),
+        ]);
+        file.extend(pad_data((0u8..8).collect()));
+
+        let image = read_image(&file, 0).unwrap();
+        assert_eq!(image.pixels, PixelData::U8(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut file = header_block(&[("SIMPLE", "T"), ("BITPIX", "8"), ("NAXIS", "0")]);
+        assert!(read_image(&file, 0).unwrap_err().contains("no 2D image data"));
+        assert!(read_image(&file, 1).unwrap_err().contains("not found"));
+
+        file.extend(header_block(&[
+            ("XTENSION", "'BINTABLE'"),
+            ("BITPIX", "8"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "4"),
+            ("NAXIS2", "1"),
+            ("PCOUNT", "0"),
+            ("GCOUNT", "1"),
+        ]));
+        file.extend(pad_data(vec![0; 4]));
+        assert!(read_image(&file, 1).unwrap_err().contains("BINTABLE"));
+
+        let bad_bitpix = header_block(&[("SIMPLE", "T"), ("BITPIX", "12"), ("NAXIS", "0")]);
+        assert!(read_image(&bad_bitpix, 0).unwrap_err().contains("BITPIX"));
+
+        let truncated = header_block(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "16"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "100"),
+            ("NAXIS2", "100"),
+        ]);
+        assert!(read_image(&truncated, 0).unwrap_err().contains("Truncated"));
+    }
+
+    #[test]
+    fn test_hostile_headers() {
+        // A multi-byte character straddling the value indicator column is rejected, not sliced
+        let mut file = header_block(&[("SIMPLE", "T"), ("BITPIX", "8"), ("NAXIS", "0")]);
+        file[CARD_SIZE + 7..CARD_SIZE + 9].copy_from_slice("é".as_bytes());
+        assert!(read_image(&file, 0).unwrap_err().contains("non-ASCII"));
+
+        // Axis lengths whose product overflows usize
+        let huge = i64::MAX.to_string();
+        let file = header_block(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "16"),
+            ("NAXIS", "2"),
+            ("NAXIS1", &huge),
+            ("NAXIS2", "3"),
+        ]);
+        assert!(read_image(&file, 0).unwrap_err().contains("too large"));
+    }
+}
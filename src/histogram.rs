@@ -0,0 +1,93 @@
+//! Histogram equalization
+//!
+//! The cumulative distribution of the pixels within the display limits maps
+//! each value to the fraction of pixels below it, so that the colormap is
+//! spread evenly over the pixels rather than over the value range.
+
+use crate::pixels::{ComplexComponent, PixelData};
+
+/// Number of histogram bins between the limits
+const BINS: usize = 4096;
+
+/// Cumulative distribution of the pixels within a pair of limits
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramCdf {
+    /// Limits the histogram was built for
+    range: (f64, f64),
+    /// Fraction of the counted pixels below each bin edge (`BINS + 1` entries, 0 to 1)
+    edges: Vec<f64>,
+}
+
+impl HistogramCdf {
+    /// Build from the finite pixels (of `component`, for complex data) within
+    /// `range` = (min, max). Pixels with a nonzero entry in `exclude` are
+    /// skipped like NaN. Without any such pixels the mapping is linear.
+    pub fn new(pixels: &PixelData, component: ComplexComponent, exclude: Option<&[u8]>, range: (f64, f64)) -> Self {
+        let (min_val, max_val) = range;
+        let span = max_val - min_val;
+        let mut counts = vec![0u64; BINS];
+        if span > 0.0 {
+            for v in pixels.finite_values(component, exclude, 1) {
+                if (min_val..=max_val).contains(&v) {
+                    let bin = ((v - min_val) / span * BINS as f64) as usize;
+                    counts[bin.min(BINS - 1)] += 1;
+                }
+            }
+        }
+
+        let total: u64 = counts.iter().sum();
+        let edges = if total == 0 {
+            (0..=BINS).map(|i| i as f64 / BINS as f64).collect()
+        } else {
+            let mut below = 0;
+            std::iter::once(0.0)
+                .chain(counts.iter().map(|&count| {
+                    below += count;
+                    below as f64 / total as f64
+                }))
+                .collect()
+        };
+        HistogramCdf { range, edges }
+    }
+
+    /// Limits the histogram was built for
+    pub fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    /// Equalized value of `x`, a value normalized to the limits (0-1)
+    pub fn apply(&self, x: f64) -> f64 {
+        let pos = x.clamp(0.0, 1.0) * BINS as f64;
+        let bin = (pos as usize).min(BINS - 1);
+        let (low, high) = (self.edges[bin], self.edges[bin + 1]);
+        low + (pos - bin as f64) * (high - low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equalization() {
+        // Three of four pixels at the bottom, plus values outside the limits
+        let data = PixelData::from(vec![0.0, 0.0, 0.0, 1.0, f64::NAN, 5.0, -5.0]);
+        let cdf = HistogramCdf::new(&data, ComplexComponent::default(), None, (0.0, 1.0));
+        assert_eq!(cdf.range(), (0.0, 1.0));
+        assert_eq!(cdf.apply(0.0), 0.0);
+        assert_eq!(cdf.apply(0.5), 0.75);
+        assert_eq!(cdf.apply(1.0), 1.0);
+
+        // Masked pixels are skipped
+        let cdf = HistogramCdf::new(&data, ComplexComponent::default(), Some(&[1, 1, 0, 0, 0, 0, 0]), (0.0, 1.0));
+        assert_eq!(cdf.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_empty_is_linear() {
+        let data = PixelData::from(vec![f32::NAN, 7.0]);
+        let cdf = HistogramCdf::new(&data, ComplexComponent::default(), None, (0.0, 1.0));
+        assert!((cdf.apply(0.3) - 0.3).abs() < 1e-12);
+        assert_eq!(cdf.apply(2.0), 1.0);
+    }
+}
@@ -23,6 +23,14 @@ use web_sys::HtmlCanvasElement;
 mod app;
 mod colormap;
 mod colormap_luts;
+mod cube;
+mod dtype;
+mod fits;
+mod histogram;
+mod limits;
+mod npy;
+mod pixels;
+mod rgb;
 mod transform;
 mod widget;
 
@@ -118,17 +126,14 @@ impl ViewerHandle {
     /// * `buffer` - ArrayBuffer containing the raw pixel data
     /// * `width` - Image width in pixels
     /// * `height` - Image height in pixels
-    /// * `array_type` - Rust-style type specifier:
-    ///   - "i8" (signed 8-bit integer)
-    ///   - "u8" (unsigned 8-bit integer)
-    ///   - "i16" (signed 16-bit integer)
-    ///   - "u16" (unsigned 16-bit integer)
-    ///   - "i32" (signed 32-bit integer)
-    ///   - "u32" (unsigned 32-bit integer)
-    ///   - "i64" (signed 64-bit integer)
-    ///   - "u64" (unsigned 64-bit integer)
-    ///   - "f32" (32-bit float)
-    ///   - "f64" (64-bit float, default)
+    /// * `array_type` - Element type of the buffer, in any of these forms:
+    ///   - Rust-style: "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "f16", "bf16", "f32", "f64",
+    ///     "c64", "c128"
+    ///   - NumPy-style, with optional byte order prefix: "<f4", ">i2", "|u1", "uint16", "float64"
+    ///   - TypedArray names: "Int16Array", "Float32Array", "BigUint64Array", ...
+    ///
+    /// Unrecognized type names and buffers whose length does not match
+    /// `width * height` elements are rejected with an error.
     #[wasm_bindgen(js_name = setImageData)]
     pub fn set_image_data(
         &self,
@@ -137,28 +142,365 @@ impl ViewerHandle {
         height: u32,
         array_type: &str,
     ) -> Result<(), JsValue> {
-        let pixels = convert_buffer_to_f64(buffer, array_type)?;
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
 
         let expected_len = (width as usize) * (height as usize);
-        if pixels.len() != expected_len {
+        let expected_bytes = expected_len * dtype.size();
+        if bytes.len() != expected_bytes {
             return Err(JsValue::from_str(&format!(
-                "Buffer size mismatch: expected {} pixels ({}x{}), got {}",
+                "Buffer size mismatch: expected {} pixels ({}x{}) of {} = {} bytes, got {} bytes",
                 expected_len,
                 width,
                 height,
-                pixels.len()
+                dtype,
+                expected_bytes,
+                bytes.len()
             )));
         }
 
-        // Determine if the source data is integer-typed (for display formatting)
-        let is_integer = matches!(
-            array_type,
-            "i8" | "u8" | "i16" | "u16" |
-            "i32" | "u32" | "i64" | "u64"
-        );
+        let pixels = dtype.decode(&bytes, byte_order).map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_image(pixels, width, height);
+
+        Ok(())
+    }
+
+    /// Set the image data from a 2D view into a larger buffer.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer containing the pixel data (and possibly other data)
+    /// * `width` - Image width in pixels
+    /// * `height` - Image height in pixels
+    /// * `array_type` - Element type of the buffer (same forms as `setImageData`)
+    /// * `byte_offset` - Offset in bytes of the first pixel (row 0, column 0)
+    /// * `row_stride` - Distance in bytes between the starts of consecutive rows
+    /// * `col_stride` - Distance in bytes between consecutive pixels within a row
+    ///
+    /// Strides follow the NumPy convention, so a Fortran-order array is read with
+    /// `row_stride = itemsize` and `col_stride = height * itemsize`. Only the
+    /// bytes spanned by the view are copied out of the buffer.
+    #[wasm_bindgen(js_name = setImageDataStrided)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_image_data_strided(
+        &self,
+        buffer: &js_sys::ArrayBuffer,
+        width: u32,
+        height: u32,
+        array_type: &str,
+        byte_offset: u32,
+        row_stride: u32,
+        col_stride: u32,
+    ) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let layout = dtype::StridedLayout {
+            byte_offset: 0,
+            row_stride: row_stride as usize,
+            col_stride: col_stride as usize,
+        };
+
+        let view_len = layout
+            .required_len(width as usize, height as usize, dtype.size())
+            .ok_or_else(|| JsValue::from_str("Strided layout overflows"))?;
+        let buffer_len = buffer.byte_length() as usize;
+        if (byte_offset as usize).checked_add(view_len).is_none_or(|end| end > buffer_len) {
+            return Err(JsValue::from_str(&format!(
+                "Strided view out of bounds: {}x{} {} image at byte offset {} spans {} bytes, buffer has {}",
+                width, height, dtype, byte_offset, view_len, buffer_len
+            )));
+        }
+
+        let bytes = js_sys::Uint8Array::new_with_byte_offset_and_length(buffer, byte_offset, view_len as u32).to_vec();
+        let pixels = dtype
+            .decode_strided(&bytes, byte_order, width as usize, height as usize, &layout)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_image(pixels, width, height);
+
+        Ok(())
+    }
+
+    /// Overwrite a rectangular region of the current image, e.g. for chunked
+    /// live readout.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Image coordinates of the region's first pixel
+    /// * `width`, `height` - Region size in pixels
+    /// * `buffer` - ArrayBuffer with the region's pixels in row-major order
+    /// * `array_type` - Element type of the buffer; must match the current image
+    /// * `keep_limits` - If true, the display limits are left as they are; if
+    ///   false they follow the updated auto-scaling range. When omitted, the
+    ///   limits policy decides.
+    ///
+    /// Only the rows touched by the region are rescanned for min/max, and only
+    /// the region of the texture is redrawn unless the limits change.
+    #[wasm_bindgen(js_name = updateRegion)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_region(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        buffer: &js_sys::ArrayBuffer,
+        array_type: &str,
+        keep_limits: Option<bool>,
+    ) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+
+        let expected_bytes = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|n| n.checked_mul(dtype.size()))
+            .ok_or_else(|| JsValue::from_str(&format!("Region of {}x{} {} is too large", width, height, dtype)))?;
+        if bytes.len() != expected_bytes {
+            return Err(JsValue::from_str(&format!(
+                "Buffer size mismatch: expected {}x{} {} = {} bytes, got {} bytes",
+                width,
+                height,
+                dtype,
+                expected_bytes,
+                bytes.len()
+            )));
+        }
+
+        let patch = dtype.decode(&bytes, byte_order).map_err(|e| JsValue::from_str(&e))?;
+        let mut widget = self.widget.borrow_mut();
+        widget
+            .update_region(x, y, width, &patch, keep_limits)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Set a color image, displayed directly without stretch or colormap.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer containing the channel data
+    /// * `width` - Image width in pixels
+    /// * `height` - Image height in pixels
+    /// * `array_type` - Channel element type: "u8" (0-255) or a float type (0-1), in
+    ///   any of the forms accepted by `setImageData`
+    /// * `channels` - Number of channels: 3 (RGB) or 4 (RGBA)
+    /// * `layout` - "interleaved" (RGBRGB..., height x width x channels) or
+    ///   "planar" (RRR...GGG...BBB..., channels x height x width)
+    ///
+    /// Pan/zoom/rotation, hover (all channels) and click callbacks work as for
+    /// scalar images.
+    #[wasm_bindgen(js_name = setRgbData)]
+    pub fn set_rgb_data(
+        &self,
+        buffer: &js_sys::ArrayBuffer,
+        width: u32,
+        height: u32,
+        array_type: &str,
+        channels: u32,
+        layout: &str,
+    ) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let layout = rgb::ChannelLayout::parse(layout).map_err(|e| JsValue::from_str(&e))?;
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+        let image = rgb::RgbImage::decode(
+            &bytes,
+            dtype,
+            byte_order,
+            width as usize,
+            height as usize,
+            channels as usize,
+            layout,
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_rgb_image(image, width, height);
+
+        Ok(())
+    }
+
+    /// Set a cube of same-shaped 2D planes, browsed one slice at a time.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer with `depth` consecutive row-major planes (C order depth x height x width)
+    /// * `depth` - Number of planes
+    /// * `width` - Plane width in pixels
+    /// * `height` - Plane height in pixels
+    /// * `array_type` - Element type of the buffer (same forms as `setImageData`)
+    ///
+    /// The current slice index is kept (clamped to the new depth), as are the
+    /// display settings. Use `setSlice` to change the displayed plane.
+    #[wasm_bindgen(js_name = setCubeData)]
+    pub fn set_cube_data(
+        &self,
+        buffer: &js_sys::ArrayBuffer,
+        depth: u32,
+        width: u32,
+        height: u32,
+        array_type: &str,
+    ) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+
+        let expected_bytes = [depth, height, width]
+            .iter()
+            .try_fold(dtype.size(), |acc, &n| acc.checked_mul(n as usize))
+            .ok_or_else(|| {
+                JsValue::from_str(&format!("Cube of {}x{}x{} {} is too large", depth, height, width, dtype))
+            })?;
+        if bytes.len() != expected_bytes {
+            return Err(JsValue::from_str(&format!(
+                "Buffer size mismatch: expected {}x{}x{} {} = {} bytes, got {} bytes",
+                depth,
+                height,
+                width,
+                dtype,
+                expected_bytes,
+                bytes.len()
+            )));
+        }
+
+        let data = dtype.decode(&bytes, byte_order).map_err(|e| JsValue::from_str(&e))?;
+        let cube = cube::Cube::new(data, vec![depth as usize, height as usize, width as usize])
+            .map_err(|e| JsValue::from_str(&e))?;
 
         let mut widget = self.widget.borrow_mut();
-        widget.set_image(pixels, width, height, is_integer);
+        widget.set_cube(cube);
+
+        Ok(())
+    }
+
+    /// Set an N-dimensional array, displayed as 2D planes.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer with the array in C order (last axis varies fastest)
+    /// * `shape` - Length of every axis, e.g. [time, wavelength, height, width]
+    /// * `array_type` - Element type of the buffer (same forms as `setImageData`)
+    ///
+    /// The last two axes are displayed (change with `setDisplayAxes`) and every
+    /// other axis gets an index slider. Axis indices and display settings are
+    /// kept when an array with the same number of axes is loaded.
+    #[wasm_bindgen(js_name = setArrayData)]
+    pub fn set_array_data(&self, buffer: &js_sys::ArrayBuffer, shape: Vec<u32>, array_type: &str) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+        let shape: Vec<usize> = shape.into_iter().map(|len| len as usize).collect();
+
+        let expected_bytes = shape
+            .iter()
+            .try_fold(dtype.size(), |acc, &n| acc.checked_mul(n))
+            .ok_or_else(|| JsValue::from_str(&format!("Array shape {:?} of {} is too large", shape, dtype)))?;
+        if bytes.len() != expected_bytes {
+            return Err(JsValue::from_str(&format!(
+                "Buffer size mismatch: expected shape {:?} of {} = {} bytes, got {} bytes",
+                shape,
+                dtype,
+                expected_bytes,
+                bytes.len()
+            )));
+        }
+
+        let data = dtype.decode(&bytes, byte_order).map_err(|e| JsValue::from_str(&e))?;
+        let cube = cube::Cube::new(data, shape).map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_cube(cube);
+
+        Ok(())
+    }
+
+    /// Set a three-channel composite from separate scalar arrays.
+    ///
+    /// # Arguments
+    /// * `red`, `green`, `blue` - ArrayBuffers with the pixel data of each channel
+    /// * `width` - Image width in pixels
+    /// * `height` - Image height in pixels
+    /// * `array_type` - Element type of all three buffers (same forms as `setImageData`)
+    ///
+    /// Unlike `setRgbData`, each channel has its own display limits, stretch and
+    /// contrast/bias before the channels are combined. Stretch and contrast
+    /// setters act on the active channel (see `setActiveChannel`).
+    #[wasm_bindgen(js_name = setCompositeData)]
+    pub fn set_composite_data(
+        &self,
+        red: &js_sys::ArrayBuffer,
+        green: &js_sys::ArrayBuffer,
+        blue: &js_sys::ArrayBuffer,
+        width: u32,
+        height: u32,
+        array_type: &str,
+    ) -> Result<(), JsValue> {
+        let (dtype, byte_order) = dtype::DType::parse(array_type).map_err(|e| JsValue::from_str(&e))?;
+        let expected_bytes = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|n| n.checked_mul(dtype.size()))
+            .ok_or_else(|| JsValue::from_str(&format!("Image of {}x{} {} is too large", width, height, dtype)))?;
+
+        let mut channels = Vec::with_capacity(3);
+        for (name, buffer) in [("red", red), ("green", green), ("blue", blue)] {
+            let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+            if bytes.len() != expected_bytes {
+                return Err(JsValue::from_str(&format!(
+                    "Buffer size mismatch for {} channel: expected {}x{} {} = {} bytes, got {} bytes",
+                    name,
+                    width,
+                    height,
+                    dtype,
+                    expected_bytes,
+                    bytes.len()
+                )));
+            }
+            channels.push(dtype.decode(&bytes, byte_order).map_err(|e| JsValue::from_str(&e))?);
+        }
+        let channels: [pixels::PixelData; 3] = channels.try_into().expect("three channels");
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_composite_image(channels, width, height);
+
+        Ok(())
+    }
+
+    /// Set the image data from the contents of a FITS file.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer containing the complete FITS file
+    /// * `hdu_index` - Index of the HDU to display (0 = primary HDU, 1 = first extension, ...)
+    ///
+    /// The HDU must be the primary HDU or an IMAGE extension. BSCALE/BZERO are
+    /// applied and BLANK pixels are shown as NaN. For images with more than two
+    /// axes, the first 2D plane is displayed.
+    #[wasm_bindgen(js_name = setFitsData)]
+    pub fn set_fits_data(&self, buffer: &js_sys::ArrayBuffer, hdu_index: u32) -> Result<(), JsValue> {
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+        let image = fits::read_image(&bytes, hdu_index as usize)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_image(image.pixels, image.width, image.height);
+
+        Ok(())
+    }
+
+    /// Set the image data from the contents of a NumPy `.npy` file or `.npz` archive.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer containing the complete `.npy` or `.npz` file
+    /// * `array_name` - For `.npz` archives, the name of the array to display
+    ///   (defaults to the first array); ignored for `.npy` files
+    ///
+    /// Dtype, byte order, memory order and shape are all taken from the file
+    /// header. For arrays with more than two dimensions, the first 2D plane
+    /// (index 0 along all leading axes) is displayed.
+    #[wasm_bindgen(js_name = setNpyData)]
+    pub fn set_npy_data(&self, buffer: &js_sys::ArrayBuffer, array_name: Option<String>) -> Result<(), JsValue> {
+        let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+        let image = if npy::is_npz(&bytes) {
+            npy::read_npz(&bytes, array_name.as_deref())
+        } else {
+            npy::read_npy(&bytes)
+        }
+        .map_err(|e| JsValue::from_str(&e))?;
+
+        let mut widget = self.widget.borrow_mut();
+        widget.set_image(image.pixels, image.width, image.height);
 
         Ok(())
     }
@@ -278,41 +620,233 @@ impl ViewerHandle {
         self.widget.borrow_mut().set_bias(bias);
     }
 
-    /// Get current stretch mode as string: "linear", "log", or "symmetric"
+    /// Get current stretch mode as string: "linear", "log", "sqrt", "squared",
+    /// "asinh", "power", "sinh", "histeq", or "symmetric"
     #[wasm_bindgen(js_name = getStretchMode)]
     pub fn get_stretch_mode(&self) -> String {
         let widget = self.widget.borrow();
         if widget.is_symmetric() {
             "symmetric".to_string()
         } else {
-            match widget.stretch_type() {
-                widget::StretchType::Linear => "linear".to_string(),
-                widget::StretchType::Log => "log".to_string(),
-            }
+            widget.stretch_type().name().to_string()
         }
     }
 
-    /// Set stretch mode: "linear", "log", or "symmetric"
+    /// Set stretch mode: "linear", "log", "sqrt", "squared", "asinh", "power",
+    /// "sinh", "histeq" (histogram equalization), or "symmetric"
     #[wasm_bindgen(js_name = setStretchMode)]
     pub fn set_stretch_mode(&self, mode: &str) {
         let mut widget = self.widget.borrow_mut();
-        match mode {
-            "linear" => {
-                widget.set_symmetric(false);
-                widget.set_stretch_type(widget::StretchType::Linear);
-            }
-            "log" => {
-                widget.set_symmetric(false);
-                widget.set_stretch_type(widget::StretchType::Log);
-            }
-            "symmetric" => {
-                widget.set_stretch_type(widget::StretchType::Linear);
-                widget.set_symmetric(true);
-            }
-            _ => {} // Ignore invalid modes
+        if mode == "symmetric" {
+            widget.set_stretch_type(widget::StretchType::Linear);
+            widget.set_symmetric(true);
+        } else if let Some(stretch_type) = widget::StretchType::from_name(mode) {
+            widget.set_symmetric(false);
+            widget.set_stretch_type(stretch_type);
+        } // Ignore invalid modes
+    }
+
+    /// Get the exponent of the log stretch
+    #[wasm_bindgen(js_name = getLogExponent)]
+    pub fn get_log_exponent(&self) -> f64 {
+        self.widget.borrow().stretch_params().log_exponent
+    }
+
+    /// Set the exponent of the log stretch (greater than 1, default 1000,
+    /// which suits optical images; smaller values suit data with less
+    /// dynamic range)
+    #[wasm_bindgen(js_name = setLogExponent)]
+    pub fn set_log_exponent(&self, exponent: f64) {
+        self.widget.borrow_mut().set_log_exponent(exponent);
+    }
+
+    /// Get the softening of the asinh stretch
+    #[wasm_bindgen(js_name = getAsinhSoftening)]
+    pub fn get_asinh_softening(&self) -> f64 {
+        self.widget.borrow().stretch_params().asinh_softening
+    }
+
+    /// Set the softening of the asinh stretch (positive, default 0.1):
+    /// values well below it are stretched linearly, values above it
+    /// logarithmically
+    #[wasm_bindgen(js_name = setAsinhSoftening)]
+    pub fn set_asinh_softening(&self, softening: f64) {
+        self.widget.borrow_mut().set_asinh_softening(softening);
+    }
+
+    /// Get the exponent of the power stretch
+    #[wasm_bindgen(js_name = getPowerExponent)]
+    pub fn get_power_exponent(&self) -> f64 {
+        self.widget.borrow().stretch_params().power_exponent
+    }
+
+    /// Set the exponent of the power stretch (positive, default 3)
+    #[wasm_bindgen(js_name = setPowerExponent)]
+    pub fn set_power_exponent(&self, exponent: f64) {
+        self.widget.borrow_mut().set_power_exponent(exponent);
+    }
+
+    /// Get which quantity of complex data is displayed:
+    /// "magnitude", "phase", "real", "imag", or "logpower"
+    #[wasm_bindgen(js_name = getComplexComponent)]
+    pub fn get_complex_component(&self) -> String {
+        self.widget.borrow().complex_component().name().to_string()
+    }
+
+    /// Set which quantity of complex data is displayed:
+    /// "magnitude", "phase", "real", "imag", or "logpower".
+    /// Has no visible effect for real-valued images.
+    #[wasm_bindgen(js_name = setComplexComponent)]
+    pub fn set_complex_component(&self, component: &str) {
+        if let Some(component) = pixels::ComplexComponent::from_name(component) {
+            self.widget.borrow_mut().set_complex_component(component);
+        } // Ignore invalid components
+    }
+
+    /// Get the index of the displayed cube slice (along the first non-displayed axis)
+    #[wasm_bindgen(js_name = getSlice)]
+    pub fn get_slice(&self) -> u32 {
+        self.widget.borrow().slice_index() as u32
+    }
+
+    /// Display another cube slice (along the first non-displayed axis, clamped
+    /// to its length). Has no effect unless a cube is loaded.
+    #[wasm_bindgen(js_name = setSlice)]
+    pub fn set_slice(&self, index: u32) {
+        self.widget.borrow_mut().set_slice(index as usize);
+    }
+
+    /// Get the number of cube slices along the first non-displayed axis
+    /// (0 if no cube is loaded)
+    #[wasm_bindgen(js_name = getNumSlices)]
+    pub fn get_num_slices(&self) -> u32 {
+        self.widget.borrow().num_slices() as u32
+    }
+
+    /// Get where cube scale limits come from: "slice" (the displayed slice)
+    /// or "global" (the whole cube)
+    #[wasm_bindgen(js_name = getSliceLimits)]
+    pub fn get_slice_limits(&self) -> String {
+        self.widget.borrow().slice_limits().name().to_string()
+    }
+
+    /// Set where cube scale limits come from: "slice" (the displayed slice)
+    /// or "global" (the whole cube). Limits are recomputed when this changes.
+    #[wasm_bindgen(js_name = setSliceLimits)]
+    pub fn set_slice_limits(&self, mode: &str) {
+        if let Some(mode) = cube::SliceLimits::from_name(mode) {
+            self.widget.borrow_mut().set_slice_limits(mode);
+        } // Ignore invalid modes
+    }
+
+    /// Get the shape of the loaded cube or N-D array (empty if none is loaded)
+    #[wasm_bindgen(js_name = getShape)]
+    pub fn get_shape(&self) -> Vec<u32> {
+        self.widget.borrow().cube_shape().iter().map(|&len| len as u32).collect()
+    }
+
+    /// Get the index along every axis of the loaded cube or N-D array
+    /// (entries of the two displayed axes are 0)
+    #[wasm_bindgen(js_name = getAxisIndex)]
+    pub fn get_axis_index(&self) -> Vec<u32> {
+        let widget = self.widget.borrow();
+        let (y_axis, x_axis) = widget.display_axes().unwrap_or_default();
+        widget
+            .axis_index()
+            .iter()
+            .enumerate()
+            .map(|(axis, &index)| if axis == y_axis || axis == x_axis { 0 } else { index as u32 })
+            .collect()
+    }
+
+    /// Move along a non-displayed axis (index clamped to the axis length).
+    /// Has no effect for the displayed axes or when no cube is loaded.
+    #[wasm_bindgen(js_name = setAxisIndex)]
+    pub fn set_axis_index(&self, axis: u32, index: u32) {
+        self.widget.borrow_mut().set_axis_index(axis as usize, index as usize);
+    }
+
+    /// Get the axes shown along image rows and columns as [yAxis, xAxis]
+    /// (empty if no cube is loaded)
+    #[wasm_bindgen(js_name = getDisplayAxes)]
+    pub fn get_display_axes(&self) -> Vec<u32> {
+        match self.widget.borrow().display_axes() {
+            Some((y_axis, x_axis)) => vec![y_axis as u32, x_axis as u32],
+            None => Vec::new(),
         }
     }
 
+    /// Choose the axes shown along image rows (`y_axis`) and columns (`x_axis`).
+    /// Limits are recomputed; pan and mask are reset if the plane shape changes.
+    #[wasm_bindgen(js_name = setDisplayAxes)]
+    pub fn set_display_axes(&self, y_axis: u32, x_axis: u32) -> Result<(), JsValue> {
+        self.widget
+            .borrow_mut()
+            .set_display_axes(y_axis as usize, x_axis as usize)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Set one label per axis, shown next to the index sliders and in the
+    /// hover readout. An empty list restores the default "axis N" labels.
+    #[wasm_bindgen(js_name = setAxisLabels)]
+    pub fn set_axis_labels(&self, labels: Vec<String>) -> Result<(), JsValue> {
+        self.widget.borrow_mut().set_axis_labels(labels).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Get whether the cube is being played as a movie
+    #[wasm_bindgen(js_name = getPlaying)]
+    pub fn get_playing(&self) -> bool {
+        self.widget.borrow().is_playing()
+    }
+
+    /// Start or stop cube playback. Has no effect unless a cube is loaded.
+    #[wasm_bindgen(js_name = setPlaying)]
+    pub fn set_playing(&self, playing: bool) {
+        self.widget.borrow_mut().set_playing(playing);
+    }
+
+    /// Get the cube playback rate in frames per second
+    #[wasm_bindgen(js_name = getPlaybackFps)]
+    pub fn get_playback_fps(&self) -> f64 {
+        self.widget.borrow().playback_fps()
+    }
+
+    /// Set the cube playback rate in frames per second (clamped to 0.1-60)
+    #[wasm_bindgen(js_name = setPlaybackFps)]
+    pub fn set_playback_fps(&self, fps: f64) {
+        self.widget.borrow_mut().set_playback_fps(fps);
+    }
+
+    /// Get what playback does at the ends of the cube: "loop" or "bounce"
+    #[wasm_bindgen(js_name = getPlaybackMode)]
+    pub fn get_playback_mode(&self) -> String {
+        self.widget.borrow().playback_mode().name().to_string()
+    }
+
+    /// Set what playback does at the ends of the cube: "loop" (wrap to the
+    /// first frame) or "bounce" (reverse direction)
+    #[wasm_bindgen(js_name = setPlaybackMode)]
+    pub fn set_playback_mode(&self, mode: &str) {
+        if let Some(mode) = cube::PlaybackMode::from_name(mode) {
+            self.widget.borrow_mut().set_playback_mode(mode);
+        } // Ignore invalid modes
+    }
+
+    /// Get the composite channel that stretch, contrast/bias and limit setters
+    /// act on: 0 (red), 1 (green) or 2 (blue)
+    #[wasm_bindgen(js_name = getActiveChannel)]
+    pub fn get_active_channel(&self) -> u32 {
+        self.widget.borrow().active_channel() as u32
+    }
+
+    /// Select the composite channel that stretch, contrast/bias and limit
+    /// setters act on: 0 (red), 1 (green) or 2 (blue).
+    /// Has no effect unless a composite is loaded.
+    #[wasm_bindgen(js_name = setActiveChannel)]
+    pub fn set_active_channel(&self, channel: u32) {
+        self.widget.borrow_mut().set_active_channel(channel as usize);
+    }
+
     /// Get visible image bounds as [xmin, xmax, ymin, ymax] in pixel coordinates.
     /// Returns the portion of the image currently visible in the viewport.
     /// If no image is loaded or bounds cannot be computed, returns [0, 0, 0, 0].
@@ -432,6 +966,42 @@ impl ViewerHandle {
         self.widget.borrow().colormap().name().to_string()
     }
 
+    /// Get the kind of the current colormap: "sequential", "diverging", "cyclic" or "qualitative"
+    #[wasm_bindgen(js_name = getColormapKind)]
+    pub fn get_colormap_kind(&self) -> String {
+        self.widget.borrow().colormap().kind().name().to_string()
+    }
+
+    /// Set the colormap by name (ignoring case), built-in or registered with `registerColormap`.
+    /// It becomes the colormap of the current mode: complex phase, symmetric or
+    /// standard (each remembers its own), so `getColormap` returns it.
+    /// Qualitative colormaps color each value as an integer label, without stretching.
+    /// Fails for color images and composites, and for qualitative colormaps on complex phase.
+    #[wasm_bindgen(js_name = setColormap)]
+    pub fn set_colormap(&self, name: &str) -> Result<(), JsValue> {
+        let colormap = colormap::Colormap::parse(name).map_err(|e| JsValue::from_str(&e))?;
+        self.widget.borrow_mut().set_colormap(colormap).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Set whether the colormap is reversed
+    #[wasm_bindgen(js_name = setColormapReversed)]
+    pub fn set_colormap_reversed(&self, reversed: bool) {
+        self.widget.borrow_mut().set_reversed(reversed);
+    }
+
+    /// Get the number of discrete colormap levels (0 when continuous)
+    #[wasm_bindgen(js_name = getColormapLevels)]
+    pub fn get_colormap_levels(&self) -> u32 {
+        self.widget.borrow().colormap_levels().unwrap_or(0)
+    }
+
+    /// Quantize the colormap into 2-256 flat levels for contour-like displays,
+    /// or make it continuous again with 0. Other values are ignored.
+    #[wasm_bindgen(js_name = setColormapLevels)]
+    pub fn set_colormap_levels(&self, levels: u32) {
+        self.widget.borrow_mut().set_colormap_levels((levels > 0).then_some(levels));
+    }
+
     /// Get whether the colormap is reversed
     #[wasm_bindgen(js_name = getColormapReversed)]
     pub fn get_colormap_reversed(&self) -> bool {
@@ -454,20 +1024,203 @@ impl ViewerHandle {
         self.widget.borrow_mut().set_value_range(min_val, max_val);
     }
 
+    /// Get what happens to the display limits when new data arrives:
+    /// "auto", "keep", "keep-if-modified" or "percentile"
+    #[wasm_bindgen(js_name = getLimitsPolicy)]
+    pub fn get_limits_policy(&self) -> String {
+        self.widget.borrow().limits_policy().name().to_string()
+    }
+
+    /// Set what happens to the display limits when new data arrives:
+    /// "auto" (recompute from every frame), "keep" (never change them),
+    /// "keep-if-modified" (recompute unless the user changed them) or
+    /// "percentile" (follow the central percentile range of every frame)
+    #[wasm_bindgen(js_name = setLimitsPolicy)]
+    pub fn set_limits_policy(&self, policy: &str) {
+        if let Some(policy) = limits::LimitsPolicy::from_name(policy) {
+            self.widget.borrow_mut().set_limits_policy(policy);
+        } // Ignore invalid policies
+    }
+
+    /// Get how automatic limits are computed: "minmax", "percentile",
+    /// "zscale" or "mad"
+    #[wasm_bindgen(js_name = getScaleMode)]
+    pub fn get_scale_mode(&self) -> String {
+        self.widget.borrow().scale_mode().name().to_string()
+    }
+
+    /// Set how automatic limits are computed: "minmax" (full finite range),
+    /// "percentile" (central percentile range), "zscale" (IRAF/DS9 ZScale) or
+    /// "mad" (median ± k·MAD). Limits are recomputed when this changes.
+    #[wasm_bindgen(js_name = setScaleMode)]
+    pub fn set_scale_mode(&self, mode: &str) {
+        if let Some(mode) = limits::ScaleMode::from_name(mode) {
+            self.widget.borrow_mut().set_scale_mode(mode);
+        } // Ignore invalid modes
+    }
+
+    /// Get the central percentile spanned by percentile limits
+    #[wasm_bindgen(js_name = getLimitsPercentile)]
+    pub fn get_limits_percentile(&self) -> f64 {
+        self.widget.borrow().limits_percentile()
+    }
+
+    /// Set the central percentile spanned by percentile limits (scale mode
+    /// or limits policy "percentile"); 0-100, default 99.5: the lowest and
+    /// highest 0.25% are clipped
+    #[wasm_bindgen(js_name = setLimitsPercentile)]
+    pub fn set_limits_percentile(&self, percent: f64) {
+        self.widget.borrow_mut().set_limits_percentile(percent);
+    }
+
+    /// Get k of the "mad" scale mode (median ± k·MAD)
+    #[wasm_bindgen(js_name = getMadFactor)]
+    pub fn get_mad_factor(&self) -> f64 {
+        self.widget.borrow().mad_factor()
+    }
+
+    /// Set k of the "mad" scale mode (median ± k·MAD; must be positive,
+    /// default 3)
+    #[wasm_bindgen(js_name = setMadFactor)]
+    pub fn set_mad_factor(&self, k: f64) {
+        self.widget.borrow_mut().set_mad_factor(k);
+    }
+
+    // =========================================================================
+    // Mask overlay
+    // =========================================================================
+
+    /// Set a mask layer drawn as a translucent overlay on the image.
+    ///
+    /// # Arguments
+    /// * `buffer` - ArrayBuffer of one byte per pixel (u8 or bool), nonzero = masked
+    /// * `width` - Mask width in pixels (must match the image)
+    /// * `height` - Mask height in pixels (must match the image)
+    #[wasm_bindgen(js_name = setMaskData)]
+    pub fn set_mask_data(&self, buffer: &js_sys::ArrayBuffer, width: u32, height: u32) -> Result<(), JsValue> {
+        let mut widget = self.widget.borrow_mut();
+        let (img_width, img_height) = widget.dimensions();
+        if (width, height) != (img_width, img_height) {
+            return Err(JsValue::from_str(&format!(
+                "Mask shape {}x{} does not match image shape {}x{}",
+                width, height, img_width, img_height
+            )));
+        }
+        let mask = js_sys::Uint8Array::new(buffer).to_vec();
+        widget.set_mask(mask).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Remove the mask layer
+    #[wasm_bindgen(js_name = clearMask)]
+    pub fn clear_mask(&self) {
+        self.widget.borrow_mut().clear_mask();
+    }
+
+    /// Get whether the mask overlay is shown
+    #[wasm_bindgen(js_name = getShowMask)]
+    pub fn get_show_mask(&self) -> bool {
+        self.widget.borrow().show_mask()
+    }
+
+    /// Set whether the mask overlay is shown
+    #[wasm_bindgen(js_name = setShowMask)]
+    pub fn set_show_mask(&self, show: bool) {
+        self.widget.borrow_mut().set_show_mask(show);
+    }
+
+    /// Get the mask overlay color as [r, g, b] (0-255)
+    #[wasm_bindgen(js_name = getMaskColor)]
+    pub fn get_mask_color(&self) -> js_sys::Uint8Array {
+        let color = self.widget.borrow().mask_color();
+        let result = js_sys::Uint8Array::new_with_length(3);
+        result.copy_from(&[color.r(), color.g(), color.b()]);
+        result
+    }
+
+    /// Set the mask overlay color (0-255 per channel)
+    #[wasm_bindgen(js_name = setMaskColor)]
+    pub fn set_mask_color(&self, r: u8, g: u8, b: u8) {
+        self.widget.borrow_mut().set_mask_color(egui::Color32::from_rgb(r, g, b));
+    }
+
+    /// Get the mask overlay opacity (0-1)
+    #[wasm_bindgen(js_name = getMaskOpacity)]
+    pub fn get_mask_opacity(&self) -> f32 {
+        self.widget.borrow().mask_opacity()
+    }
+
+    /// Set the mask overlay opacity (0-1)
+    #[wasm_bindgen(js_name = setMaskOpacity)]
+    pub fn set_mask_opacity(&self, opacity: f32) {
+        self.widget.borrow_mut().set_mask_opacity(opacity);
+    }
+
+    /// Get whether masked pixels are ignored when computing min/max
+    #[wasm_bindgen(js_name = getMaskExcludesLimits)]
+    pub fn get_mask_excludes_limits(&self) -> bool {
+        self.widget.borrow().mask_excludes_limits()
+    }
+
+    /// Set whether masked pixels are ignored (like NaN) when computing min/max
+    #[wasm_bindgen(js_name = setMaskExcludesLimits)]
+    pub fn set_mask_excludes_limits(&self, exclude: bool) {
+        self.widget.borrow_mut().set_mask_excludes_limits(exclude);
+    }
+
+    /// Get a special color ("bad", "under" or "over") as [r, g, b]
+    #[wasm_bindgen(js_name = getSpecialColor)]
+    pub fn get_special_color(&self, which: &str) -> Result<js_sys::Uint8Array, JsValue> {
+        let kind = widget::SpecialColor::parse(which).map_err(|e| JsValue::from_str(&e))?;
+        let color = self.widget.borrow().special_color(kind).color;
+        let result = js_sys::Uint8Array::new_with_length(3);
+        result.copy_from(&[color.r(), color.g(), color.b()]);
+        Ok(result)
+    }
+
+    /// Set the color of NaN/Inf ("bad"), below-range ("under") or above-range
+    /// ("over") pixels (0-255 per channel)
+    #[wasm_bindgen(js_name = setSpecialColor)]
+    pub fn set_special_color(&self, which: &str, r: u8, g: u8, b: u8) -> Result<(), JsValue> {
+        let kind = widget::SpecialColor::parse(which).map_err(|e| JsValue::from_str(&e))?;
+        self.widget.borrow_mut().set_special_color(kind, egui::Color32::from_rgb(r, g, b));
+        Ok(())
+    }
+
+    /// Get whether a special color ("bad", "under" or "over") is used
+    #[wasm_bindgen(js_name = getSpecialColorEnabled)]
+    pub fn get_special_color_enabled(&self, which: &str) -> Result<bool, JsValue> {
+        let kind = widget::SpecialColor::parse(which).map_err(|e| JsValue::from_str(&e))?;
+        Ok(self.widget.borrow().special_color(kind).enabled)
+    }
+
+    /// Enable or disable a special color ("bad", "under" or "over")
+    #[wasm_bindgen(js_name = setSpecialColorEnabled)]
+    pub fn set_special_color_enabled(&self, which: &str, enabled: bool) -> Result<(), JsValue> {
+        let kind = widget::SpecialColor::parse(which).map_err(|e| JsValue::from_str(&e))?;
+        self.widget.borrow_mut().set_special_color_enabled(kind, enabled);
+        Ok(())
+    }
+
     // =========================================================================
     // Callback registration
     // =========================================================================
 
     /// Register a callback to be called when viewer state changes.
     /// The callback receives an object with the current state:
-    /// { contrast, bias, stretchMode, zoom, xlim, ylim, colormap, colormapReversed, vmin, vmax }
+    /// { contrast, bias, stretchMode, logExponent, asinhSoftening, powerExponent, zoom, xlim, ylim, colormap, colormapReversed, colormapLevels, vmin, vmax,
+    /// limitsPolicy, scaleMode, limitsPercentile, madFactor, activeChannel },
+    /// plus { slice, sliceLimits, playing, axisIndex, displayAxes } when a cube
+    /// or N-D array is loaded (`axisIndex` has null for the displayed axes)
     #[wasm_bindgen(js_name = onStateChange)]
     pub fn on_state_change(&self, callback: js_sys::Function) {
         self.callbacks.borrow_mut().on_state_change = Some(callback);
     }
 
     /// Register a callback to be called when the user clicks on the image.
-    /// The callback receives: { x, y, value } in image coordinates.
+    /// The callback receives: { x, y, value } in image coordinates, where `value`
+    /// is a number ([re, im] for complex data). Color images report
+    /// { x, y, channels } with one number per channel instead. When a cube is
+    /// loaded, the displayed `slice` and the full N-D `index` are included.
     #[wasm_bindgen(js_name = onClick)]
     pub fn on_click(&self, callback: js_sys::Function) {
         self.callbacks.borrow_mut().on_click = Some(callback);
@@ -482,65 +1235,56 @@ impl ViewerHandle {
     }
 }
 
-/// Convert a JavaScript ArrayBuffer to Vec<f64> based on ArrayType string.
-/// ArrayType values are Rust-style type specifiers (i8, u8, i16, etc.).
+/// Register a colormap for all viewers, replacing any registered colormap of
+/// the same name. It shows up in the colormap picker and works with `setColormap`.
+///
+/// # Arguments
+/// * `name` - Display name; must not be that of a built-in colormap
+/// * `stops` - Array of `[r, g, b]` colors (0-255) evenly spaced from 0 to 1,
+///   such as a 256-entry lookup table, or of `[position, [r, g, b]]` control
+///   points with positions increasing from 0 to 1
+/// * `kind` - "sequential", "diverging" (listed first in symmetric mode), "cyclic"
+///   (listed first for complex phase; values wrap around) or "qualitative" (one
+///   color per integer label, used as listed)
+/// * `color_space` - Where colors between stops are interpolated: "srgb", "linear" or "oklab"
 #[cfg(target_arch = "wasm32")]
-fn convert_buffer_to_f64(buffer: &js_sys::ArrayBuffer, array_type: &str) -> Result<Vec<f64>, JsValue> {
-    match array_type {
-        "i8" => {
-            let view = js_sys::Int8Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "u8" => {
-            let view = js_sys::Uint8Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "i16" => {
-            let view = js_sys::Int16Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "u16" => {
-            let view = js_sys::Uint16Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "i32" => {
-            let view = js_sys::Int32Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "u32" => {
-            let view = js_sys::Uint32Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "i64" => {
-            let view = js_sys::BigInt64Array::new(buffer);
-            let len = view.length() as usize;
-            let mut result = Vec::with_capacity(len);
-            for i in 0..len {
-                let val = view.get_index(i as u32);
-                // i64 converts to f64 directly (may lose precision for very large values)
-                result.push(val as f64);
-            }
-            Ok(result)
-        }
-        "u64" => {
-            let view = js_sys::BigUint64Array::new(buffer);
-            let len = view.length() as usize;
-            let mut result = Vec::with_capacity(len);
-            for i in 0..len {
-                let val = view.get_index(i as u32);
-                // u64 converts to f64 directly (may lose precision for very large values)
-                result.push(val as f64);
-            }
-            Ok(result)
-        }
-        "f32" => {
-            let view = js_sys::Float32Array::new(buffer);
-            Ok(view.to_vec().into_iter().map(|v| v as f64).collect())
-        }
-        "f64" | _ => {
-            // Default to Float64
-            let view = js_sys::Float64Array::new(buffer);
-            Ok(view.to_vec())
-        }
-    }
+#[wasm_bindgen(js_name = registerColormap)]
+pub fn register_colormap(name: &str, stops: js_sys::Array, kind: &str, color_space: &str) -> Result<(), JsValue> {
+    let kind = colormap::ColormapKind::parse(kind).map_err(|e| JsValue::from_str(&e))?;
+    let space = colormap::ColorSpace::parse(color_space).map_err(|e| JsValue::from_str(&e))?;
+    let points = parse_colormap_stops(&stops).map_err(|e| JsValue::from_str(&e))?;
+    colormap::register_colormap(name, &points, space, kind).map_err(|e| JsValue::from_str(&e))?;
+    Ok(())
+}
+
+/// Read colormap stops: all `[r, g, b]` colors, or all `[position, [r, g, b]]` control points
+#[cfg(target_arch = "wasm32")]
+fn parse_colormap_stops(stops: &js_sys::Array) -> Result<Vec<(f64, [f64; 3])>, String> {
+    let rgb = |value: &JsValue| -> Option<[f64; 3]> {
+        let array = value.dyn_ref::<js_sys::Array>().filter(|array| array.length() == 3)?;
+        let channels = array.iter().map(|v| v.as_f64()).collect::<Option<Vec<f64>>>()?;
+        Some([channels[0], channels[1], channels[2]])
+    };
+    let control_point = |value: &JsValue| -> Option<(f64, [f64; 3])> {
+        let array = value.dyn_ref::<js_sys::Array>().filter(|array| array.length() == 2)?;
+        Some((array.get(0).as_f64()?, rgb(&array.get(1))?))
+    };
+
+    let explicit = control_point(&stops.get(0)).is_some();
+    let last = stops.length().max(2) - 1;
+    stops
+        .iter()
+        .enumerate()
+        .map(|(i, stop)| {
+            let point = if explicit {
+                control_point(&stop)
+            } else {
+                rgb(&stop).map(|color| (i as f64 / last as f64, color))
+            };
+            point.ok_or_else(|| {
+                let expected = if explicit { "[position, [r, g, b]]" } else { "[r, g, b]" };
+                format!("Colormap stop {} must be {}, like the first stop", i, expected)
+            })
+        })
+        .collect()
 }
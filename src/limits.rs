@@ -0,0 +1,309 @@
+//! Display limit policies and estimators
+//!
+//! A `LimitsPolicy` decides what happens to the display limits when new data
+//! arrives (a new frame, cube or region update): follow the data, or keep
+//! what the user has set. A `ScaleMode` decides how the automatic limits are
+//! estimated from the data, so that a few hot pixels don't swamp the range.
+//! Percentile tracking follows a central percentile range of every frame
+//! whatever the scale mode.
+
+/// Default central percentile kept by percentile limits
+pub const DEFAULT_PERCENTILE: f64 = 99.5;
+/// Percentiles offered as presets in the scale mode picker
+pub const PERCENTILE_PRESETS: [f64; 3] = [99.5, 99.0, 98.0];
+/// Default k of median ± k·MAD limits
+pub const DEFAULT_MAD_FACTOR: f64 = 3.0;
+
+/// Number of values ZScale fits its line to
+const ZSCALE_SAMPLES: usize = 1000;
+/// ZScale contrast: the fitted slope is divided by this
+const ZSCALE_CONTRAST: f64 = 0.25;
+/// ZScale rejection threshold, in standard deviations of the fit residuals
+const ZSCALE_REJECT_SIGMA: f64 = 2.5;
+/// Maximum number of ZScale rejection iterations
+const ZSCALE_MAX_ITERATIONS: usize = 5;
+/// Minimum number of values left after ZScale rejection
+const ZSCALE_MIN_PIXELS: usize = 5;
+/// Maximum fraction of values ZScale may reject
+const ZSCALE_MAX_REJECT: f64 = 0.5;
+
+/// What happens to the display limits when new data arrives
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LimitsPolicy {
+    /// Recompute the limits from every frame
+    #[default]
+    Auto,
+    /// Never change the current limits
+    Keep,
+    /// Recompute the limits unless the user has modified them
+    KeepIfModified,
+    /// Follow a central percentile range of every frame
+    Percentile,
+}
+
+impl LimitsPolicy {
+    /// All policies, in UI order
+    pub fn all() -> &'static [LimitsPolicy] {
+        &[
+            LimitsPolicy::Auto,
+            LimitsPolicy::Keep,
+            LimitsPolicy::KeepIfModified,
+            LimitsPolicy::Percentile,
+        ]
+    }
+
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            LimitsPolicy::Auto => "auto",
+            LimitsPolicy::Keep => "keep",
+            LimitsPolicy::KeepIfModified => "keep-if-modified",
+            LimitsPolicy::Percentile => "percentile",
+        }
+    }
+
+    /// Look up a policy by its JS API name
+    pub fn from_name(name: &str) -> Option<LimitsPolicy> {
+        Self::all().iter().copied().find(|p| p.name() == name)
+    }
+
+    /// Whether the current limits stay when new data arrives, given whether
+    /// the user has modified them
+    pub fn keeps_limits(&self, modified: bool) -> bool {
+        match self {
+            LimitsPolicy::Auto | LimitsPolicy::Percentile => false,
+            LimitsPolicy::Keep => true,
+            LimitsPolicy::KeepIfModified => modified,
+        }
+    }
+}
+
+/// How automatic display limits are estimated from the data
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ScaleMode {
+    /// Full finite range
+    #[default]
+    MinMax,
+    /// Central percentile range
+    Percentile,
+    /// IRAF/DS9 ZScale: a line fit to the sorted values, for the range
+    /// around the median
+    ZScale,
+    /// Median ± k times the median absolute deviation
+    Mad,
+}
+
+impl ScaleMode {
+    /// All modes, in UI order
+    pub fn all() -> &'static [ScaleMode] {
+        &[ScaleMode::MinMax, ScaleMode::Percentile, ScaleMode::ZScale, ScaleMode::Mad]
+    }
+
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScaleMode::MinMax => "minmax",
+            ScaleMode::Percentile => "percentile",
+            ScaleMode::ZScale => "zscale",
+            ScaleMode::Mad => "mad",
+        }
+    }
+
+    /// Short label for the UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScaleMode::MinMax => "min/max",
+            ScaleMode::Percentile => "percentile",
+            ScaleMode::ZScale => "zscale",
+            ScaleMode::Mad => "median ± k·MAD",
+        }
+    }
+
+    /// Look up a mode by its JS API name
+    pub fn from_name(name: &str) -> Option<ScaleMode> {
+        Self::all().iter().copied().find(|m| m.name() == name)
+    }
+
+    /// Limits of the finite `values` (reordered in place), or `None` if
+    /// there are none
+    pub fn range(&self, values: &mut [f64], percent: f64, mad_factor: f64) -> Option<(f64, f64)> {
+        match self {
+            ScaleMode::MinMax => min_max(values),
+            ScaleMode::Percentile => percentile_range(values, percent),
+            ScaleMode::ZScale => zscale_range(values),
+            ScaleMode::Mad => mad_range(values, mad_factor),
+        }
+    }
+}
+
+fn min_max(values: &[f64]) -> Option<(f64, f64)> {
+    values.iter().fold(None, |range, &v| match range {
+        Some((min_val, max_val)) => Some((v.min(min_val), v.max(max_val))),
+        None => Some((v, v)),
+    })
+}
+
+/// Median of non-empty `values` (reordered in place)
+fn median(values: &mut [f64]) -> f64 {
+    let mid = values.len() / 2;
+    let upper = *values.select_nth_unstable_by(mid, f64::total_cmp).1;
+    if values.len() % 2 == 1 {
+        return upper;
+    }
+    // Everything before `mid` is now at most `upper`
+    let lower = values[..mid].iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (lower + upper) / 2.0
+}
+
+/// Range spanning the central `percent` of `values`, DS9 style: 99.5 clips
+/// the lowest and highest 0.25%
+pub fn percentile_range(values: &mut [f64], percent: f64) -> Option<(f64, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let tail = (1.0 - percent.clamp(0.0, 100.0) / 100.0) / 2.0;
+    let last = values.len() - 1;
+    let low_idx = (last as f64 * tail).round() as usize;
+    let high_idx = (last as f64 * (1.0 - tail)).round() as usize;
+    let low = *values.select_nth_unstable_by(low_idx, f64::total_cmp).1;
+    let high = *values.select_nth_unstable_by(high_idx, f64::total_cmp).1;
+    Some((low, high))
+}
+
+/// Median ± `k`·MAD, within the range of `values`
+pub fn mad_range(values: &mut [f64], k: f64) -> Option<(f64, f64)> {
+    let (min_val, max_val) = min_max(values)?;
+    let center = median(values);
+    let mut deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    let mad = median(&mut deviations);
+    Some(((center - k * mad).max(min_val), (center + k * mad).min(max_val)))
+}
+
+/// IRAF/DS9 ZScale limits: fit a line to a sorted sample of `values`,
+/// iteratively rejecting outliers, and span the median ± the fitted slope
+/// (divided by the contrast) over the sample. Falls back to the sample's
+/// range if too many values are rejected.
+pub fn zscale_range(values: &[f64]) -> Option<(f64, f64)> {
+    let stride = (values.len() / ZSCALE_SAMPLES).max(1);
+    let mut samples: Vec<f64> = values.iter().step_by(stride).take(ZSCALE_SAMPLES).copied().collect();
+    samples.sort_unstable_by(f64::total_cmp);
+    let (mut min_val, mut max_val) = (*samples.first()?, *samples.last()?);
+
+    let npix = samples.len();
+    let min_pixels = ZSCALE_MIN_PIXELS.max((npix as f64 * ZSCALE_MAX_REJECT) as usize);
+    // Rejections are grown to neighbouring samples
+    let grow = ((npix as f64 * 0.01) as usize).max(1);
+    let mut rejected = vec![false; npix];
+    let mut good = npix;
+    let mut last_good = npix + 1;
+    let mut slope = None;
+    for _ in 0..ZSCALE_MAX_ITERATIONS {
+        if good >= last_good || good < min_pixels {
+            break;
+        }
+        let (fit_slope, intercept) = fit_line(&samples, &rejected);
+        slope = Some(fit_slope);
+
+        let residuals: Vec<f64> = samples
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| v - (intercept + fit_slope * i as f64))
+            .collect();
+        let kept: Vec<f64> = residuals.iter().zip(&rejected).filter(|(_, &r)| !r).map(|(&d, _)| d).collect();
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let std = (kept.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / kept.len() as f64).sqrt();
+        let threshold = ZSCALE_REJECT_SIGMA * std;
+        for (i, residual) in residuals.iter().enumerate() {
+            if residual.abs() > threshold {
+                let end = (i + (grow - 1) / 2).min(npix - 1);
+                rejected[i.saturating_sub(grow / 2)..=end].fill(true);
+            }
+        }
+        last_good = good;
+        good = rejected.iter().filter(|&&r| !r).count();
+    }
+
+    if let Some(slope) = slope.filter(|_| good >= min_pixels) {
+        let slope = slope / ZSCALE_CONTRAST;
+        let center = (npix - 1) / 2;
+        let median = if npix % 2 == 1 {
+            samples[center]
+        } else {
+            (samples[center] + samples[center + 1]) / 2.0
+        };
+        min_val = min_val.max(median - center as f64 * slope);
+        max_val = max_val.min(median + (npix - center - 1) as f64 * slope);
+    }
+    Some((min_val, max_val))
+}
+
+/// Least-squares line through the non-rejected `(index, value)` points, as
+/// (slope, intercept)
+fn fit_line(values: &[f64], rejected: &[bool]) -> (f64, f64) {
+    let (mut n, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (i, (&y, _)) in values.iter().zip(rejected).enumerate().filter(|(_, (_, &r))| !r) {
+        let x = i as f64;
+        n += 1.0;
+        sx += x;
+        sy += y;
+        sxx += x * x;
+        sxy += x * y;
+    }
+    let denom = n * sxx - sx * sx;
+    if denom.abs() < f64::EPSILON {
+        return (0.0, sy / n);
+    }
+    let slope = (n * sxy - sx * sy) / denom;
+    (slope, (sy - slope * sx) / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_range() {
+        // 0..=100 plus an outlier
+        let mut values: Vec<f64> = (0..=100).map(f64::from).collect();
+        values.push(1e9);
+
+        assert_eq!(percentile_range(&mut values, 100.0), Some((0.0, 1e9)));
+        assert_eq!(percentile_range(&mut values, 98.0), Some((1.0, 100.0)));
+        assert_eq!(percentile_range(&mut [], 99.5), None);
+    }
+
+    #[test]
+    fn test_mad_range() {
+        let mut values: Vec<f64> = (1..=9).map(f64::from).collect();
+        values.push(1000.0);
+        // Median 5.5, MAD 2.5; the low end is clipped to the data
+        assert_eq!(mad_range(&mut values, 2.0), Some((1.0, 10.5)));
+        assert_eq!(ScaleMode::Mad.range(&mut [], 99.5, 2.0), None);
+    }
+
+    #[test]
+    fn test_zscale_range() {
+        // A noisy ramp with one hot pixel
+        let mut values = vec![1e9];
+        values.extend((0..999).map(|i| i as f64 + ((i * 7919) % 13) as f64));
+        let (min_val, max_val) = zscale_range(&values).unwrap();
+        assert_eq!(min_val, 0.0);
+        assert!(max_val > 1000.0 && max_val < 1e4, "{}", max_val);
+
+        // Too few values to fit: the full range
+        assert_eq!(zscale_range(&[3.0, 1.0, 2.0]), Some((1.0, 3.0)));
+        assert_eq!(zscale_range(&[]), None);
+    }
+
+    #[test]
+    fn test_policies() {
+        assert!(!LimitsPolicy::Auto.keeps_limits(true));
+        assert!(LimitsPolicy::Keep.keeps_limits(false));
+        assert!(LimitsPolicy::KeepIfModified.keeps_limits(true));
+        assert!(!LimitsPolicy::KeepIfModified.keeps_limits(false));
+        assert_eq!(LimitsPolicy::from_name("keep-if-modified"), Some(LimitsPolicy::KeepIfModified));
+        assert_eq!(LimitsPolicy::from_name("manual"), None);
+        assert_eq!(ScaleMode::from_name("zscale"), Some(ScaleMode::ZScale));
+    }
+}
@@ -0,0 +1,475 @@
+//! NumPy .npy / .npz reader
+//!
+//! Parses the .npy header (dtype, byte order, `fortran_order` and shape) and
+//! decodes the first 2D plane of the array. `.npz` archives are zip files of
+//! .npy members, either stored or deflate-compressed.
+
+use crate::dtype::{ByteOrder, DType, StridedLayout};
+use crate::pixels::PixelData;
+
+/// Magic string at the start of every .npy file
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+/// Zip local file header signature (start of every .npz file)
+const ZIP_LOCAL_SIGNATURE: u32 = 0x04034b50;
+/// Zip central directory entry signature
+const ZIP_CENTRAL_SIGNATURE: u32 = 0x02014b50;
+/// Zip end of central directory signature
+const ZIP_EOCD_SIGNATURE: u32 = 0x06054b50;
+
+/// A 2D image decoded from a NumPy array
+#[derive(Debug)]
+pub struct NpyImage {
+    /// Pixel values in row-major order, in the array's dtype
+    pub pixels: PixelData,
+    /// Image width (last axis)
+    pub width: u32,
+    /// Image height (second-to-last axis)
+    pub height: u32,
+}
+
+/// Parsed .npy header
+#[derive(Debug)]
+struct NpyHeader {
+    dtype: DType,
+    byte_order: ByteOrder,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// Check whether a buffer looks like a .npz (zip) archive rather than a bare .npy file
+pub fn is_npz(data: &[u8]) -> bool {
+    data.len() >= 4 && read_u32(data, 0) == ZIP_LOCAL_SIGNATURE
+}
+
+/// Read the first 2D plane of a .npy file.
+///
+/// Arrays with more than two dimensions are indexed at 0 along all leading axes.
+pub fn read_npy(data: &[u8]) -> Result<NpyImage, String> {
+    let (header, data_start) = parse_header(data)?;
+
+    let ndim = header.shape.len();
+    if ndim < 2 {
+        return Err(format!(
+            "Array must have at least 2 dimensions, got shape {:?}",
+            header.shape
+        ));
+    }
+    let height = header.shape[ndim - 2];
+    let width = header.shape[ndim - 1];
+
+    let itemsize = header.dtype.size();
+    let total_bytes = header
+        .shape
+        .iter()
+        .try_fold(itemsize, |acc, &n| acc.checked_mul(n))
+        .ok_or_else(|| format!("Invalid .npy header: shape {:?} is too large", header.shape))?;
+    let payload = &data[data_start..];
+    if payload.len() < total_bytes {
+        return Err(format!(
+            "Truncated .npy data: shape {:?} needs {} bytes, only {} available",
+            header.shape,
+            total_bytes,
+            payload.len()
+        ));
+    }
+
+    // Byte layout of the first plane for the array's memory order
+    let layout = if header.fortran_order {
+        let leading: usize = header.shape[..ndim - 2].iter().product();
+        StridedLayout {
+            byte_offset: 0,
+            row_stride: leading * itemsize,
+            col_stride: leading * height * itemsize,
+        }
+    } else {
+        StridedLayout::row_major(width, itemsize)
+    };
+    let pixels = header.dtype.decode_strided(payload, header.byte_order, width, height, &layout)?;
+
+    Ok(NpyImage {
+        pixels,
+        width: width as u32,
+        height: height as u32,
+    })
+}
+
+/// Read an array from a .npz archive.
+///
+/// `name` selects the member (with or without the `.npy` suffix); if `None`,
+/// the first array in the archive is used.
+pub fn read_npz(data: &[u8], name: Option<&str>) -> Result<NpyImage, String> {
+    let members = read_zip_members(data)?;
+    if members.is_empty() {
+        return Err("The .npz archive contains no arrays".to_string());
+    }
+
+    let member = match name {
+        Some(name) => {
+            let wanted = name.strip_suffix(".npy").unwrap_or(name);
+            members
+                .iter()
+                .find(|m| m.name.strip_suffix(".npy").unwrap_or(&m.name) == wanted)
+                .ok_or_else(|| {
+                    let available: Vec<&str> = members
+                        .iter()
+                        .map(|m| m.name.strip_suffix(".npy").unwrap_or(&m.name))
+                        .collect();
+                    format!("Array \"{}\" not found in .npz archive (available: {})", name, available.join(", "))
+                })?
+        }
+        None => &members[0],
+    };
+
+    let contents = member.extract(data)?;
+    read_npy(&contents)
+}
+
+/// Parse the .npy preamble and header dictionary.
+/// Returns the header and the byte offset at which array data starts.
+fn parse_header(data: &[u8]) -> Result<(NpyHeader, usize), String> {
+    if data.len() < 10 || &data[..6] != NPY_MAGIC {
+        return Err("Not a .npy file: missing \\x93NUMPY magic string".to_string());
+    }
+
+    let major = data[6];
+    let (header_len, header_start) = match major {
+        1 => (u16::from_le_bytes([data[8], data[9]]) as usize, 10),
+        2 | 3 => {
+            if data.len() < 12 {
+                return Err("Truncated .npy header".to_string());
+            }
+            (read_u32(data, 8) as usize, 12)
+        }
+        _ => return Err(format!("Unsupported .npy format version {}.{}", major, data[7])),
+    };
+
+    let header_end = header_start + header_len;
+    if data.len() < header_end {
+        return Err("Truncated .npy header".to_string());
+    }
+    let text = std::str::from_utf8(&data[header_start..header_end])
+        .map_err(|_| "Invalid .npy header: not valid text".to_string())?;
+
+    let descr = dict_value(text, "descr")?;
+    let descr = descr
+        .strip_prefix('\'')
+        .and_then(|d| d.strip_suffix('\''))
+        .ok_or_else(|| format!("Unsupported dtype descr {}: structured dtypes are not supported", descr))?;
+    let (dtype, byte_order) =
+        DType::parse(descr).map_err(|_| format!("Unsupported dtype: {}", descr))?;
+
+    let fortran_order = match dict_value(text, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        other => return Err(format!("Invalid fortran_order value: {}", other)),
+    };
+
+    let shape_text = dict_value(text, "shape")?;
+    let shape = shape_text
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.trim_end_matches('L').parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("Invalid shape: {}", shape_text))?;
+
+    Ok((
+        NpyHeader {
+            dtype,
+            byte_order,
+            fortran_order,
+            shape,
+        },
+        header_end,
+    ))
+}
+
+/// Extract the literal value for `key` from the header's Python dict literal
+fn dict_value<'a>(header: &'a str, key: &str) -> Result<&'a str, String> {
+    let pattern = format!("'{}':", key);
+    let start = header
+        .find(&pattern)
+        .ok_or_else(|| format!("Invalid .npy header: missing '{}' key", key))?
+        + pattern.len();
+    let rest = header[start..].trim_start();
+
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else if let Some(quoted) = rest.strip_prefix('\'') {
+        quoted.find('\'').map(|i| i + 2)
+    } else {
+        rest.find([',', '}'])
+    };
+    end.map(|end| rest[..end].trim())
+        .ok_or_else(|| format!("Invalid .npy header: malformed '{}' value", key))
+}
+
+/// A member of a zip archive, located via the central directory
+struct ZipMember {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    local_header_offset: usize,
+}
+
+impl ZipMember {
+    /// Return the uncompressed contents of this member
+    fn extract(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        // Offsets and sizes come from the archive, so every sum is checked
+        let offset = self.local_header_offset;
+        let has_header = offset.checked_add(30).is_some_and(|end| end <= data.len());
+        if !has_header || read_u32(data, offset) != ZIP_LOCAL_SIGNATURE {
+            return Err(format!("Invalid .npz archive: bad local header for {}", self.name));
+        }
+        let name_len = read_u16(data, offset + 26) as usize;
+        let extra_len = read_u16(data, offset + 28) as usize;
+        let start = (offset + 30).checked_add(name_len + extra_len);
+        let range = start.and_then(|start| Some(start..start.checked_add(self.compressed_size)?));
+        let raw = range
+            .and_then(|range| data.get(range))
+            .ok_or_else(|| format!("Invalid .npz archive: {} is truncated", self.name))?;
+
+        match self.method {
+            0 => Ok(raw.to_vec()),
+            8 => miniz_oxide::inflate::decompress_to_vec(raw)
+                .map_err(|e| format!("Failed to decompress {}: {:?}", self.name, e)),
+            m => Err(format!("Unsupported compression method {} for {}", m, self.name)),
+        }
+    }
+}
+
+/// List the members of a zip archive from its central directory
+fn read_zip_members(data: &[u8]) -> Result<Vec<ZipMember>, String> {
+    // The end of central directory record is at least 22 bytes and may be
+    // followed by a comment of up to 65535 bytes
+    let search_start = data.len().saturating_sub(22 + 65535);
+    let eocd = (search_start..=data.len().saturating_sub(22))
+        .rev()
+        .find(|&i| read_u32(data, i) == ZIP_EOCD_SIGNATURE)
+        .ok_or_else(|| "Invalid .npz archive: end of central directory not found".to_string())?;
+
+    let entry_count = read_u16(data, eocd + 10) as usize;
+    let mut offset = read_u32(data, eocd + 16) as usize;
+    let corrupt = || "Invalid .npz archive: corrupt central directory".to_string();
+
+    let mut members = Vec::with_capacity(entry_count);
+    for _ in 0..entry_count {
+        let has_entry = offset.checked_add(46).is_some_and(|end| end <= data.len());
+        if !has_entry || read_u32(data, offset) != ZIP_CENTRAL_SIGNATURE {
+            return Err(corrupt());
+        }
+        let method = read_u16(data, offset + 10);
+        let mut compressed_size = read_u32(data, offset + 20) as u64;
+        let mut uncompressed_size = read_u32(data, offset + 24) as u64;
+        let name_len = read_u16(data, offset + 28) as usize;
+        let extra_len = read_u16(data, offset + 30) as usize;
+        let comment_len = read_u16(data, offset + 32) as usize;
+        let mut local_header_offset = read_u32(data, offset + 42) as u64;
+
+        let name_start = offset + 46;
+        let extra_start = name_start + name_len;
+        let next = extra_start
+            .checked_add(extra_len + comment_len)
+            .filter(|&next| next <= data.len())
+            .ok_or_else(corrupt)?;
+        let name = String::from_utf8_lossy(&data[name_start..extra_start]).into_owned();
+
+        // Zip64 extended information replaces any field saturated at 0xFFFFFFFF
+        let extra = &data[extra_start..extra_start + extra_len];
+        let mut pos = 0;
+        while pos + 4 <= extra.len() {
+            let id = read_u16(extra, pos);
+            let size = read_u16(extra, pos + 2) as usize;
+            if id == 0x0001 {
+                let mut field = pos + 4;
+                for value in [&mut uncompressed_size, &mut compressed_size, &mut local_header_offset] {
+                    if *value == 0xFFFF_FFFF && field + 8 <= extra.len() {
+                        *value = u64::from_le_bytes(extra[field..field + 8].try_into().unwrap());
+                        field += 8;
+                    }
+                }
+            }
+            pos += 4 + size;
+        }
+
+        // Zip64 values may not fit a 32-bit usize
+        members.push(ZipMember {
+            name,
+            method,
+            compressed_size: usize::try_from(compressed_size).map_err(|_| corrupt())?,
+            local_header_offset: usize::try_from(local_header_offset).map_err(|_| corrupt())?,
+        });
+        offset = next;
+    }
+
+    // Directory entries are not selectable
+    members.retain(|m| !m.name.ends_with('/'));
+    Ok(members)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a version 1.0 .npy file with the given header fields and payload
+    fn npy(descr: &str, fortran_order: bool, shape: &str, payload: &[u8]) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            descr,
+            if fortran_order { "True" } else { "False" },
+            shape
+        );
+        // Pad so the data starts on a 64-byte boundary, as numpy does
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+
+        let mut out = NPY_MAGIC.to_vec();
+        out.extend_from_slice(&[1, 0]);
+        out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        out.extend_from_slice(header.as_bytes());
+        out.extend_from_slice(payload);
+        out
+    }
+
+    /// Build a zip archive with the given (name, contents) members
+    fn zip(members: &[(&str, Vec<u8>)], deflate: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, contents) in members {
+            let stored = if deflate {
+                miniz_oxide::deflate::compress_to_vec(contents, 6)
+            } else {
+                contents.clone()
+            };
+            let method: u16 = if deflate { 8 } else { 0 };
+            let offset = out.len() as u32;
+
+            out.extend_from_slice(&ZIP_LOCAL_SIGNATURE.to_le_bytes());
+            out.extend_from_slice(&[20, 0, 0, 0]);
+            out.extend_from_slice(&method.to_le_bytes());
+            out.extend_from_slice(&[0; 8]); // time, date, crc
+            out.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&[0, 0]);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&stored);
+
+            central.extend_from_slice(&ZIP_CENTRAL_SIGNATURE.to_le_bytes());
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            central.extend_from_slice(&method.to_le_bytes());
+            central.extend_from_slice(&[0; 8]);
+            central.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central.extend_from_slice(&[0; 12]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let central_offset = out.len() as u32;
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&ZIP_EOCD_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        out.extend_from_slice(&central_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    #[test]
+    fn test_read_c_order_float32() {
+        let values: [f32; 6] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let payload: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let image = read_npy(&npy("<f4", false, "(2, 3)", &payload)).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.pixels, PixelData::F32(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]));
+        assert!(!image.pixels.is_integer());
+    }
+
+    #[test]
+    fn test_read_fortran_order_big_endian() {
+        // Logical array [[1, 2, 3], [4, 5, 6]] stored column-major as big-endian i2
+        let column_major: [i16; 6] = [1, 4, 2, 5, 3, 6];
+        let payload: Vec<u8> = column_major.iter().flat_map(|v| v.to_be_bytes()).collect();
+        let image = read_npy(&npy(">i2", true, "(2, 3)", &payload)).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.pixels, PixelData::I16(vec![1, 2, 3, 4, 5, 6]));
+        assert!(image.pixels.is_integer());
+    }
+
+    #[test]
+    fn test_read_first_plane_of_3d() {
+        let payload: Vec<u8> = (0u8..8).collect();
+        let image = read_npy(&npy("|u1", false, "(2, 2, 2)", &payload)).unwrap();
+        assert_eq!(image.pixels, PixelData::U8(vec![0, 1, 2, 3]));
+
+        // Fortran order: element (k, i, j) is at k + 2*i + 4*j
+        let image = read_npy(&npy("|u1", true, "(2, 2, 2)", &payload)).unwrap();
+        assert_eq!(image.pixels, PixelData::U8(vec![0, 4, 2, 6]));
+    }
+
+    #[test]
+    fn test_read_complex() {
+        let mut payload = Vec::new();
+        for x in [1.0f32, -2.0, 0.5, 4.0] {
+            payload.extend_from_slice(&x.to_le_bytes());
+        }
+        let image = read_npy(&npy("<c8", false, "(1, 2)", &payload)).unwrap();
+        assert_eq!(image.pixels, PixelData::C64(vec![[1.0, -2.0], [0.5, 4.0]]));
+    }
+
+    #[test]
+    fn test_npy_errors() {
+        assert!(read_npy(b"not numpy").unwrap_err().contains("magic"));
+        assert!(read_npy(&npy("<U4", false, "(1, 1)", &[0; 16])).unwrap_err().contains("Unsupported dtype"));
+        assert!(read_npy(&npy("<f8", false, "(4,)", &[0; 32])).unwrap_err().contains("at least 2 dimensions"));
+        assert!(read_npy(&npy("<f8", false, "(2, 2)", &[0; 8])).unwrap_err().contains("Truncated"));
+        let huge = format!("({}, {})", usize::MAX, 3);
+        assert!(read_npy(&npy("<f8", false, &huge, &[0; 8])).unwrap_err().contains("too large"));
+    }
+
+    #[test]
+    fn test_read_npz_members() {
+        let a = npy("|u1", false, "(1, 2)", &[1, 2]);
+        let b = npy("<u2", false, "(1, 1)", &[0x34, 0x12]);
+
+        for deflate in [false, true] {
+            let archive = zip(&[("a.npy", a.clone()), ("b.npy", b.clone())], deflate);
+            assert!(is_npz(&archive));
+
+            let first = read_npz(&archive, None).unwrap();
+            assert_eq!(first.pixels, PixelData::U8(vec![1, 2]));
+
+            let named = read_npz(&archive, Some("b")).unwrap();
+            assert_eq!(named.pixels, PixelData::U16(vec![0x1234]));
+
+            let err = read_npz(&archive, Some("c")).unwrap_err();
+            assert!(err.contains("not found") && err.contains("a, b"));
+        }
+
+        // Offsets near the top of the address space are rejected, not wrapped
+        let archive = zip(&[("a.npy", a)], false);
+        let mut bad_directory = archive.clone();
+        let eocd = bad_directory.len() - 22;
+        bad_directory[eocd + 16..eocd + 20].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        assert!(read_npz(&bad_directory, None).unwrap_err().contains("corrupt central directory"));
+        let mut bad_local = archive;
+        let central = read_u32(&bad_local, eocd + 16) as usize;
+        bad_local[central + 42..central + 46].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        assert!(read_npz(&bad_local, None).unwrap_err().contains("bad local header"));
+    }
+}
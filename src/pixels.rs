@@ -0,0 +1,395 @@
+//! Typed pixel storage
+//!
+//! Image data is kept in its source element type rather than widened to f64,
+//! so an 8k x 8k u16 frame takes 128 MB instead of 512 MB, and hover readouts
+//! can show the exact stored value (including full-precision 64-bit integers).
+//! Values are converted to f64 one at a time when stretching; for complex data
+//! a `ComplexComponent` selects which real quantity is displayed.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::dtype::DType;
+
+/// Pixel values in row-major order, stored in their native element type
+#[derive(Clone, Debug, PartialEq)]
+pub enum PixelData {
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+    I16(Vec<i16>),
+    U16(Vec<u16>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+    I64(Vec<i64>),
+    U64(Vec<u64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    /// Complex values as [real, imaginary]
+    C64(Vec<[f32; 2]>),
+    /// Complex values as [real, imaginary]
+    C128(Vec<[f64; 2]>),
+}
+
+/// A single pixel value, exact for every storage type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PixelValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Complex(f64, f64),
+}
+
+/// Which real quantity of complex data is displayed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ComplexComponent {
+    /// Absolute value |z|
+    #[default]
+    Magnitude,
+    /// Argument of z in radians (-π..π)
+    Phase,
+    Real,
+    Imaginary,
+    /// Power in decibels, 10·log10(|z|²)
+    LogPower,
+}
+
+/// Run `$real` with `$v` bound to the typed vector inside a real-valued
+/// `PixelData`, or `$complex` with `$c` bound to a complex one
+macro_rules! with_values {
+    ($data:expr, $v:ident => $real:expr, $c:ident => $complex:expr) => {
+        match $data {
+            PixelData::I8($v) => $real,
+            PixelData::U8($v) => $real,
+            PixelData::I16($v) => $real,
+            PixelData::U16($v) => $real,
+            PixelData::I32($v) => $real,
+            PixelData::U32($v) => $real,
+            PixelData::I64($v) => $real,
+            PixelData::U64($v) => $real,
+            PixelData::F32($v) => $real,
+            PixelData::F64($v) => $real,
+            PixelData::C64($c) => $complex,
+            PixelData::C128($c) => $complex,
+        }
+    };
+}
+
+impl PixelData {
+    /// Element type of the stored values
+    pub fn dtype(&self) -> DType {
+        match self {
+            PixelData::I8(_) => DType::I8,
+            PixelData::U8(_) => DType::U8,
+            PixelData::I16(_) => DType::I16,
+            PixelData::U16(_) => DType::U16,
+            PixelData::I32(_) => DType::I32,
+            PixelData::U32(_) => DType::U32,
+            PixelData::I64(_) => DType::I64,
+            PixelData::U64(_) => DType::U64,
+            PixelData::F32(_) => DType::F32,
+            PixelData::F64(_) => DType::F64,
+            PixelData::C64(_) => DType::C64,
+            PixelData::C128(_) => DType::C128,
+        }
+    }
+
+    /// Number of stored values
+    pub fn len(&self) -> usize {
+        with_values!(self, v => v.len(), c => c.len())
+    }
+
+    /// Copy of the values in `range`, keeping the element type
+    pub fn slice(&self, range: Range<usize>) -> PixelData {
+        with_values!(self, v => v[range].to_vec().into(), c => c[range].to_vec().into())
+    }
+
+    /// Copy of the values at the given flat indices, keeping the element type
+    pub fn gather(&self, indices: &[usize]) -> PixelData {
+        with_values!(self,
+            v => indices.iter().map(|&i| v[i]).collect::<Vec<_>>().into(),
+            c => indices.iter().map(|&i| c[i]).collect::<Vec<_>>().into()
+        )
+    }
+
+    /// Overwrite the block of `patch.len() / w` rows of `w` values at
+    /// (`x`, `y`) in this `width`-wide row-major image with the row-major
+    /// values of `patch`, which must have the same element type
+    pub fn write_region(&mut self, width: usize, x: usize, y: usize, w: usize, patch: &PixelData) -> Result<(), String> {
+        let h = patch.len().checked_div(w).unwrap_or(0);
+        if w == 0 || h * w != patch.len() || x + w > width || (y + h) * width > self.len() {
+            return Err(format!(
+                "Region {}x{} at ({}, {}) does not fit in a {}x{} image",
+                w,
+                h,
+                x,
+                y,
+                width,
+                self.len() / width.max(1)
+            ));
+        }
+
+        macro_rules! copy_rows {
+            ($($variant:ident),*) => {
+                match (self, patch) {
+                    $(
+                        (PixelData::$variant(dst), PixelData::$variant(src)) => {
+                            for (row, values) in src.chunks(w).enumerate() {
+                                let start = (y + row) * width + x;
+                                dst[start..start + w].copy_from_slice(values);
+                            }
+                            Ok(())
+                        }
+                    )*
+                    (dst, src) => Err(format!("Region type {} does not match image type {}", src.dtype(), dst.dtype())),
+                }
+            };
+        }
+        copy_rows!(I8, U8, I16, U16, I32, U32, I64, U64, F32, F64, C64, C128)
+    }
+
+    /// Whether the stored values are integers (for display formatting)
+    pub fn is_integer(&self) -> bool {
+        self.dtype().is_integer()
+    }
+
+    /// Whether the stored values are complex
+    pub fn is_complex(&self) -> bool {
+        self.dtype().is_complex()
+    }
+
+    /// Get the exact value at a flat index
+    pub fn get(&self, idx: usize) -> Option<PixelValue> {
+        match self {
+            PixelData::I8(v) => v.get(idx).map(|&x| PixelValue::Int(x as i64)),
+            PixelData::U8(v) => v.get(idx).map(|&x| PixelValue::UInt(x as u64)),
+            PixelData::I16(v) => v.get(idx).map(|&x| PixelValue::Int(x as i64)),
+            PixelData::U16(v) => v.get(idx).map(|&x| PixelValue::UInt(x as u64)),
+            PixelData::I32(v) => v.get(idx).map(|&x| PixelValue::Int(x as i64)),
+            PixelData::U32(v) => v.get(idx).map(|&x| PixelValue::UInt(x as u64)),
+            PixelData::I64(v) => v.get(idx).map(|&x| PixelValue::Int(x)),
+            PixelData::U64(v) => v.get(idx).map(|&x| PixelValue::UInt(x)),
+            PixelData::F32(v) => v.get(idx).map(|&x| PixelValue::Float(x as f64)),
+            PixelData::F64(v) => v.get(idx).map(|&x| PixelValue::Float(x)),
+            PixelData::C64(v) => v.get(idx).map(|&[re, im]| PixelValue::Complex(re as f64, im as f64)),
+            PixelData::C128(v) => v.get(idx).map(|&[re, im]| PixelValue::Complex(re, im)),
+        }
+    }
+
+    /// Map every pixel (converted to f64) through `f`, in order.
+    /// `component` selects the displayed quantity of complex data and is
+    /// ignored for real data.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn map_f64<T>(&self, component: ComplexComponent, mut f: impl FnMut(f64) -> T) -> Vec<T> {
+        with_values!(self,
+            v => v.iter().map(|&x| f(x as f64)).collect(),
+            c => c.iter().map(|&[re, im]| f(component.apply(re as f64, im as f64))).collect()
+        )
+    }
+
+    /// Minimum and maximum of the finite values (of `component`, for complex
+    /// data), or `None` if there are none.
+    /// Pixels with a nonzero entry in `exclude` are skipped like NaN.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn finite_range(&self, component: ComplexComponent, exclude: Option<&[u8]>) -> Option<(f64, f64)> {
+        let mut min_val = f64::INFINITY;
+        let mut max_val = f64::NEG_INFINITY;
+        let mut include = |idx: usize, x: f64| {
+            let excluded = exclude.is_some_and(|mask| mask.get(idx).is_some_and(|&m| m != 0));
+            if x.is_finite() && !excluded {
+                min_val = min_val.min(x);
+                max_val = max_val.max(x);
+            }
+        };
+        with_values!(self,
+            v => v.iter().enumerate().for_each(|(i, &x)| include(i, x as f64)),
+            c => c.iter().enumerate().for_each(|(i, &[re, im])| include(i, component.apply(re as f64, im as f64)))
+        );
+        (min_val <= max_val).then_some((min_val, max_val))
+    }
+
+    /// Finite values (of `component`, for complex data) of every `step`-th
+    /// element. Pixels with a nonzero entry in `exclude` are skipped like NaN.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn finite_values(&self, component: ComplexComponent, exclude: Option<&[u8]>, step: usize) -> Vec<f64> {
+        let step = step.max(1);
+        let mut values = Vec::with_capacity(self.len().div_ceil(step));
+        let mut include = |idx: usize, x: f64| {
+            let excluded = exclude.is_some_and(|mask| mask.get(idx).is_some_and(|&m| m != 0));
+            if x.is_finite() && !excluded {
+                values.push(x);
+            }
+        };
+        with_values!(self,
+            v => v.iter().enumerate().step_by(step).for_each(|(i, &x)| include(i, x as f64)),
+            c => c.iter().enumerate().step_by(step).for_each(|(i, &[re, im])| include(i, component.apply(re as f64, im as f64)))
+        );
+        values
+    }
+}
+
+impl ComplexComponent {
+    /// All components, in UI order
+    pub fn all() -> &'static [ComplexComponent] {
+        &[
+            ComplexComponent::Magnitude,
+            ComplexComponent::Phase,
+            ComplexComponent::Real,
+            ComplexComponent::Imaginary,
+            ComplexComponent::LogPower,
+        ]
+    }
+
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComplexComponent::Magnitude => "magnitude",
+            ComplexComponent::Phase => "phase",
+            ComplexComponent::Real => "real",
+            ComplexComponent::Imaginary => "imag",
+            ComplexComponent::LogPower => "logpower",
+        }
+    }
+
+    /// Short label for the overlay selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComplexComponent::Magnitude => "|z|",
+            ComplexComponent::Phase => "Arg",
+            ComplexComponent::Real => "Re",
+            ComplexComponent::Imaginary => "Im",
+            ComplexComponent::LogPower => "dB",
+        }
+    }
+
+    /// Look up a component by its JS API name
+    pub fn from_name(name: &str) -> Option<ComplexComponent> {
+        Self::all().iter().copied().find(|c| c.name() == name)
+    }
+
+    /// Compute this component of the complex value `re + im·i`
+    pub fn apply(&self, re: f64, im: f64) -> f64 {
+        match self {
+            ComplexComponent::Magnitude => re.hypot(im),
+            ComplexComponent::Phase => im.atan2(re),
+            ComplexComponent::Real => re,
+            ComplexComponent::Imaginary => im,
+            ComplexComponent::LogPower => 10.0 * (re * re + im * im).log10(),
+        }
+    }
+}
+
+macro_rules! impl_from_vec {
+    ($($variant:ident($t:ty)),*) => {
+        $(
+            impl From<Vec<$t>> for PixelData {
+                fn from(values: Vec<$t>) -> Self {
+                    PixelData::$variant(values)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_vec!(
+    I8(i8), U8(u8), I16(i16), U16(u16), I32(i32), U32(u32), I64(i64), U64(u64), F32(f32), F64(f64),
+    C64([f32; 2]), C128([f64; 2])
+);
+
+impl fmt::Display for PixelValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PixelValue::Int(x) => write!(f, "{}", x),
+            PixelValue::UInt(x) => write!(f, "{}", x),
+            PixelValue::Float(x) => write!(f, "{:.6}", x),
+            PixelValue::Complex(re, im) => {
+                let sign = if im.is_sign_negative() { '-' } else { '+' };
+                write!(f, "{:.6} {} {:.6}i", re, sign, im.abs())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_64_bit_values() {
+        let data = PixelData::from(vec![u64::MAX, 1]);
+        assert_eq!(data.get(0), Some(PixelValue::UInt(u64::MAX)));
+        assert_eq!(data.get(0).unwrap().to_string(), "18446744073709551615");
+
+        let data = PixelData::from(vec![i64::MIN + 1]);
+        assert_eq!(data.get(0).unwrap().to_string(), "-9223372036854775807");
+        assert_eq!(data.get(1), None);
+    }
+
+    #[test]
+    fn test_finite_range() {
+        let data = PixelData::from(vec![f32::NAN, 3.0, f32::INFINITY, -2.5]);
+        let any = ComplexComponent::default();
+        assert_eq!(data.finite_range(any, None), Some((-2.5, 3.0)));
+        assert_eq!(PixelData::from(vec![f64::NAN]).finite_range(any, None), None);
+        assert_eq!(PixelData::from(vec![7u16, 65535, 0]).finite_range(any, None), Some((0.0, 65535.0)));
+
+        // Masked pixels are skipped like NaN
+        let data = PixelData::from(vec![7u16, 65535, 0]);
+        assert_eq!(data.finite_range(any, Some(&[0, 1, 0])), Some((0.0, 7.0)));
+        assert_eq!(data.finite_range(any, Some(&[1, 1, 1])), None);
+        assert_eq!(data.finite_values(any, Some(&[0, 1, 0]), 1), vec![7.0, 0.0]);
+        assert_eq!(data.finite_values(any, None, 2), vec![7.0, 0.0]);
+    }
+
+    #[test]
+    fn test_map_f64_and_dtype() {
+        let data = PixelData::from(vec![-1i16, 2]);
+        assert_eq!(data.dtype(), DType::I16);
+        assert!(data.is_integer());
+        assert_eq!(data.map_f64(ComplexComponent::default(), |x| x * 2.0), vec![-2.0, 4.0]);
+        assert_eq!(data.get(1), Some(PixelValue::Int(2)));
+    }
+
+    #[test]
+    fn test_slice_and_gather() {
+        let data = PixelData::from(vec![1u8, 2, 3, 4]);
+        assert_eq!(data.len(), 4);
+        assert_eq!(data.slice(1..3), PixelData::from(vec![2u8, 3]));
+        assert_eq!(data.slice(2..2), PixelData::from(Vec::<u8>::new()));
+        assert_eq!(data.gather(&[3, 0, 3]), PixelData::from(vec![4u8, 1, 4]));
+    }
+
+    #[test]
+    fn test_write_region() {
+        // 3x2 image, patch the 2x2 block at (1, 0)
+        let mut data = PixelData::from(vec![0u16; 6]);
+        data.write_region(3, 1, 0, 2, &PixelData::from(vec![1u16, 2, 3, 4])).unwrap();
+        assert_eq!(data, PixelData::from(vec![0u16, 1, 2, 0, 3, 4]));
+
+        let err = data.write_region(3, 2, 0, 2, &PixelData::from(vec![1u16, 2])).unwrap_err();
+        assert!(err.contains("does not fit"));
+        let err = data.write_region(3, 0, 1, 3, &PixelData::from(vec![1u16; 6])).unwrap_err();
+        assert!(err.contains("does not fit"));
+        let err = data.write_region(3, 0, 0, 1, &PixelData::from(vec![1.0f32])).unwrap_err();
+        assert!(err.contains("does not match"));
+    }
+
+    #[test]
+    fn test_complex_components() {
+        let data = PixelData::from(vec![[3.0f32, -4.0], [0.0, 0.0]]);
+        assert!(data.is_complex() && !data.is_integer());
+        assert_eq!(data.get(0).unwrap().to_string(), "3.000000 - 4.000000i");
+
+        assert_eq!(data.map_f64(ComplexComponent::Magnitude, |x| x), vec![5.0, 0.0]);
+        assert_eq!(data.map_f64(ComplexComponent::Real, |x| x), vec![3.0, 0.0]);
+        assert_eq!(data.map_f64(ComplexComponent::Imaginary, |x| x), vec![-4.0, 0.0]);
+        let phase = data.map_f64(ComplexComponent::Phase, |x| x);
+        assert!((phase[0] - (-4.0f64).atan2(3.0)).abs() < 1e-12);
+
+        // log10(0) is -inf, which is excluded from the range
+        let power = ComplexComponent::LogPower;
+        assert!((data.finite_range(power, None).unwrap().0 - 10.0 * 25.0f64.log10()).abs() < 1e-12);
+
+        assert_eq!(ComplexComponent::from_name("imag"), Some(ComplexComponent::Imaginary));
+        assert_eq!(ComplexComponent::from_name("angle"), None);
+    }
+}
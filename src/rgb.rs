@@ -0,0 +1,178 @@
+//! Direct color (RGB/RGBA) images
+//!
+//! Color images bypass the stretch/colormap pipeline: channels are kept in
+//! their native type for hover readout and converted straight to `Color32`.
+//! u8 channels are used as-is; float channels are clamped to 0-1.
+
+use egui::Color32;
+
+use crate::dtype::{ByteOrder, DType, StridedLayout};
+use crate::pixels::{ComplexComponent, PixelData, PixelValue};
+
+/// How the channels of a color image are arranged in the buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelLayout {
+    /// RGBRGB... (height x width x channels)
+    Interleaved,
+    /// RRR...GGG...BBB... (channels x height x width)
+    Planar,
+}
+
+impl ChannelLayout {
+    /// Parse a layout name: "interleaved" (or "hwc") or "planar" (or "chw")
+    pub fn parse(name: &str) -> Result<ChannelLayout, String> {
+        match name {
+            "interleaved" | "hwc" => Ok(ChannelLayout::Interleaved),
+            "planar" | "chw" => Ok(ChannelLayout::Planar),
+            _ => Err(format!(
+                "Unknown channel layout \"{}\". Expected \"interleaved\" or \"planar\"",
+                name
+            )),
+        }
+    }
+}
+
+/// A color image with 3 (RGB) or 4 (RGBA) channels, each in row-major order
+#[derive(Clone, Debug, PartialEq)]
+pub struct RgbImage {
+    channels: Vec<PixelData>,
+}
+
+impl RgbImage {
+    /// Decode a `width` x `height` color image with `num_channels` channels of `dtype`
+    pub fn decode(
+        bytes: &[u8],
+        dtype: DType,
+        order: ByteOrder,
+        width: usize,
+        height: usize,
+        num_channels: usize,
+        layout: ChannelLayout,
+    ) -> Result<RgbImage, String> {
+        if !matches!(num_channels, 3 | 4) {
+            return Err(format!("Color images need 3 (RGB) or 4 (RGBA) channels, got {}", num_channels));
+        }
+        let supported = dtype == DType::U8 || !(dtype.is_integer() || dtype.is_complex());
+        if !supported {
+            return Err(format!("Color channels must be u8 or floating point, got {}", dtype));
+        }
+
+        let itemsize = dtype.size();
+        let expected_bytes = width * height * num_channels * itemsize;
+        if bytes.len() != expected_bytes {
+            return Err(format!(
+                "Buffer size mismatch: expected {}x{}x{} {} = {} bytes, got {} bytes",
+                width,
+                height,
+                num_channels,
+                dtype,
+                expected_bytes,
+                bytes.len()
+            ));
+        }
+
+        let channels = (0..num_channels)
+            .map(|c| {
+                let layout = match layout {
+                    ChannelLayout::Interleaved => StridedLayout {
+                        byte_offset: c * itemsize,
+                        row_stride: width * num_channels * itemsize,
+                        col_stride: num_channels * itemsize,
+                    },
+                    ChannelLayout::Planar => StridedLayout {
+                        byte_offset: c * width * height * itemsize,
+                        ..StridedLayout::row_major(width, itemsize)
+                    },
+                };
+                dtype.decode_strided(bytes, order, width, height, &layout)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RgbImage { channels })
+    }
+
+    /// Number of channels (3 or 4)
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Exact channel values at a flat index, or an empty vector if out of range
+    pub fn get(&self, idx: usize) -> Vec<PixelValue> {
+        self.channels.iter().filter_map(|channel| channel.get(idx)).collect()
+    }
+
+    /// Convert to display colors, in row-major order
+    pub fn to_colors(&self) -> Vec<Color32> {
+        let bytes: Vec<Vec<u8>> = self
+            .channels
+            .iter()
+            .map(|channel| {
+                let is_u8 = channel.dtype() == DType::U8;
+                channel.map_f64(ComplexComponent::default(), |v| {
+                    if is_u8 {
+                        v as u8
+                    } else if v.is_finite() {
+                        (v.clamp(0.0, 1.0) * 255.0).round() as u8
+                    } else {
+                        0
+                    }
+                })
+            })
+            .collect();
+
+        let alpha = bytes.get(3);
+        (0..bytes[0].len())
+            .map(|i| {
+                let a = alpha.map_or(255, |a| a[i]);
+                Color32::from_rgba_unmultiplied(bytes[0][i], bytes[1][i], bytes[2][i], a)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleaved_and_planar_agree() {
+        // 2x1 image: red, then cyan
+        let interleaved = [255, 0, 0, 0, 255, 255];
+        let planar = [255, 0, 0, 255, 0, 255];
+        let a = RgbImage::decode(&interleaved, DType::U8, ByteOrder::Little, 2, 1, 3, ChannelLayout::Interleaved)
+            .unwrap();
+        let b = RgbImage::decode(&planar, DType::U8, ByteOrder::Little, 2, 1, 3, ChannelLayout::Planar).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.to_colors(), vec![Color32::RED, Color32::from_rgb(0, 255, 255)]);
+        assert_eq!(a.get(1), vec![PixelValue::UInt(0), PixelValue::UInt(255), PixelValue::UInt(255)]);
+        assert!(a.get(2).is_empty());
+    }
+
+    #[test]
+    fn test_alpha_channel() {
+        let image = RgbImage::decode(&[0, 255, 255, 128], DType::U8, ByteOrder::Little, 1, 1, 4, ChannelLayout::Interleaved)
+            .unwrap();
+        assert_eq!(image.num_channels(), 4);
+        assert_eq!(image.to_colors(), vec![Color32::from_rgba_unmultiplied(0, 255, 255, 128)]);
+    }
+
+    #[test]
+    fn test_float_channels_clamped() {
+        let values = [0.0f32, 0.5, 2.0, f32::NAN, -1.0, 1.0];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let image = RgbImage::decode(&bytes, DType::F32, ByteOrder::Little, 2, 1, 3, ChannelLayout::Interleaved)
+            .unwrap();
+        assert_eq!(image.num_channels(), 3);
+        assert_eq!(image.to_colors(), vec![Color32::from_rgb(0, 128, 255), Color32::from_rgb(0, 0, 255)]);
+    }
+
+    #[test]
+    fn test_rgb_errors() {
+        let err = RgbImage::decode(&[0; 4], DType::U8, ByteOrder::Little, 2, 1, 2, ChannelLayout::Planar).unwrap_err();
+        assert!(err.contains("3 (RGB) or 4 (RGBA)"));
+        let err = RgbImage::decode(&[0; 12], DType::U16, ByteOrder::Little, 2, 1, 3, ChannelLayout::Planar).unwrap_err();
+        assert!(err.contains("u8 or floating point"));
+        let err = RgbImage::decode(&[0; 5], DType::U8, ByteOrder::Little, 2, 1, 3, ChannelLayout::Planar).unwrap_err();
+        assert!(err.contains("size mismatch"));
+        assert!(ChannelLayout::parse("bgr").is_err());
+    }
+}
@@ -4,10 +4,18 @@
 //! including pan/zoom, stretch functions, colormaps, and overlays. Multiple instances
 //! can be used side-by-side without sharing state.
 
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
 use egui::{Color32, ColorImage, Key, PointerButton, Response, TextureHandle, TextureOptions, Ui, Vec2};
 use egui_phosphor::regular as phosphor;
 
-use crate::colormap::Colormap;
+use crate::colormap::{self, Colormap, ColormapKind};
+use crate::cube::{Cube, PlaybackMode, SliceLimits};
+use crate::histogram::HistogramCdf;
+use crate::limits::{self, LimitsPolicy, ScaleMode};
+use crate::pixels::{ComplexComponent, PixelData, PixelValue};
+use crate::rgb::RgbImage;
 use crate::transform::{self, ViewTransform};
 
 /// Default contrast value (DS9 default)
@@ -18,8 +26,14 @@ const DEFAULT_BIAS: f64 = 0.5;
 const MAX_CONTRAST: f64 = 10.0;
 /// Minimum contrast value
 const MIN_CONTRAST: f64 = 0.0;
-/// Log stretch exponent (DS9 default for optical images)
-const LOG_EXPONENT: f64 = 1000.0;
+/// Default log stretch exponent (DS9 default for optical images)
+const DEFAULT_LOG_EXPONENT: f64 = 1000.0;
+/// Default asinh softening (astropy default)
+const DEFAULT_ASINH_SOFTENING: f64 = 0.1;
+/// Default power stretch exponent
+const DEFAULT_POWER_EXPONENT: f64 = 3.0;
+/// Scale of the sinh stretch (astropy default)
+const SINH_SCALE: f64 = 1.0 / 3.0;
 /// Color bar width in pixels
 const COLORBAR_WIDTH: f32 = 32.0;
 /// Maximum color bar height in pixels
@@ -28,6 +42,28 @@ const COLORBAR_MAX_HEIGHT: f32 = 300.0;
 const COLORBAR_MARGIN: f32 = 10.0;
 /// Duration to show zoom level overlay after zooming
 const ZOOM_OVERLAY_DURATION: f64 = 0.5;
+/// Height of the colormap dropdown, which scrolls beyond this
+const COLORMAP_PICKER_HEIGHT: f32 = 240.0;
+/// Maximum number of labels listed in the legend of a qualitative colormap
+const MAX_LEGEND_ENTRIES: usize = 16;
+/// Default mask overlay color
+const DEFAULT_MASK_COLOR: Color32 = Color32::from_rgb(255, 0, 0);
+/// Default mask overlay opacity
+const DEFAULT_MASK_OPACITY: f32 = 0.5;
+/// Default colors of NaN/Inf, under-range and over-range pixels (when enabled)
+const DEFAULT_SPECIAL_COLORS: [Color32; 3] = [
+    Color32::from_rgb(255, 0, 255),
+    Color32::from_rgb(0, 0, 255),
+    Color32::from_rgb(255, 0, 0),
+];
+/// Default cube playback rate in frames per second
+const DEFAULT_PLAYBACK_FPS: f64 = 10.0;
+/// Maximum cube playback rate in frames per second
+const MAX_PLAYBACK_FPS: f64 = 60.0;
+/// Number of upcoming playback frames kept as prebuilt textures
+const PREFETCH_FRAMES: usize = 8;
+/// Maximum number of cube values sampled for robust global limits
+const MAX_CUBE_LIMIT_SAMPLES: usize = 1 << 22;
 
 /// Actions returned from zoom controls overlay
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -47,12 +83,33 @@ enum ZoomAction {
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum StretchAction {
     None,
-    SetLinear,
-    SetLog,
+    SetStretch(StretchType),
+    SetLogExponent(f64),
+    SetAsinhSoftening(f64),
+    SetPowerExponent(f64),
     SetDiverging,
     SetColormap(Colormap),
     ToggleReverse,
+    SetColormapLevels(Option<u32>),
     ResetStretch,
+    SetComplexComponent(ComplexComponent),
+    ToggleMask,
+    SetMaskColor(Color32),
+    SetMaskOpacity(f32),
+    ToggleMaskExcludesLimits,
+    SetActiveChannel(usize),
+}
+
+/// Actions returned from slice controls overlay
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SliceAction {
+    None,
+    SetAxisIndex(usize, usize),
+    SetDisplayAxes(usize, usize),
+    SetSliceLimits(SliceLimits),
+    TogglePlaying,
+    SetPlaybackFps(f64),
+    SetPlaybackMode(PlaybackMode),
 }
 
 /// Stretch function type
@@ -60,6 +117,79 @@ enum StretchAction {
 pub enum StretchType {
     Linear,
     Log,
+    Sqrt,
+    Squared,
+    /// Inverse hyperbolic sine, linear near zero and log-like above the softening
+    Asinh,
+    /// x raised to a configurable exponent
+    Power,
+    /// Hyperbolic sine, emphasizing the bright end
+    Sinh,
+    /// Histogram equalization over the pixels within the limits
+    HistEq,
+}
+
+impl StretchType {
+    /// All stretch types, in UI order
+    pub fn all() -> &'static [StretchType] {
+        &[
+            StretchType::Linear,
+            StretchType::Log,
+            StretchType::Sqrt,
+            StretchType::Squared,
+            StretchType::Asinh,
+            StretchType::Power,
+            StretchType::Sinh,
+            StretchType::HistEq,
+        ]
+    }
+
+    /// Name used by the JS API
+    pub fn name(&self) -> &'static str {
+        match self {
+            StretchType::Linear => "linear",
+            StretchType::Log => "log",
+            StretchType::Sqrt => "sqrt",
+            StretchType::Squared => "squared",
+            StretchType::Asinh => "asinh",
+            StretchType::Power => "power",
+            StretchType::Sinh => "sinh",
+            StretchType::HistEq => "histeq",
+        }
+    }
+
+    /// Look up a stretch type by its JS API name
+    pub fn from_name(name: &str) -> Option<StretchType> {
+        Self::all().iter().copied().find(|t| t.name() == name)
+    }
+
+    /// Short label for the overlay selector
+    fn label(&self) -> &'static str {
+        match self {
+            StretchType::Linear => "Lin",
+            StretchType::Log => "Log",
+            StretchType::Sqrt => "Sqrt",
+            StretchType::Squared => "Sq",
+            StretchType::Asinh => "Asinh",
+            StretchType::Power => "Pow",
+            StretchType::Sinh => "Sinh",
+            StretchType::HistEq => "HistEq",
+        }
+    }
+
+    /// Full name for tooltips and the adjustment overlay
+    fn title(&self) -> &'static str {
+        match self {
+            StretchType::Linear => "Linear",
+            StretchType::Log => "Logarithmic",
+            StretchType::Sqrt => "Square root",
+            StretchType::Squared => "Squared",
+            StretchType::Asinh => "Asinh",
+            StretchType::Power => "Power",
+            StretchType::Sinh => "Sinh",
+            StretchType::HistEq => "Histogram equalization",
+        }
+    }
 }
 
 impl Default for StretchType {
@@ -91,14 +221,179 @@ impl ContrastBias {
     }
 }
 
+/// Pixels that get a fixed color instead of a colormap color, like
+/// matplotlib's `set_bad`/`set_under`/`set_over`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialColor {
+    /// NaN or infinite values
+    Bad = 0,
+    /// Values below the lower limit
+    Under = 1,
+    /// Values above the upper limit
+    Over = 2,
+}
+
+impl SpecialColor {
+    /// Parse a name: "bad", "under" or "over"
+    pub fn parse(name: &str) -> Result<SpecialColor, String> {
+        match name {
+            "bad" => Ok(SpecialColor::Bad),
+            "under" => Ok(SpecialColor::Under),
+            "over" => Ok(SpecialColor::Over),
+            _ => Err(format!(
+                "Unknown special color \"{}\". Expected \"bad\", \"under\" or \"over\"",
+                name
+            )),
+        }
+    }
+}
+
+/// A special color and whether it is used
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpecialColorSetting {
+    pub color: Color32,
+    pub enabled: bool,
+}
+
+/// Parameters of the stretch functions that have one
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StretchParams {
+    /// Exponent of the log stretch; larger values brighten faint pixels more
+    pub log_exponent: f64,
+    /// Softening of the asinh stretch; smaller values are more log-like
+    pub asinh_softening: f64,
+    /// Exponent of the power stretch
+    pub power_exponent: f64,
+}
+
+impl Default for StretchParams {
+    fn default() -> Self {
+        Self {
+            log_exponent: DEFAULT_LOG_EXPONENT,
+            asinh_softening: DEFAULT_ASINH_SOFTENING,
+            power_exponent: DEFAULT_POWER_EXPONENT,
+        }
+    }
+}
+
+/// Display names and colors of the composite channels
+const COMPOSITE_CHANNELS: [(&str, Color32); 3] = [
+    ("R", Color32::from_rgb(255, 80, 80)),
+    ("G", Color32::from_rgb(80, 220, 80)),
+    ("B", Color32::from_rgb(100, 140, 255)),
+];
+
+/// A cube plane prebuilt for playback, ready to be shown as is
+struct PlaybackFrame {
+    /// Raw pixel data of the plane
+    pixels: PixelData,
+    /// Auto limits of the plane (per-slice limits only)
+    auto_limits: Option<(f64, f64)>,
+    /// Histogram equalization CDF the texture was built with
+    hist_eq: Option<HistogramCdf>,
+    /// The plane as it will be displayed
+    texture: TextureHandle,
+}
+
+/// One channel of an RGB composite, with its own limits and stretch
+struct CompositeChannel {
+    /// Raw pixel data in its native element type
+    pixels: PixelData,
+    /// Whether the source data is integer-typed (for display formatting)
+    is_integer: bool,
+    /// Current min value for scaling
+    min_val: f64,
+    /// Current max value for scaling
+    max_val: f64,
+    /// Original auto-computed min value (for reset)
+    original_min_val: f64,
+    /// Original auto-computed max value (for reset)
+    original_max_val: f64,
+    /// Stretch function for this channel
+    stretch_type: StretchType,
+    /// Contrast/bias for this channel
+    cb: ContrastBias,
+    /// Histogram equalization CDF, while this channel uses that stretch
+    hist_eq: Option<HistogramCdf>,
+    /// Text buffer for min limit input
+    min_limit_input_text: String,
+    /// Text buffer for max limit input
+    max_limit_input_text: String,
+}
+
+impl CompositeChannel {
+    fn new(pixels: PixelData) -> Self {
+        Self {
+            is_integer: pixels.is_integer(),
+            pixels,
+            min_val: 0.0,
+            max_val: 1.0,
+            original_min_val: 0.0,
+            original_max_val: 1.0,
+            stretch_type: StretchType::default(),
+            cb: ContrastBias::default(),
+            hist_eq: None,
+            min_limit_input_text: String::new(),
+            max_limit_input_text: String::new(),
+        }
+    }
+
+    fn reset_limits(&mut self) {
+        self.min_val = self.original_min_val;
+        self.max_val = self.original_max_val;
+        self.min_limit_input_text = format_limit(self.min_val, self.is_integer);
+        self.max_limit_input_text = format_limit(self.max_val, self.is_integer);
+    }
+
+    fn is_limits_modified(&self) -> bool {
+        (self.min_val - self.original_min_val).abs() > 1e-10
+            || (self.max_val - self.original_max_val).abs() > 1e-10
+    }
+}
+
 /// A self-contained widget for viewing 2D array/image data.
 ///
 /// This widget owns all its state and can be embedded in any egui application.
 /// Multiple instances can coexist without sharing state.
 pub struct ArrayViewerWidget {
     // === Image data ===
-    /// Raw pixel data as f64 values
-    pixels: Option<Vec<f64>>,
+    /// Raw pixel data in its native element type
+    pixels: Option<PixelData>,
+    /// Color image shown directly, bypassing stretch and colormap (exclusive with `pixels`)
+    rgb: Option<RgbImage>,
+    /// Red, green and blue channels of a composite (exclusive with `pixels`)
+    composite: Option<[CompositeChannel; 3]>,
+    /// Composite channel that stretch and limit controls apply to (0-2)
+    active_channel: usize,
+    /// Full stack of planes when a cube is loaded (`pixels` holds the displayed plane)
+    cube: Option<Cube>,
+    /// Index along every cube axis (entries of the display axes are unused)
+    axis_index: Vec<usize>,
+    /// Whether cube limits follow the displayed slice or the whole cube
+    slice_limits: SliceLimits,
+    /// Whether the cube is being played as a movie
+    playing: bool,
+    /// Playback rate in frames per second
+    playback_fps: f64,
+    /// What playback does at the ends of the cube
+    playback_mode: PlaybackMode,
+    /// Current playback direction (bounce mode plays backward too)
+    playback_forward: bool,
+    /// Frame clock time at which the displayed playback frame was due
+    last_frame_time: Option<f64>,
+    /// Prebuilt upcoming playback frames, by slice index
+    frame_cache: HashMap<usize, PlaybackFrame>,
+    /// Cached finite range of each image row, for incremental min/max after
+    /// region updates (empty until the first update)
+    row_ranges: Vec<Option<(f64, f64)>>,
+    /// What happens to the display limits when new data arrives
+    limits_policy: LimitsPolicy,
+    /// How automatic limits are estimated from the data
+    scale_mode: ScaleMode,
+    /// Central percentile of the data spanned by percentile limits
+    limits_percentile: f64,
+    /// k of median ± k·MAD limits
+    mad_factor: f64,
     /// Image width in pixels
     width: u32,
     /// Image height in pixels
@@ -113,6 +408,8 @@ pub struct ArrayViewerWidget {
     original_max_val: f64,
     /// Whether the source data is integer-typed (for display formatting)
     is_integer: bool,
+    /// Which quantity of complex data is displayed (ignored for real data)
+    complex_component: ComplexComponent,
 
     // === View transformation ===
     /// Pan/zoom/rotation transformation state
@@ -131,12 +428,29 @@ pub struct ArrayViewerWidget {
     max_limit_input_text: String,
 
     // === Stretch settings ===
-    /// Current stretch type (Linear or Log)
+    /// Current stretch type
     stretch_type: StretchType,
+    /// Parameters of the log, asinh and power stretches
+    stretch_params: StretchParams,
     /// Contrast/bias settings for Linear mode
     linear_cb: ContrastBias,
     /// Contrast/bias settings for Log mode
     log_cb: ContrastBias,
+    /// Contrast/bias settings for Sqrt mode
+    sqrt_cb: ContrastBias,
+    /// Contrast/bias settings for Squared mode
+    squared_cb: ContrastBias,
+    /// Contrast/bias settings for Asinh mode
+    asinh_cb: ContrastBias,
+    /// Contrast/bias settings for Power mode
+    power_cb: ContrastBias,
+    /// Contrast/bias settings for Sinh mode
+    sinh_cb: ContrastBias,
+    /// Contrast/bias settings for HistEq mode
+    histeq_cb: ContrastBias,
+    /// Histogram equalization CDF of the scalar image, while that stretch is
+    /// in use (rebuilt when the data or limits change)
+    hist_eq: Option<HistogramCdf>,
     /// Contrast settings for Symmetric mode (bias is ignored)
     symmetric_cb: ContrastBias,
     /// Whether user is currently dragging to adjust contrast/bias
@@ -147,20 +461,49 @@ pub struct ArrayViewerWidget {
     standard_colormap: Colormap,
     /// Current colormap for symmetric/diverging mode
     diverging_colormap: Colormap,
+    /// Current colormap for complex phase display
+    cyclic_colormap: Colormap,
     /// Symmetric mode (scale around zero)
     symmetric_mode: bool,
     /// Whether colormap is reversed
     colormap_reversed: bool,
+    /// Number of discrete colormap levels, or None for a continuous colormap
+    colormap_levels: Option<u32>,
+
+    // === Special colors ===
+    /// Colors of bad, under-range and over-range pixels, by `SpecialColor`
+    special_colors: [SpecialColorSetting; 3],
+
+    // === Mask overlay ===
+    /// Optional mask of the same shape as the image (nonzero = masked)
+    mask: Option<Vec<u8>>,
+    /// Whether the mask overlay is drawn
+    show_mask: bool,
+    /// Color of masked pixels in the overlay
+    mask_color: Color32,
+    /// Opacity of the mask overlay (0-1)
+    mask_opacity: f32,
+    /// Whether masked pixels are ignored (like NaN) when computing min/max
+    mask_excludes_limits: bool,
 
     // === Rendering state ===
     /// Flag indicating texture needs rebuild
     texture_dirty: bool,
-    /// Cached hover information: (image_x, image_y, raw_value)
-    hover_info: Option<(u32, u32, f64)>,
+    /// Cached hover information: (image_x, image_y, raw values of each channel)
+    hover_info: Option<(u32, u32, Vec<PixelValue>)>,
+    /// Last plain click on the image, waiting to be taken by the host
+    pending_click: Option<(u32, u32, Vec<PixelValue>)>,
     /// Main image texture
     texture: Option<TextureHandle>,
     /// Colorbar texture
     colorbar_texture: Option<TextureHandle>,
+    /// Smallest distinct labels of the image, listed in the legend of a
+    /// qualitative colormap (one more than fits, if there are more)
+    legend_labels: Vec<i64>,
+    /// Mask overlay texture (white where masked, transparent elsewhere)
+    mask_texture: Option<TextureHandle>,
+    /// Flag indicating mask texture needs rebuild
+    mask_texture_dirty: bool,
     /// Track if right mouse button started a drag (for contrast/bias adjustment)
     stretch_drag_active: bool,
     /// Track when zoom was last changed (for overlay display)
@@ -182,6 +525,23 @@ impl ArrayViewerWidget {
     pub fn new() -> Self {
         Self {
             pixels: None,
+            rgb: None,
+            composite: None,
+            active_channel: 0,
+            cube: None,
+            axis_index: Vec::new(),
+            slice_limits: SliceLimits::default(),
+            playing: false,
+            playback_fps: DEFAULT_PLAYBACK_FPS,
+            playback_mode: PlaybackMode::default(),
+            playback_forward: true,
+            last_frame_time: None,
+            frame_cache: HashMap::new(),
+            row_ranges: Vec::new(),
+            limits_policy: LimitsPolicy::default(),
+            scale_mode: ScaleMode::default(),
+            limits_percentile: limits::DEFAULT_PERCENTILE,
+            mad_factor: limits::DEFAULT_MAD_FACTOR,
             width: 0,
             height: 0,
             min_val: 0.0,
@@ -189,24 +549,45 @@ impl ArrayViewerWidget {
             original_min_val: 0.0,
             original_max_val: 1.0,
             is_integer: false,
+            complex_component: ComplexComponent::default(),
             transform: ViewTransform::new(),
             rotation_input_text: "0".to_string(),
             rotation_input_focused: false,
             min_limit_input_text: "0".to_string(),
             max_limit_input_text: "1".to_string(),
             stretch_type: StretchType::default(),
+            stretch_params: StretchParams::default(),
             linear_cb: ContrastBias::default(),
             log_cb: ContrastBias::default(),
+            sqrt_cb: ContrastBias::default(),
+            squared_cb: ContrastBias::default(),
+            asinh_cb: ContrastBias::default(),
+            power_cb: ContrastBias::default(),
+            sinh_cb: ContrastBias::default(),
+            histeq_cb: ContrastBias::default(),
+            hist_eq: None,
             symmetric_cb: ContrastBias::default(),
             is_adjusting_stretch: false,
             standard_colormap: Colormap::default(),
             diverging_colormap: Colormap::RdBu,
+            cyclic_colormap: Colormap::Hsv,
             symmetric_mode: false,
             colormap_reversed: false,
+            colormap_levels: None,
+            special_colors: DEFAULT_SPECIAL_COLORS.map(|color| SpecialColorSetting { color, enabled: false }),
+            mask: None,
+            show_mask: true,
+            mask_color: DEFAULT_MASK_COLOR,
+            mask_opacity: DEFAULT_MASK_OPACITY,
+            mask_excludes_limits: false,
             texture_dirty: false,
             hover_info: None,
+            pending_click: None,
             texture: None,
             colorbar_texture: None,
+            legend_labels: Vec::new(),
+            mask_texture: None,
+            mask_texture_dirty: false,
             stretch_drag_active: false,
             zoom_changed_time: None,
             prev_zoom_level: 1.0,
@@ -219,68 +600,422 @@ impl ArrayViewerWidget {
     // =========================================================================
 
     /// Set new image data, computing min/max for auto-scaling.
+    /// Whether the display limits follow the new data is decided by the
+    /// limits policy. Integer-typed data is displayed without decimals.
     /// Pan is reset if dimensions change; zoom is always preserved.
-    pub fn set_image(&mut self, pixels: Vec<f64>, width: u32, height: u32, is_integer: bool) {
-        // Check if dimensions changed
-        let dimensions_changed = width != self.width || height != self.height;
-
-        // Compute min/max, ignoring NaN values
-        let mut min_val = f64::INFINITY;
-        let mut max_val = f64::NEG_INFINITY;
+    pub fn set_image(&mut self, pixels: PixelData, width: u32, height: u32) {
+        let had_scalar = self.pixels.is_some();
+        self.is_integer = pixels.is_integer();
+        self.pixels = Some(pixels);
+        self.rgb = None;
+        self.composite = None;
+        self.cube = None;
+        self.set_dimensions(width, height);
+        self.apply_limits_policy(had_scalar);
+    }
 
-        for &v in &pixels {
-            if v.is_finite() {
-                if v < min_val {
-                    min_val = v;
-                }
-                if v > max_val {
-                    max_val = v;
-                }
+    /// Set a cube (N-D array) and display the plane through the current
+    /// axis indices, which are kept (clamped) when the number of axes matches.
+    /// Display settings are kept; limits follow the limits policy.
+    /// Pan is reset if dimensions change; zoom is always preserved.
+    pub fn set_cube(&mut self, cube: Cube) {
+        let had_scalar = self.pixels.is_some();
+        if self.axis_index.len() == cube.ndim() {
+            for (index, &len) in self.axis_index.iter_mut().zip(cube.shape()) {
+                *index = (*index).min(len - 1);
+            }
+        } else {
+            self.axis_index = vec![0; cube.ndim()];
+        }
+        let plane = cube.plane(&self.axis_index);
+        let (width, height) = (cube.width() as u32, cube.height() as u32);
+        self.is_integer = plane.is_integer();
+        self.pixels = Some(plane);
+        self.rgb = None;
+        self.composite = None;
+        self.cube = Some(cube);
+        self.set_dimensions(width, height);
+        self.apply_limits_policy(had_scalar);
+    }
+
+    /// Set an RGB composite from three scalar images of the same shape.
+    /// Each channel gets its own limits; stretch settings carry over from a
+    /// previously loaded composite, and so do its limits where the limits
+    /// policy keeps them.
+    /// Pan is reset if dimensions change; zoom is always preserved.
+    pub fn set_composite_image(&mut self, channels: [PixelData; 3], width: u32, height: u32) {
+        let mut channels = channels.map(CompositeChannel::new);
+        let previous = self.composite.as_ref().map(|previous| {
+            for (channel, prev) in channels.iter_mut().zip(previous) {
+                channel.stretch_type = prev.stretch_type;
+                channel.cb = prev.cb;
             }
+            previous.each_ref().map(|prev| (prev.min_val, prev.max_val, prev.is_limits_modified()))
+        });
+        self.pixels = None;
+        self.rgb = None;
+        self.cube = None;
+        self.composite = Some(channels);
+        self.set_dimensions(width, height);
+        self.apply_composite_limits_policy(previous);
+    }
+
+    /// Set a color image, displayed directly without stretch or colormap.
+    /// Pan is reset if dimensions change; zoom is always preserved.
+    pub fn set_rgb_image(&mut self, image: RgbImage, width: u32, height: u32) {
+        self.pixels = None;
+        self.composite = None;
+        self.cube = None;
+        self.rgb = Some(image);
+        self.set_dimensions(width, height);
+        self.texture_dirty = true;
+    }
+
+    /// Overwrite a block of the scalar image starting at (`x`, `y`) with
+    /// `patch` (`w` values per row, same element type as the image).
+    /// Only the affected rows are rescanned for min/max and only the block of
+    /// the texture is rebuilt. `keep_limits` overrides the limits policy:
+    /// when set the current display limits stay as they are; otherwise they
+    /// follow the updated auto-scaling range.
+    pub fn update_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        w: u32,
+        patch: &PixelData,
+        keep_limits: Option<bool>,
+    ) -> Result<(), String> {
+        let keep_limits = keep_limits.unwrap_or_else(|| self.limits_policy.keeps_limits(self.is_limits_modified()));
+        let width = self.width as usize;
+        let pixels = match self.pixels.as_mut() {
+            Some(pixels) if self.cube.is_none() => pixels,
+            _ => return Err("Regions can only be updated on a 2D scalar image".to_string()),
+        };
+        pixels.write_region(width, x as usize, y as usize, w as usize, patch)?;
+        let (w, h) = (w as usize, patch.len() / w as usize);
+
+        let (min_val, max_val) = if self.effective_scale_mode() != ScaleMode::MinMax {
+            // Robust limits can't be merged per row
+            self.scalar_auto_limits()
+        } else {
+            self.update_row_ranges(y as usize..y as usize + h)
+        };
+        self.original_min_val = min_val;
+        self.original_max_val = max_val;
+        let limits_changed = (self.min_val - min_val).abs() > 1e-10 || (self.max_val - max_val).abs() > 1e-10;
+        if limits_changed && !keep_limits {
+            // New limits restretch every pixel
+            self.reset_limits();
+            return Ok(());
         }
 
-        // Handle edge cases
-        if !min_val.is_finite() {
-            min_val = 0.0;
+        self.hist_eq = None;
+        if self.texture_dirty {
+            return Ok(()); // A full rebuild is already pending
         }
-        if !max_val.is_finite() {
-            max_val = 1.0;
+        if self.stretch_type == StretchType::HistEq {
+            // The new data changes the mapping of every pixel
+            self.texture_dirty = true;
+            return Ok(());
         }
-        if (max_val - min_val).abs() < f64::EPSILON {
-            max_val = min_val + 1.0;
+        let (scale_min, scale_max) = self.scaling_range();
+        let color_image = self.colorize(patch, [w, h], scale_min, scale_max, None);
+        match self.texture.as_mut() {
+            Some(texture) => texture.set_partial([x as usize, y as usize], color_image, TextureOptions::NEAREST),
+            None => self.texture_dirty = true,
         }
+        Ok(())
+    }
 
-        self.pixels = Some(pixels);
-        self.width = width;
-        self.height = height;
-        self.min_val = min_val;
-        self.max_val = max_val;
-        self.original_min_val = min_val;
-        self.original_max_val = max_val;
-        self.min_limit_input_text = if is_integer {
-            format!("{}", min_val as i64)
-        } else {
-            format_scientific(min_val)
+    /// Check if we have image data
+    pub fn has_image(&self) -> bool {
+        self.pixels.is_some() || self.rgb.is_some() || self.composite.is_some()
+    }
+
+    /// Check if the current image is an RGB composite of three scalar channels
+    pub fn is_composite(&self) -> bool {
+        self.composite.is_some()
+    }
+
+    /// Get the composite channel that stretch and limit controls apply to (0-2)
+    pub fn active_channel(&self) -> usize {
+        self.active_channel
+    }
+
+    /// Set the composite channel that stretch and limit controls apply to (0-2)
+    pub fn set_active_channel(&mut self, channel: usize) {
+        self.active_channel = channel.min(2);
+    }
+
+    /// The active composite channel, if a composite is loaded
+    fn active_composite_channel(&self) -> Option<&CompositeChannel> {
+        self.composite.as_ref().map(|channels| &channels[self.active_channel])
+    }
+
+    /// Mutable access to the active composite channel, if a composite is loaded
+    fn active_composite_channel_mut(&mut self) -> Option<&mut CompositeChannel> {
+        self.composite.as_mut().map(|channels| &mut channels[self.active_channel])
+    }
+
+    /// Check if a cube is loaded
+    pub fn is_cube(&self) -> bool {
+        self.cube.is_some()
+    }
+
+    /// Length of every cube axis (empty if no cube is loaded)
+    pub fn cube_shape(&self) -> &[usize] {
+        self.cube.as_ref().map_or(&[], Cube::shape)
+    }
+
+    /// The first axis that is not displayed: stepped by the arrow keys,
+    /// played back, and addressed by `slice_index`/`set_slice`
+    fn slice_axis(&self) -> Option<usize> {
+        self.cube.as_ref().and_then(|cube| cube.slice_axes().first().copied())
+    }
+
+    /// Number of planes along the slice axis (0 if no cube is loaded)
+    pub fn num_slices(&self) -> usize {
+        self.slice_axis().map_or(0, |axis| self.cube_shape()[axis])
+    }
+
+    /// Index of the displayed plane along the slice axis
+    pub fn slice_index(&self) -> usize {
+        self.slice_axis().map_or(0, |axis| self.axis_index[axis])
+    }
+
+    /// Display another plane along the slice axis (clamped to its length)
+    pub fn set_slice(&mut self, index: usize) {
+        if let Some(axis) = self.slice_axis() {
+            self.set_axis_index(axis, index);
+        }
+    }
+
+    /// Index along every cube axis (empty if no cube is loaded; entries of
+    /// the display axes are unused)
+    pub fn axis_index(&self) -> &[usize] {
+        &self.axis_index
+    }
+
+    /// Move along a non-displayed cube axis (index clamped to its length).
+    /// With per-slice limits the auto limits follow the new plane, unless the
+    /// user has modified them.
+    pub fn set_axis_index(&mut self, axis: usize, index: usize) {
+        let Some(cube) = &self.cube else {
+            return;
         };
-        self.max_limit_input_text = if is_integer {
-            format!("{}", max_val as i64)
+        if !cube.slice_axes().contains(&axis) {
+            return;
+        }
+        let index = index.min(cube.shape()[axis] - 1);
+        if index == self.axis_index[axis] {
+            return;
+        }
+        self.axis_index[axis] = index;
+        self.pixels = Some(cube.plane(&self.axis_index));
+
+        if self.slice_limits == SliceLimits::PerSlice && !self.is_limits_modified() {
+            self.reset_auto_limits();
         } else {
-            format_scientific(max_val)
+            if self.slice_limits == SliceLimits::PerSlice {
+                // Keep the user's limits; reset now returns to this slice's range
+                (self.original_min_val, self.original_max_val) = self.scalar_auto_limits();
+            }
+            self.hist_eq = None;
+            self.texture_dirty = true;
+        }
+    }
+
+    /// Axes shown along image rows and columns, as (y, x)
+    pub fn display_axes(&self) -> Option<(usize, usize)> {
+        self.cube.as_ref().map(Cube::display_axes)
+    }
+
+    /// Choose the cube axes shown along image rows and columns.
+    /// Limits are recomputed; pan and mask are reset if the plane shape changes.
+    pub fn set_display_axes(&mut self, y_axis: usize, x_axis: usize) -> Result<(), String> {
+        let Some(cube) = self.cube.as_mut() else {
+            return Err("No cube is loaded".to_string());
         };
-        self.texture_dirty = true;
-        self.is_integer = is_integer;
+        if cube.display_axes() == (y_axis, x_axis) {
+            return Ok(());
+        }
+        cube.set_display_axes(y_axis, x_axis)?;
+        let (width, height) = (cube.width() as u32, cube.height() as u32);
+        self.pixels = Some(cube.plane(&self.axis_index));
+        self.set_dimensions(width, height);
+        self.reset_auto_limits();
+        Ok(())
+    }
+
+    /// Set one label per cube axis (an empty list restores the defaults)
+    pub fn set_axis_labels(&mut self, labels: Vec<String>) -> Result<(), String> {
+        match self.cube.as_mut() {
+            Some(cube) => cube.set_labels(labels),
+            None => Err("No cube is loaded".to_string()),
+        }
+    }
 
-        // Only reset pan if dimensions changed; always keep zoom
-        if dimensions_changed {
-            self.transform.reset_pan();
-            // Initialize pivot point to image center when dimensions change
-            self.transform.set_pivot_to_center(width, height);
+    /// Full cube index of an image pixel, in axis order
+    pub fn cube_index_at(&self, x: u32, y: u32) -> Option<Vec<usize>> {
+        let (y_axis, x_axis) = self.display_axes()?;
+        let mut index = self.axis_index.clone();
+        index[y_axis] = y as usize;
+        index[x_axis] = x as usize;
+        Some(index)
+    }
+
+    /// Get whether cube limits follow the displayed slice or the whole cube
+    pub fn slice_limits(&self) -> SliceLimits {
+        self.slice_limits
+    }
+
+    /// Set whether cube limits follow the displayed slice or the whole cube.
+    /// Limits are recomputed when this changes.
+    pub fn set_slice_limits(&mut self, mode: SliceLimits) {
+        if self.slice_limits != mode {
+            self.slice_limits = mode;
+            if self.is_cube() {
+                self.reset_auto_limits();
+            }
         }
     }
 
-    /// Check if we have image data
-    pub fn has_image(&self) -> bool {
-        self.pixels.is_some()
+    /// Get what happens to the display limits when new data arrives
+    pub fn limits_policy(&self) -> LimitsPolicy {
+        self.limits_policy
+    }
+
+    /// Set what happens to the display limits when new data arrives.
+    /// Switching percentile tracking on or off recomputes the limits.
+    pub fn set_limits_policy(&mut self, policy: LimitsPolicy) {
+        let mode = self.effective_scale_mode();
+        self.limits_policy = policy;
+        if self.effective_scale_mode() != mode {
+            self.reset_auto_limits();
+        }
+    }
+
+    /// Get how automatic limits are estimated from the data
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.scale_mode
+    }
+
+    /// Set how automatic limits are estimated from the data.
+    /// Limits are recomputed when this changes.
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+        let effective = self.effective_scale_mode();
+        self.scale_mode = mode;
+        if self.effective_scale_mode() != effective {
+            self.reset_auto_limits();
+        }
+    }
+
+    /// Get the central percentile of the data spanned by percentile limits
+    pub fn limits_percentile(&self) -> f64 {
+        self.limits_percentile
+    }
+
+    /// Set the central percentile of the data spanned by percentile limits
+    /// (clamped to 0-100; e.g. 99.5 clips 0.25% at each end)
+    pub fn set_limits_percentile(&mut self, percent: f64) {
+        if percent.is_finite() {
+            self.limits_percentile = percent.clamp(0.0, 100.0);
+            if self.effective_scale_mode() == ScaleMode::Percentile {
+                self.reset_auto_limits();
+            }
+        }
+    }
+
+    /// Get k of median ± k·MAD limits
+    pub fn mad_factor(&self) -> f64 {
+        self.mad_factor
+    }
+
+    /// Set k of median ± k·MAD limits (must be positive)
+    pub fn set_mad_factor(&mut self, k: f64) {
+        if k.is_finite() && k > 0.0 {
+            self.mad_factor = k;
+            if self.effective_scale_mode() == ScaleMode::Mad {
+                self.reset_auto_limits();
+            }
+        }
+    }
+
+    /// Scale mode in effect: percentile tracking overrides the chosen mode
+    fn effective_scale_mode(&self) -> ScaleMode {
+        if self.limits_policy == LimitsPolicy::Percentile {
+            ScaleMode::Percentile
+        } else {
+            self.scale_mode
+        }
+    }
+
+    /// Check if cube playback is running
+    pub fn is_playing(&self) -> bool {
+        self.playing && self.is_cube()
+    }
+
+    /// Start or stop cube playback (ignored unless a cube is loaded)
+    pub fn set_playing(&mut self, playing: bool) {
+        self.playing = playing && self.is_cube();
+        self.last_frame_time = None;
+        if !self.playing {
+            self.frame_cache.clear();
+        }
+    }
+
+    /// Get the playback rate in frames per second
+    pub fn playback_fps(&self) -> f64 {
+        self.playback_fps
+    }
+
+    /// Set the playback rate in frames per second (clamped to 0.1-60)
+    pub fn set_playback_fps(&mut self, fps: f64) {
+        if fps.is_finite() {
+            self.playback_fps = fps.clamp(0.1, MAX_PLAYBACK_FPS);
+        }
+    }
+
+    /// Get what playback does at the ends of the cube
+    pub fn playback_mode(&self) -> PlaybackMode {
+        self.playback_mode
+    }
+
+    /// Set what playback does at the ends of the cube
+    pub fn set_playback_mode(&mut self, mode: PlaybackMode) {
+        self.playback_mode = mode;
+        self.frame_cache.clear();
+    }
+
+    /// Check if the current image is a color image
+    pub fn is_rgb(&self) -> bool {
+        self.rgb.is_some()
+    }
+
+    /// Check if the current image holds complex values
+    pub fn is_complex(&self) -> bool {
+        self.pixels.as_ref().is_some_and(PixelData::is_complex)
+    }
+
+    /// Get which quantity of complex data is displayed
+    pub fn complex_component(&self) -> ComplexComponent {
+        self.complex_component
+    }
+
+    /// Set which quantity of complex data is displayed.
+    /// Limits are recomputed for the new quantity; phase uses a fixed -π..π range.
+    pub fn set_complex_component(&mut self, component: ComplexComponent) {
+        if self.complex_component != component {
+            self.complex_component = component;
+            if self.is_complex() {
+                self.reset_auto_limits();
+            }
+        }
+    }
+
+    /// Whether complex phase is being displayed (uses the cyclic colormap)
+    fn is_phase_mode(&self) -> bool {
+        self.complex_component == ComplexComponent::Phase && self.is_complex()
     }
 
     /// Get image dimensions
@@ -373,35 +1108,45 @@ impl ArrayViewerWidget {
     // Stretch / Colormap API
     // =========================================================================
 
-    /// Get current stretch type
+    /// Get current stretch type (of the active channel, for composites)
     pub fn stretch_type(&self) -> StretchType {
-        self.stretch_type
+        match self.active_composite_channel() {
+            Some(channel) => channel.stretch_type,
+            None => self.stretch_type,
+        }
     }
 
     /// Toggle between Linear and Log stretch
     pub fn toggle_stretch_type(&mut self) {
         self.stretch_type = match self.stretch_type {
             StretchType::Linear => StretchType::Log,
-            StretchType::Log => StretchType::Linear,
+            _ => StretchType::Linear,
         };
         self.texture_dirty = true;
     }
 
     /// Set stretch type directly (used by selectable labels)
     pub fn set_stretch_type(&mut self, stretch_type: StretchType) {
-        if self.stretch_type != stretch_type {
+        if let Some(channel) = self.active_composite_channel_mut() {
+            if channel.stretch_type != stretch_type {
+                channel.stretch_type = stretch_type;
+                self.texture_dirty = true;
+            }
+        } else if self.stretch_type != stretch_type {
             self.stretch_type = stretch_type;
-            // If switching to log, disable symmetric mode (log doesn't work well with negative values)
-            if stretch_type == StretchType::Log && self.symmetric_mode {
+            // Symmetric mode is linear only; the other stretches assume min..max maps to 0..1
+            if stretch_type != StretchType::Linear && self.symmetric_mode {
                 self.symmetric_mode = false;
             }
             self.texture_dirty = true;
         }
     }
 
-    /// Get current contrast/bias for the active stretch mode
+    /// Get current contrast/bias for the active stretch mode (or composite channel)
     pub fn current_contrast_bias(&self) -> ContrastBias {
-        if self.symmetric_mode {
+        if let Some(channel) = self.active_composite_channel() {
+            channel.cb
+        } else if self.symmetric_mode {
             // In symmetric mode, always use default bias (0.5) to keep it centered
             ContrastBias {
                 contrast: self.symmetric_cb.contrast,
@@ -411,22 +1156,65 @@ impl ArrayViewerWidget {
             match self.stretch_type {
                 StretchType::Linear => self.linear_cb,
                 StretchType::Log => self.log_cb,
+                StretchType::Sqrt => self.sqrt_cb,
+                StretchType::Squared => self.squared_cb,
+                StretchType::Asinh => self.asinh_cb,
+                StretchType::Power => self.power_cb,
+                StretchType::Sinh => self.sinh_cb,
+                StretchType::HistEq => self.histeq_cb,
             }
         }
     }
 
     /// Get mutable reference to current contrast/bias
     fn current_contrast_bias_mut(&mut self) -> &mut ContrastBias {
-        if self.symmetric_mode {
+        if let Some(channels) = self.composite.as_mut() {
+            &mut channels[self.active_channel].cb
+        } else if self.symmetric_mode {
             &mut self.symmetric_cb
         } else {
             match self.stretch_type {
                 StretchType::Linear => &mut self.linear_cb,
                 StretchType::Log => &mut self.log_cb,
+                StretchType::Sqrt => &mut self.sqrt_cb,
+                StretchType::Squared => &mut self.squared_cb,
+                StretchType::Asinh => &mut self.asinh_cb,
+                StretchType::Power => &mut self.power_cb,
+                StretchType::Sinh => &mut self.sinh_cb,
+                StretchType::HistEq => &mut self.histeq_cb,
             }
         }
     }
 
+    /// Get the parameters of the log, asinh and power stretches
+    pub fn stretch_params(&self) -> StretchParams {
+        self.stretch_params
+    }
+
+    /// Set the log stretch exponent (must be greater than 1)
+    pub fn set_log_exponent(&mut self, exponent: f64) {
+        if exponent.is_finite() && exponent > 1.0 {
+            self.stretch_params.log_exponent = exponent;
+            self.texture_dirty = true;
+        }
+    }
+
+    /// Set the asinh softening (must be positive; smaller is more log-like)
+    pub fn set_asinh_softening(&mut self, softening: f64) {
+        if softening.is_finite() && softening > 0.0 {
+            self.stretch_params.asinh_softening = softening;
+            self.texture_dirty = true;
+        }
+    }
+
+    /// Set the power stretch exponent (must be positive)
+    pub fn set_power_exponent(&mut self, exponent: f64) {
+        if exponent.is_finite() && exponent > 0.0 {
+            self.stretch_params.power_exponent = exponent;
+            self.texture_dirty = true;
+        }
+    }
+
     /// Set contrast value directly (clamped to valid range)
     pub fn set_contrast(&mut self, contrast: f64) {
         let cb = self.current_contrast_bias_mut();
@@ -470,10 +1258,17 @@ impl ArrayViewerWidget {
         self.texture_dirty = true;
     }
 
-    /// Reset all stretch settings (both modes) to defaults
+    /// Reset all stretch settings (every mode) to defaults
     pub fn reset_all_stretch(&mut self) {
         self.linear_cb = ContrastBias::default();
         self.log_cb = ContrastBias::default();
+        self.sqrt_cb = ContrastBias::default();
+        self.squared_cb = ContrastBias::default();
+        self.asinh_cb = ContrastBias::default();
+        self.power_cb = ContrastBias::default();
+        self.sinh_cb = ContrastBias::default();
+        self.histeq_cb = ContrastBias::default();
+        self.stretch_params = StretchParams::default();
         self.symmetric_cb = ContrastBias::default();
         self.stretch_type = StretchType::Linear;
         self.texture_dirty = true;
@@ -481,7 +1276,9 @@ impl ArrayViewerWidget {
 
     /// Check if current stretch mode has non-default contrast/bias
     pub fn is_stretch_modified(&self) -> bool {
-        if self.symmetric_mode {
+        if self.is_composite() {
+            !self.current_contrast_bias().is_default()
+        } else if self.symmetric_mode {
             // In symmetric mode, only contrast matters
             (self.symmetric_cb.contrast - DEFAULT_CONTRAST).abs() >= 0.001
         } else {
@@ -491,6 +1288,9 @@ impl ArrayViewerWidget {
 
     /// Check if min/max limits have been modified from original values
     pub fn is_limits_modified(&self) -> bool {
+        if let Some(channels) = &self.composite {
+            return channels.iter().any(CompositeChannel::is_limits_modified);
+        }
         (self.min_val - self.original_min_val).abs() > 1e-10
             || (self.max_val - self.original_max_val).abs() > 1e-10
     }
@@ -502,6 +1302,11 @@ impl ArrayViewerWidget {
 
     /// Reset limits to original auto-computed values
     pub fn reset_limits(&mut self) {
+        if let Some(channels) = self.composite.as_mut() {
+            channels.iter_mut().for_each(CompositeChannel::reset_limits);
+            self.texture_dirty = true;
+            return;
+        }
         self.min_val = self.original_min_val;
         self.max_val = self.original_max_val;
         self.min_limit_input_text = if self.is_integer {
@@ -535,21 +1340,52 @@ impl ArrayViewerWidget {
 
     /// Get current colormap (based on current mode)
     pub fn colormap(&self) -> Colormap {
-        if self.symmetric_mode {
+        if self.is_phase_mode() {
+            self.cyclic_colormap
+        } else if self.symmetric_mode {
             self.diverging_colormap
         } else {
             self.standard_colormap
         }
     }
 
-    /// Set colormap (stores in appropriate field based on colormap type)
-    pub fn set_colormap(&mut self, colormap: Colormap) {
-        if colormap.is_diverging() {
+    /// Set the colormap of the current mode (complex phase, symmetric or
+    /// standard); each mode remembers its own. Fails if the image is not drawn
+    /// with this colormap: color images and composites use none, and complex
+    /// phase has no integer labels for qualitative colormaps.
+    pub fn set_colormap(&mut self, colormap: Colormap) -> Result<(), String> {
+        if self.is_rgb() || self.is_composite() {
+            return Err("Color images and composites are shown without a colormap".to_string());
+        }
+        if !self.colormap_kinds().contains(&colormap.kind()) {
+            return Err(format!(
+                "Colormap \"{}\" is {} and can't show complex phase",
+                colormap.name(),
+                colormap.kind().name()
+            ));
+        }
+        if self.is_phase_mode() {
+            self.cyclic_colormap = colormap;
+        } else if self.symmetric_mode {
             self.diverging_colormap = colormap;
         } else {
             self.standard_colormap = colormap;
         }
         self.texture_dirty = true;
+        Ok(())
+    }
+
+    /// Kinds of colormaps offered in the current mode, the best fitting first:
+    /// cyclic ones for complex phase, diverging ones in symmetric mode
+    fn colormap_kinds(&self) -> &'static [ColormapKind] {
+        use ColormapKind::*;
+        if self.is_phase_mode() {
+            &[Cyclic, Sequential, Diverging]
+        } else if self.symmetric_mode {
+            &[Diverging, Cyclic, Sequential, Qualitative]
+        } else {
+            &[Sequential, Cyclic, Diverging, Qualitative]
+        }
     }
 
     /// Check if symmetric mode is enabled
@@ -575,45 +1411,376 @@ impl ArrayViewerWidget {
         self.colormap_reversed
     }
 
-    /// Toggle colormap reversal
-    pub fn toggle_reverse(&mut self) {
-        self.colormap_reversed = !self.colormap_reversed;
+    /// Set whether the colormap is reversed
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.colormap_reversed = reversed;
         self.texture_dirty = true;
     }
 
-    // =========================================================================
-    // Internal helpers
-    // =========================================================================
+    /// Get the number of discrete colormap levels (None when continuous)
+    pub fn colormap_levels(&self) -> Option<u32> {
+        self.colormap_levels
+    }
 
-    /// Get the scaling range based on symmetric mode
-    fn scaling_range(&self) -> (f64, f64) {
-        if self.symmetric_mode {
-            let abs_max = self.min_val.abs().max(self.max_val.abs());
-            (-abs_max, abs_max)
-        } else {
-            (self.min_val, self.max_val)
+    /// Quantize the colormap into `levels` flat bands (2 to `MAX_COLORMAP_LEVELS`)
+    /// for contour-like displays, or make it continuous again with None
+    pub fn set_colormap_levels(&mut self, levels: Option<u32>) {
+        if levels.is_none_or(|n| (2..=colormap::MAX_COLORMAP_LEVELS).contains(&n)) {
+            self.colormap_levels = levels;
+            self.texture_dirty = true;
         }
     }
 
-    /// Get raw pixel value at image coordinates
-    fn get_pixel_value(&self, x: u32, y: u32) -> Option<f64> {
-        let pixels = self.pixels.as_ref()?;
-        if x < self.width && y < self.height {
-            let idx = (y as usize) * (self.width as usize) + (x as usize);
-            pixels.get(idx).copied()
-        } else {
+    /// Toggle colormap reversal
+    pub fn toggle_reverse(&mut self) {
+        self.colormap_reversed = !self.colormap_reversed;
+        self.texture_dirty = true;
+    }
+
+    /// Get a special color and whether it is used
+    pub fn special_color(&self, kind: SpecialColor) -> SpecialColorSetting {
+        self.special_colors[kind as usize]
+    }
+
+    /// Set a special color; it is used once enabled
+    pub fn set_special_color(&mut self, kind: SpecialColor, color: Color32) {
+        self.special_colors[kind as usize].color = color;
+        self.texture_dirty = true;
+    }
+
+    /// Enable or disable a special color. Disabled, bad pixels take the bottom
+    /// colormap color and out-of-range pixels the nearest end of the colormap.
+    pub fn set_special_color_enabled(&mut self, kind: SpecialColor, enabled: bool) {
+        self.special_colors[kind as usize].enabled = enabled;
+        self.texture_dirty = true;
+    }
+
+    // =========================================================================
+    // Mask API
+    // =========================================================================
+
+    /// Set the mask layer (nonzero = masked), which must match the image shape
+    pub fn set_mask(&mut self, mask: Vec<u8>) -> Result<(), String> {
+        if !self.has_image() {
+            return Err("Cannot set a mask before image data is loaded".to_string());
+        }
+        let expected_len = (self.width as usize) * (self.height as usize);
+        if mask.len() != expected_len {
+            return Err(format!(
+                "Mask size mismatch: expected {} pixels ({}x{}), got {}",
+                expected_len,
+                self.width,
+                self.height,
+                mask.len()
+            ));
+        }
+        self.mask = Some(mask);
+        self.mask_texture_dirty = true;
+        if self.mask_excludes_limits {
+            self.reset_auto_limits();
+        }
+        Ok(())
+    }
+
+    /// Remove the mask layer
+    pub fn clear_mask(&mut self) {
+        if self.mask.take().is_some() {
+            self.mask_texture = None;
+            if self.mask_excludes_limits {
+                self.reset_auto_limits();
+            }
+        }
+    }
+
+    /// Check if a mask layer is loaded
+    pub fn has_mask(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    /// Get mask value at image coordinates
+    pub fn mask_value(&self, x: u32, y: u32) -> Option<u8> {
+        let mask = self.mask.as_ref()?;
+        if x < self.width && y < self.height {
+            mask.get((y as usize) * (self.width as usize) + (x as usize)).copied()
+        } else {
             None
         }
     }
 
-    /// Get min/max values
+    /// Get whether the mask overlay is drawn
+    pub fn show_mask(&self) -> bool {
+        self.show_mask
+    }
+
+    /// Set whether the mask overlay is drawn
+    pub fn set_show_mask(&mut self, show: bool) {
+        self.show_mask = show;
+    }
+
+    /// Get mask overlay color
+    pub fn mask_color(&self) -> Color32 {
+        self.mask_color
+    }
+
+    /// Set mask overlay color
+    pub fn set_mask_color(&mut self, color: Color32) {
+        self.mask_color = color;
+    }
+
+    /// Get mask overlay opacity (0-1)
+    pub fn mask_opacity(&self) -> f32 {
+        self.mask_opacity
+    }
+
+    /// Set mask overlay opacity (clamped to 0-1)
+    pub fn set_mask_opacity(&mut self, opacity: f32) {
+        self.mask_opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// Get whether masked pixels are ignored when computing min/max
+    pub fn mask_excludes_limits(&self) -> bool {
+        self.mask_excludes_limits
+    }
+
+    /// Set whether masked pixels are ignored (like NaN) when computing min/max.
+    /// Limits are recomputed if a mask is loaded.
+    pub fn set_mask_excludes_limits(&mut self, exclude: bool) {
+        if self.mask_excludes_limits != exclude {
+            self.mask_excludes_limits = exclude;
+            if self.has_mask() {
+                self.reset_auto_limits();
+            }
+        }
+    }
+
+    // =========================================================================
+    // Internal helpers
+    // =========================================================================
+
+    /// Recompute the auto-scaling limits from the current data and apply them
+    fn reset_auto_limits(&mut self) {
+        self.row_ranges.clear();
+        self.hist_eq = None;
+        let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+
+        if let Some(channels) = &self.composite {
+            let ranges = channels.each_ref().map(|channel| self.data_limits(&channel.pixels, exclude));
+            if let Some(channels) = self.composite.as_mut() {
+                for (channel, range) in channels.iter_mut().zip(ranges) {
+                    (channel.original_min_val, channel.original_max_val) = range;
+                    channel.hist_eq = None;
+                }
+            }
+            self.reset_limits();
+            return;
+        }
+
+        if self.pixels.is_none() {
+            return;
+        }
+        (self.original_min_val, self.original_max_val) = self.scalar_auto_limits();
+        self.reset_limits();
+    }
+
+    /// Rebuild stale histogram equalization CDFs (invalidated by a data
+    /// change, or built for other limits) and drop unused ones
+    fn update_hist_eq(&mut self) {
+        let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+        let component = self.complex_component;
+        if let Some(channels) = self.composite.as_mut() {
+            for channel in channels.iter_mut() {
+                let range = (channel.min_val, channel.max_val);
+                let active = channel.stretch_type == StretchType::HistEq;
+                refresh_cdf(&mut channel.hist_eq, active, &channel.pixels, component, exclude, range);
+            }
+            return;
+        }
+        let range = self.scaling_range();
+        if let Some(pixels) = &self.pixels {
+            let active = self.stretch_type == StretchType::HistEq;
+            refresh_cdf(&mut self.hist_eq, active, pixels, component, exclude, range);
+        }
+    }
+
+    /// Apply the limits policy after new data replaced the scalar image.
+    /// Limits are always recomputed when there was no scalar image before.
+    fn apply_limits_policy(&mut self, had_scalar: bool) {
+        if had_scalar && self.limits_policy.keeps_limits(self.is_limits_modified()) {
+            // Keep the current limits; reset now returns to the new data's range
+            self.row_ranges.clear();
+            self.hist_eq = None;
+            (self.original_min_val, self.original_max_val) = self.scalar_auto_limits();
+            self.texture_dirty = true;
+        } else {
+            self.reset_auto_limits();
+        }
+    }
+
+    /// Apply the limits policy to each channel after new data replaced the
+    /// composite. `previous` holds the limits of the replaced channels and
+    /// whether the user had modified them; without it limits are recomputed.
+    fn apply_composite_limits_policy(&mut self, previous: Option<[(f64, f64, bool); 3]>) {
+        self.reset_auto_limits();
+        let (Some(previous), Some(channels)) = (previous, self.composite.as_mut()) else {
+            return;
+        };
+        for (channel, (min_val, max_val, modified)) in channels.iter_mut().zip(previous) {
+            if self.limits_policy.keeps_limits(modified) {
+                // Keep the current limits; reset now returns to the new data's range
+                (channel.min_val, channel.max_val) = (min_val, max_val);
+                channel.min_limit_input_text = format_limit(min_val, channel.is_integer);
+                channel.max_limit_input_text = format_limit(max_val, channel.is_integer);
+            }
+        }
+    }
+
+    /// Rescan the finite range of the given rows of the scalar image and
+    /// return the auto-scaling limits of the whole image. Per-row ranges are
+    /// cached, so only changed rows are scanned after the first call.
+    fn update_row_ranges(&mut self, rows: Range<usize>) -> (f64, f64) {
+        let Some(pixels) = &self.pixels else {
+            return auto_limits(None);
+        };
+        if self.is_phase_mode() {
+            return self.plane_auto_limits(pixels);
+        }
+        let (width, height) = (self.width as usize, self.height as usize);
+        let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+        let component = self.complex_component;
+        let row_range = |row: usize| {
+            let span = row * width..(row + 1) * width;
+            pixels.slice(span.clone()).finite_range(component, exclude.map(|mask| &mask[span]))
+        };
+
+        if self.row_ranges.len() == height {
+            for row in rows {
+                self.row_ranges[row] = row_range(row);
+            }
+        } else {
+            self.row_ranges = (0..height).map(row_range).collect();
+        }
+        let range = self
+            .row_ranges
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)));
+        auto_limits(range)
+    }
+
+    /// Auto-scaling limits of the scalar image (or cube), ignoring NaN values
+    fn scalar_auto_limits(&self) -> (f64, f64) {
+        match (&self.cube, &self.pixels) {
+            (Some(cube), _) if self.slice_limits == SliceLimits::Global && !self.is_phase_mode() => {
+                let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+                let component = self.complex_component;
+                auto_limits(match self.effective_scale_mode() {
+                    ScaleMode::MinMax => cube.finite_range(component, exclude),
+                    mode => {
+                        // Robust estimators work on a sample of large cubes
+                        let step = cube.size().div_ceil(MAX_CUBE_LIMIT_SAMPLES);
+                        let mut values = cube.finite_values(component, exclude, step);
+                        mode.range(&mut values, self.limits_percentile, self.mad_factor)
+                    }
+                })
+            }
+            (_, Some(pixels)) => self.plane_auto_limits(pixels),
+            _ => auto_limits(None),
+        }
+    }
+
+    /// Auto-scaling limits of a single plane; phase uses a fixed -π..π range
+    fn plane_auto_limits(&self, pixels: &PixelData) -> (f64, f64) {
+        if self.is_phase_mode() {
+            return (-std::f64::consts::PI, std::f64::consts::PI);
+        }
+        let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+        self.data_limits(pixels, exclude)
+    }
+
+    /// Auto-scaling limits of `pixels`, estimated by the scale mode in effect
+    fn data_limits(&self, pixels: &PixelData, exclude: Option<&[u8]>) -> (f64, f64) {
+        let component = self.complex_component;
+        auto_limits(match self.effective_scale_mode() {
+            ScaleMode::MinMax => pixels.finite_range(component, exclude),
+            mode => {
+                let mut values = pixels.finite_values(component, exclude, 1);
+                mode.range(&mut values, self.limits_percentile, self.mad_factor)
+            }
+        })
+    }
+
+    /// Get the scaling range based on symmetric mode
+    fn scaling_range(&self) -> (f64, f64) {
+        self.scaling_range_for(self.min_val, self.max_val)
+    }
+
+    /// Scaling range for the given limits, based on symmetric mode
+    fn scaling_range_for(&self, min_val: f64, max_val: f64) -> (f64, f64) {
+        if self.symmetric_mode {
+            let abs_max = min_val.abs().max(max_val.abs());
+            (-abs_max, abs_max)
+        } else {
+            (min_val, max_val)
+        }
+    }
+
+    /// Record new image dimensions. When they change, the mask is dropped
+    /// (it only applies to images of the same shape) and pan/pivot are reset;
+    /// zoom is always kept.
+    fn set_dimensions(&mut self, width: u32, height: u32) {
+        if width != self.width || height != self.height {
+            self.width = width;
+            self.height = height;
+            self.clear_mask();
+            self.transform.reset_pan();
+            // Initialize pivot point to image center when dimensions change
+            self.transform.set_pivot_to_center(width, height);
+        }
+    }
+
+    /// Get raw values of each channel at image coordinates
+    /// (empty if out of bounds or no image is loaded)
+    pub fn pixel_values(&self, x: u32, y: u32) -> Vec<PixelValue> {
+        if x >= self.width || y >= self.height {
+            return Vec::new();
+        }
+        let idx = (y as usize) * (self.width as usize) + (x as usize);
+        if let Some(rgb) = &self.rgb {
+            rgb.get(idx)
+        } else if let Some(channels) = &self.composite {
+            channels.iter().filter_map(|channel| channel.pixels.get(idx)).collect()
+        } else {
+            self.pixels.as_ref().and_then(|p| p.get(idx)).into_iter().collect()
+        }
+    }
+
+    /// Short labels for the channels returned by `pixel_values`
+    fn channel_labels(&self) -> &'static [&'static str] {
+        match &self.rgb {
+            Some(rgb) => &["R", "G", "B", "A"][..rgb.num_channels()],
+            None if self.is_composite() => &["R", "G", "B"],
+            None => &[],
+        }
+    }
+
+    /// Get min/max values (of the active channel, for composites)
     pub fn value_range(&self) -> (f64, f64) {
-        (self.min_val, self.max_val)
+        match self.active_composite_channel() {
+            Some(channel) => (channel.min_val, channel.max_val),
+            None => (self.min_val, self.max_val),
+        }
     }
 
     /// Set the min value for scaling (marks texture dirty)
     pub fn set_min_val(&mut self, min_val: f64) {
-        if (self.min_val - min_val).abs() > 1e-15 {
+        if let Some(channel) = self.active_composite_channel_mut() {
+            if (channel.min_val - min_val).abs() > 1e-15 {
+                channel.min_val = min_val;
+                channel.min_limit_input_text = format_scientific(min_val);
+                self.texture_dirty = true;
+            }
+        } else if (self.min_val - min_val).abs() > 1e-15 {
             self.min_val = min_val;
             self.min_limit_input_text = format_scientific(min_val);
             self.texture_dirty = true;
@@ -622,7 +1789,13 @@ impl ArrayViewerWidget {
 
     /// Set the max value for scaling (marks texture dirty)
     pub fn set_max_val(&mut self, max_val: f64) {
-        if (self.max_val - max_val).abs() > 1e-15 {
+        if let Some(channel) = self.active_composite_channel_mut() {
+            if (channel.max_val - max_val).abs() > 1e-15 {
+                channel.max_val = max_val;
+                channel.max_limit_input_text = format_scientific(max_val);
+                self.texture_dirty = true;
+            }
+        } else if (self.max_val - max_val).abs() > 1e-15 {
             self.max_val = max_val;
             self.max_limit_input_text = format_scientific(max_val);
             self.texture_dirty = true;
@@ -635,18 +1808,21 @@ impl ArrayViewerWidget {
         self.set_max_val(max_val);
     }
 
-    /// Check if source data is integer-typed
-    pub fn is_integer(&self) -> bool {
-        self.is_integer
+    /// Get current hover info
+    pub fn hover_info(&self) -> Option<&(u32, u32, Vec<PixelValue>)> {
+        self.hover_info.as_ref()
     }
 
-    /// Get current hover info
-    pub fn hover_info(&self) -> Option<(u32, u32, f64)> {
-        self.hover_info
+    /// Take the last plain click on the image: (image_x, image_y, channel values)
+    pub fn take_click(&mut self) -> Option<(u32, u32, Vec<PixelValue>)> {
+        self.pending_click.take()
     }
 
     /// Apply full stretch pipeline to a single value
-    /// Returns a value in 0-1 range suitable for colormap lookup
+    /// Returns a value in 0-1 range suitable for colormap lookup. With `wrap`
+    /// (cyclic colormaps), values outside the limits wrap around instead of
+    /// clamping.
+    #[allow(clippy::too_many_arguments)]
     fn apply_full_stretch(
         &self,
         v: f64,
@@ -654,52 +1830,142 @@ impl ArrayViewerWidget {
         scale_max: f64,
         cb: ContrastBias,
         stretch_type: StretchType,
+        cdf: Option<&HistogramCdf>,
+        wrap: bool,
     ) -> f64 {
         // Step 1: Normalize to 0-1
         let range = scale_max - scale_min;
         let normalized = if v.is_finite() && range.abs() > f64::EPSILON {
-            ((v - scale_min) / range).clamp(0.0, 1.0)
+            let x = (v - scale_min) / range;
+            if wrap { x.rem_euclid(1.0) } else { x.clamp(0.0, 1.0) }
         } else {
             0.0 // NaN/Inf -> black
         };
 
         // Step 2: Apply stretch function
-        let stretched = apply_stretch(normalized, stretch_type);
+        let stretched = apply_stretch(normalized, stretch_type, &self.stretch_params, cdf);
 
         // Step 3: Apply contrast/bias (DS9 formula)
-        apply_contrast_bias(stretched, cb.contrast, cb.bias)
+        if wrap {
+            wrap_contrast_bias(stretched, cb.contrast, cb.bias)
+        } else {
+            apply_contrast_bias(stretched, cb.contrast, cb.bias)
+        }
     }
 
     /// Build a ColorImage from the current pixel data using colormap
     fn build_color_image(&self) -> Option<ColorImage> {
+        if let Some(rgb) = &self.rgb {
+            return Some(ColorImage {
+                size: [self.width as usize, self.height as usize],
+                pixels: rgb.to_colors(),
+                source_size: egui::Vec2::new(self.width as f32, self.height as f32),
+            });
+        }
+        if let Some(channels) = &self.composite {
+            return Some(self.build_composite_image(channels));
+        }
         let pixels = self.pixels.as_ref()?;
-
         let (scale_min, scale_max) = self.scaling_range();
+        let size = [self.width as usize, self.height as usize];
+        Some(self.colorize(pixels, size, scale_min, scale_max, self.hist_eq.as_ref()))
+    }
+
+    /// Map a `size` = [width, height] block of scalar pixel data through the
+    /// stretch and colormap (`cdf` is the histogram equalization CDF, if used).
+    /// Qualitative colormaps skip the stretch and color each value as an
+    /// integer label.
+    fn colorize(
+        &self,
+        pixels: &PixelData,
+        size: [usize; 2],
+        scale_min: f64,
+        scale_max: f64,
+        cdf: Option<&HistogramCdf>,
+    ) -> ColorImage {
         let cb = self.current_contrast_bias();
         let stretch_type = self.stretch_type;
         let colormap = self.colormap();
         let reversed = self.colormap_reversed;
+        let levels = self.colormap_levels;
+        let wrap = colormap.is_cyclic();
 
-        let rgba: Vec<Color32> = pixels
-            .iter()
-            .map(|&v| {
-                let mut adjusted = self.apply_full_stretch(v, scale_min, scale_max, cb, stretch_type);
-                if reversed {
-                    adjusted = 1.0 - adjusted;
-                }
-                colormap.map(adjusted)
+        let rgba: Vec<Color32> = pixels.map_f64(self.complex_component, |v| {
+            if colormap.is_qualitative() {
+                return if v.is_finite() {
+                    colormap.label_color(v.round() as i64)
+                } else {
+                    self.special_color_of(v, scale_min, scale_max).unwrap_or(Color32::TRANSPARENT)
+                };
+            }
+            if let Some(color) = self.special_color_of(v, scale_min, scale_max) {
+                return color;
+            }
+            let mut adjusted = self.apply_full_stretch(v, scale_min, scale_max, cb, stretch_type, cdf, wrap);
+            if reversed {
+                adjusted = 1.0 - adjusted;
+            }
+            if let Some(levels) = levels {
+                adjusted = colormap::quantize(adjusted, levels);
+            }
+            colormap.map(adjusted)
+        });
+
+        ColorImage {
+            size,
+            pixels: rgba,
+            source_size: egui::Vec2::new(size[0] as f32, size[1] as f32),
+        }
+    }
+
+    /// Enabled special color of a value that is bad or outside the scaling range
+    fn special_color_of(&self, v: f64, scale_min: f64, scale_max: f64) -> Option<Color32> {
+        let kind = if !v.is_finite() {
+            SpecialColor::Bad
+        } else if v < scale_min {
+            SpecialColor::Under
+        } else if v > scale_max {
+            SpecialColor::Over
+        } else {
+            return None;
+        };
+        let setting = self.special_colors[kind as usize];
+        setting.enabled.then_some(setting.color)
+    }
+
+    /// Combine the stretched composite channels into one color image
+    fn build_composite_image(&self, channels: &[CompositeChannel; 3]) -> ColorImage {
+        let [r, g, b] = channels.each_ref().map(|channel| {
+            channel.pixels.map_f64(self.complex_component, |v| {
+                let stretched = self.apply_full_stretch(
+                    v,
+                    channel.min_val,
+                    channel.max_val,
+                    channel.cb,
+                    channel.stretch_type,
+                    channel.hist_eq.as_ref(),
+                    false,
+                );
+                (stretched * 255.0) as u8
             })
+        });
+        let pixels = r.into_iter()
+            .zip(g)
+            .zip(b)
+            .map(|((r, g), b)| Color32::from_rgb(r, g, b))
             .collect();
 
-        Some(ColorImage {
+        ColorImage {
             size: [self.width as usize, self.height as usize],
-            pixels: rgba,
+            pixels,
             source_size: egui::Vec2::new(self.width as f32, self.height as f32),
-        })
+        }
     }
 
     /// Rebuild the main image texture
     fn rebuild_texture(&mut self, ctx: &egui::Context) {
+        self.update_hist_eq();
+        self.update_legend_labels();
         if let Some(color_image) = self.build_color_image() {
             self.texture = Some(ctx.load_texture(
                 "image",
@@ -711,6 +1977,140 @@ impl ArrayViewerWidget {
         self.rebuild_colorbar_texture(ctx);
     }
 
+    /// Collect the labels listed in the legend when a qualitative colormap is shown
+    fn update_legend_labels(&mut self) {
+        self.legend_labels.clear();
+        let Some(pixels) = self.pixels.as_ref().filter(|_| self.colormap().is_qualitative()) else {
+            return;
+        };
+        let mut labels = BTreeSet::new();
+        pixels.map_f64(self.complex_component, |v| {
+            if v.is_finite() {
+                labels.insert(v.round() as i64);
+                if labels.len() > MAX_LEGEND_ENTRIES + 1 {
+                    labels.pop_last();
+                }
+            }
+        });
+        self.legend_labels = labels.into_iter().collect();
+    }
+
+    /// Build the plane at `frame` along the slice axis as it will look when
+    /// displayed: with its own auto limits in per-slice mode (unless the user
+    /// has modified the limits), otherwise with the current limits
+    fn build_frame(&self, ctx: &egui::Context, frame: usize) -> Option<PlaybackFrame> {
+        let cube = self.cube.as_ref()?;
+        let mut index = self.axis_index.clone();
+        index[self.slice_axis()?] = frame;
+        let pixels = cube.plane(&index);
+        let auto_limits = (self.slice_limits == SliceLimits::PerSlice).then(|| self.plane_auto_limits(&pixels));
+        let (min_val, max_val) = match auto_limits {
+            Some(limits) if !self.is_limits_modified() => limits,
+            _ => (self.min_val, self.max_val),
+        };
+        let (scale_min, scale_max) = self.scaling_range_for(min_val, max_val);
+        let cdf = (self.stretch_type == StretchType::HistEq).then(|| {
+            let exclude = self.mask.as_deref().filter(|_| self.mask_excludes_limits);
+            HistogramCdf::new(&pixels, self.complex_component, exclude, (scale_min, scale_max))
+        });
+        let size = [cube.width(), cube.height()];
+        let color_image = self.colorize(&pixels, size, scale_min, scale_max, cdf.as_ref());
+        let texture = ctx.load_texture("image", color_image, TextureOptions::NEAREST);
+        Some(PlaybackFrame { pixels, auto_limits, hist_eq: cdf, texture })
+    }
+
+    /// Show a prebuilt playback frame at `frame` along the slice axis. Like
+    /// `set_slice`, but reuses the plane, limits and texture of the frame.
+    fn show_frame(&mut self, frame: usize, prebuilt: PlaybackFrame) {
+        let Some(axis) = self.slice_axis() else {
+            return;
+        };
+        self.axis_index[axis] = frame;
+        self.pixels = Some(prebuilt.pixels);
+        self.row_ranges.clear();
+        if let Some((min_val, max_val)) = prebuilt.auto_limits {
+            let follow = !self.is_limits_modified();
+            // With modified limits, reset now returns to this slice's range
+            (self.original_min_val, self.original_max_val) = (min_val, max_val);
+            if follow {
+                self.reset_limits();
+            }
+        }
+        self.hist_eq = prebuilt.hist_eq;
+        self.texture = Some(prebuilt.texture);
+        // The prebuilt texture already matches the new slice and limits
+        self.texture_dirty = false;
+    }
+
+    /// Advance cube playback when the next frame is due on the frame clock,
+    /// then prebuild the texture of one upcoming frame
+    fn update_playback(&mut self, ctx: &egui::Context) {
+        if !self.playing {
+            return;
+        }
+        let depth = self.num_slices();
+        if depth == 0 {
+            self.playing = false;
+            return;
+        }
+
+        let now = ctx.input(|i| i.time);
+        let interval = 1.0 / self.playback_fps;
+        let last = *self.last_frame_time.get_or_insert(now);
+        if now - last >= interval {
+            let (next, forward) = self.playback_mode.advance(self.slice_index(), depth, self.playback_forward);
+            let prebuilt = match self.frame_cache.remove(&next) {
+                Some(prebuilt) => Some(prebuilt),
+                None => self.build_frame(ctx, next),
+            };
+            self.playback_forward = forward;
+            // Catch up after a stall instead of rushing through frames
+            self.last_frame_time = Some(if now - last > 2.0 * interval { now } else { last + interval });
+
+            match prebuilt {
+                Some(prebuilt) => self.show_frame(next, prebuilt),
+                None => self.set_slice(next),
+            }
+        }
+
+        self.prefetch_frames(ctx, depth);
+        ctx.request_repaint();
+    }
+
+    /// Build the texture of the nearest upcoming playback frame that is not
+    /// cached yet, and drop cached frames that are no longer upcoming
+    fn prefetch_frames(&mut self, ctx: &egui::Context, depth: usize) {
+        let mut upcoming = Vec::with_capacity(PREFETCH_FRAMES);
+        let (mut index, mut forward) = (self.slice_index(), self.playback_forward);
+        for _ in 0..PREFETCH_FRAMES.min(depth - 1) {
+            (index, forward) = self.playback_mode.advance(index, depth, forward);
+            upcoming.push(index);
+        }
+
+        self.frame_cache.retain(|index, _| upcoming.contains(index));
+        if let Some(&index) = upcoming.iter().find(|index| !self.frame_cache.contains_key(index)) {
+            if let Some(prebuilt) = self.build_frame(ctx, index) {
+                self.frame_cache.insert(index, prebuilt);
+            }
+        }
+    }
+
+    /// Rebuild the mask overlay texture
+    fn rebuild_mask_texture(&mut self, ctx: &egui::Context) {
+        self.mask_texture = self.mask.as_ref().map(|mask| {
+            let pixels = mask
+                .iter()
+                .map(|&m| if m != 0 { Color32::WHITE } else { Color32::TRANSPARENT })
+                .collect();
+            let color_image = ColorImage {
+                size: [self.width as usize, self.height as usize],
+                pixels,
+                source_size: egui::Vec2::new(self.width as f32, self.height as f32),
+            };
+            ctx.load_texture("mask", color_image, TextureOptions::NEAREST)
+        });
+    }
+
     /// Rebuild the colorbar texture
     fn rebuild_colorbar_texture(&mut self, ctx: &egui::Context) {
         let height = 256;
@@ -720,16 +2120,24 @@ impl ArrayViewerWidget {
         let stretch_type = self.stretch_type;
         let colormap = self.colormap();
         let reversed = self.colormap_reversed;
+        let levels = self.colormap_levels;
 
         let pixels: Vec<Color32> = (0..height)
             .rev() // Reverse so high values are at top
             .map(|y| {
                 let t = y as f64 / (height - 1) as f64;
-                let stretched = apply_stretch(t, stretch_type);
-                let mut adjusted = apply_contrast_bias(stretched, cb.contrast, cb.bias);
+                let stretched = apply_stretch(t, stretch_type, &self.stretch_params, self.hist_eq.as_ref());
+                let mut adjusted = if colormap.is_cyclic() {
+                    wrap_contrast_bias(stretched, cb.contrast, cb.bias)
+                } else {
+                    apply_contrast_bias(stretched, cb.contrast, cb.bias)
+                };
                 if reversed {
                     adjusted = 1.0 - adjusted;
                 }
+                if let Some(levels) = levels {
+                    adjusted = colormap::quantize(adjusted, levels);
+                }
                 colormap.map(adjusted)
             })
             .collect();
@@ -761,9 +2169,18 @@ impl ArrayViewerWidget {
         // Check if texture needs rebuilding
         if self.texture_dirty {
             self.texture_dirty = false;
+            // Display settings may have changed, so prebuilt frames are stale
+            self.frame_cache.clear();
             self.rebuild_texture(&ctx);
         }
 
+        // Advance cube playback on the frame clock
+        self.update_playback(&ctx);
+        if self.mask_texture_dirty {
+            self.mask_texture_dirty = false;
+            self.rebuild_mask_texture(&ctx);
+        }
+
         // Handle keyboard shortcuts
         self.handle_keyboard_input(&ctx);
 
@@ -804,58 +2221,16 @@ impl ArrayViewerWidget {
         // Draw the image with rotation
         if let Some(texture) = &self.texture {
             let painter = ui.painter_at(rect);
-            
-            if self.transform.rotation().abs() < 0.001 {
-                // No rotation - use simple image draw (faster)
-                // Flip Y-axis for FITS convention: Y=0 at bottom
-                painter.image(
-                    texture.id(),
-                    image_rect,
-                    egui::Rect::from_min_max(egui::pos2(0.0, 1.0), egui::pos2(1.0, 0.0)),
-                    egui::Color32::WHITE,
-                );
-            } else {
-                // With rotation - use mesh with rotated vertices
-                let pivot_screen = self.transform.pivot_to_screen(image_rect, (img_width, img_height));
-                let rotation_rad = self.transform.rotation().to_radians();
-                let cos_r = rotation_rad.cos();
-                let sin_r = rotation_rad.sin();
-                
-                // Helper to rotate a point around pivot
-                let rotate = |p: egui::Pos2| -> egui::Pos2 {
-                    let dx = p.x - pivot_screen.x;
-                    let dy = p.y - pivot_screen.y;
-                    egui::pos2(
-                        pivot_screen.x + dx * cos_r - dy * sin_r,
-                        pivot_screen.y + dx * sin_r + dy * cos_r,
-                    )
-                };
-                
-                // Calculate rotated corners
-                let tl = rotate(image_rect.left_top());
-                let tr = rotate(image_rect.right_top());
-                let br = rotate(image_rect.right_bottom());
-                let bl = rotate(image_rect.left_bottom());
-                
-                // UV coordinates (Y-flipped for FITS convention)
-                let uv_tl = egui::pos2(0.0, 1.0);
-                let uv_tr = egui::pos2(1.0, 1.0);
-                let uv_br = egui::pos2(1.0, 0.0);
-                let uv_bl = egui::pos2(0.0, 0.0);
-                
-                // Build mesh with two triangles using Vertex struct
-                let mut mesh = egui::Mesh::with_texture(texture.id());
-                let color = egui::Color32::WHITE;
-                mesh.vertices.push(egui::epaint::Vertex { pos: tl, uv: uv_tl, color });
-                mesh.vertices.push(egui::epaint::Vertex { pos: tr, uv: uv_tr, color });
-                mesh.vertices.push(egui::epaint::Vertex { pos: br, uv: uv_br, color });
-                mesh.vertices.push(egui::epaint::Vertex { pos: bl, uv: uv_bl, color });
-                mesh.add_triangle(0, 1, 2);
-                mesh.add_triangle(0, 2, 3);
-                
-                painter.add(egui::Shape::mesh(mesh));
+            self.paint_image_layer(&painter, texture.id(), image_rect, Color32::WHITE);
+
+            // Draw mask overlay on top of the image (white texture tinted with mask color)
+            if self.show_mask {
+                if let Some(mask_texture) = &self.mask_texture {
+                    let tint = self.mask_color.gamma_multiply(self.mask_opacity);
+                    self.paint_image_layer(&painter, mask_texture.id(), image_rect, tint);
+                }
             }
-            
+
             // Draw pivot marker if enabled
             if self.transform.show_pivot_marker {
                 let pivot_screen = self.transform.pivot_to_screen(image_rect, (img_width, img_height));
@@ -900,7 +2275,8 @@ impl ArrayViewerWidget {
         }
 
         // Handle contrast/bias adjustment via right-click drag (DS9 style)
-        if response.drag_started_by(PointerButton::Secondary) {
+        // (color images have no stretch to adjust)
+        if response.drag_started_by(PointerButton::Secondary) && !self.is_rgb() {
             self.stretch_drag_active = true;
             self.is_adjusting_stretch = true;
         }
@@ -953,6 +2329,20 @@ impl ArrayViewerWidget {
             }
         }
 
+        // Plain click: record image coordinates and values for the host's click callback
+        if response.clicked() && !has_cmd_or_ctrl {
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                if let Some((img_x, img_y)) = self.transform.screen_to_image_rotated(
+                    click_pos,
+                    image_rect,
+                    (img_width, img_height),
+                ) {
+                    let values = self.pixel_values(img_x, img_y);
+                    self.pending_click = Some((img_x, img_y, values));
+                }
+            }
+        }
+
         // Handle hover to show pixel value (using rotation-aware conversion)
         if let Some(hover_pos) = response.hover_pos() {
             if let Some((img_x, img_y)) = self.transform.screen_to_image_rotated(
@@ -960,11 +2350,8 @@ impl ArrayViewerWidget {
                 image_rect,
                 (img_width, img_height),
             ) {
-                if let Some(value) = self.get_pixel_value(img_x, img_y) {
-                    self.hover_info = Some((img_x, img_y, value));
-                } else {
-                    self.hover_info = None;
-                }
+                let values = self.pixel_values(img_x, img_y);
+                self.hover_info = (!values.is_empty()).then_some((img_x, img_y, values));
             } else {
                 self.hover_info = None;
             }
@@ -985,6 +2372,7 @@ impl ArrayViewerWidget {
         let zoom_action = self.render_zoom_controls(&ctx, viewport_center, rect);
         let rotation_action = self.render_rotation_controls(&ctx, rect);
         let stretch_action = self.render_stretch_controls(&ctx, rect);
+        let slice_action = self.render_slice_controls(&ctx, rect);
         self.render_colorbar(&ctx, rect);
         self.render_stretch_info_overlay(&ctx, rect);
         self.render_zoom_info_overlay(&ctx, rect, current_time);
@@ -1032,21 +2420,45 @@ impl ArrayViewerWidget {
 
         match stretch_action {
             StretchAction::None => {}
-            StretchAction::SetLinear => {
-                self.set_symmetric(false);
-                self.set_stretch_type(StretchType::Linear);
-            }
-            StretchAction::SetLog => {
+            StretchAction::SetStretch(stretch_type) => {
                 self.set_symmetric(false);
-                self.set_stretch_type(StretchType::Log);
+                self.set_stretch_type(stretch_type);
             }
+            StretchAction::SetLogExponent(exponent) => self.set_log_exponent(exponent),
+            StretchAction::SetAsinhSoftening(softening) => self.set_asinh_softening(softening),
+            StretchAction::SetPowerExponent(exponent) => self.set_power_exponent(exponent),
             StretchAction::SetDiverging => {
                 self.set_stretch_type(StretchType::Linear);
                 self.set_symmetric(true);
             }
-            StretchAction::SetColormap(cmap) => self.set_colormap(cmap),
+            StretchAction::SetColormap(cmap) => {
+                // Always valid: the picker only lists colormaps of the current mode
+                let _ = self.set_colormap(cmap);
+            }
+            StretchAction::SetColormapLevels(levels) => self.set_colormap_levels(levels),
             StretchAction::ToggleReverse => self.toggle_reverse(),
             StretchAction::ResetStretch => self.reset_current_stretch(),
+            StretchAction::SetComplexComponent(component) => self.set_complex_component(component),
+            StretchAction::ToggleMask => self.set_show_mask(!self.show_mask),
+            StretchAction::SetMaskColor(color) => self.set_mask_color(color),
+            StretchAction::SetMaskOpacity(opacity) => self.set_mask_opacity(opacity),
+            StretchAction::ToggleMaskExcludesLimits => {
+                self.set_mask_excludes_limits(!self.mask_excludes_limits)
+            }
+            StretchAction::SetActiveChannel(channel) => self.set_active_channel(channel),
+        }
+
+        match slice_action {
+            SliceAction::None => {}
+            SliceAction::SetAxisIndex(axis, index) => self.set_axis_index(axis, index),
+            SliceAction::SetDisplayAxes(y_axis, x_axis) => {
+                // Always valid: built from the cube's own axes
+                let _ = self.set_display_axes(y_axis, x_axis);
+            }
+            SliceAction::SetSliceLimits(mode) => self.set_slice_limits(mode),
+            SliceAction::TogglePlaying => self.set_playing(!self.playing),
+            SliceAction::SetPlaybackFps(fps) => self.set_playback_fps(fps),
+            SliceAction::SetPlaybackMode(mode) => self.set_playback_mode(mode),
         }
 
         response
@@ -1075,6 +2487,23 @@ impl ArrayViewerWidget {
             if i.key_pressed(Key::Num0) {
                 self.zoom_to_fit();
             }
+            // Play/pause cube: space
+            if i.key_pressed(Key::Space) {
+                self.set_playing(!self.playing);
+            }
+            // Step along the first two non-displayed cube axes:
+            // left/right and down/up arrows
+            let slice_axes = self.cube.as_ref().map(Cube::slice_axes).unwrap_or_default();
+            let steps = [(Key::ArrowLeft, Key::ArrowRight), (Key::ArrowDown, Key::ArrowUp)];
+            for (&axis, (back, forward)) in slice_axes.iter().zip(steps) {
+                let index = self.axis_index[axis];
+                if i.key_pressed(back) {
+                    self.set_axis_index(axis, index.saturating_sub(1));
+                }
+                if i.key_pressed(forward) {
+                    self.set_axis_index(axis, index + 1);
+                }
+            }
             // Debug toggle
             if i.key_pressed(Key::F1) {
                 self.show_build_info = !self.show_build_info;
@@ -1281,7 +2710,7 @@ impl ArrayViewerWidget {
             .show(ctx, |ui| {
                 let stretch_type = self.stretch_type();
                 let colormap = self.colormap();
-                let symmetric = self.is_symmetric();
+                let symmetric = self.is_symmetric() && !self.is_composite();
                 let reversed = self.is_reversed();
 
                 // Get themed colors for overlay
@@ -1291,55 +2720,303 @@ impl ArrayViewerWidget {
                 ui.horizontal(|ui| {
                     ui.spacing_mut().item_spacing.x = 4.0;
 
-                    // Colormaps group with Rev toggle
-                    frame_style.show(ui, |ui| {
-                        ui.horizontal(|ui| {
-                            if symmetric {
-                                // Diverging colormaps for symmetric mode
-                                for &cmap in Colormap::diverging_colormaps() {
-                                    let selected = colormap == cmap;
-                                    let label = egui::RichText::new(cmap.name()).color(text_color);
-                                    if ui.selectable_label(selected, label).clicked() {
-                                        action = StretchAction::SetColormap(cmap);
+                    // Colormap and stretch groups (color images bypass both)
+                    if !self.is_rgb() {
+                        if self.is_composite() {
+                            // Channel selector: stretch, contrast/bias and limits apply to the active channel
+                            frame_style.show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    for (i, (name, color)) in COMPOSITE_CHANNELS.into_iter().enumerate() {
+                                        let label = egui::RichText::new(name).color(color).strong();
+                                        if ui.selectable_label(self.active_channel == i, label).on_hover_text("Adjust this channel").clicked() {
+                                            action = StretchAction::SetActiveChannel(i);
+                                        }
+                                    }
+                                });
+                            });
+                        } else {
+                            // Colormaps group with Rev toggle
+                            frame_style.show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    // Colormaps grouped by kind, the best fitting for the mode first
+                                    // (registered colormaps last in each group)
+                                    let colormaps = Colormap::all();
+                                    egui::ComboBox::from_id_salt("colormap")
+                                        .selected_text(egui::RichText::new(colormap.name()).color(text_color))
+                                        .height(COLORMAP_PICKER_HEIGHT)
+                                        .show_ui(ui, |ui| {
+                                            for (i, &kind) in self.colormap_kinds().iter().enumerate() {
+                                                if i > 0 {
+                                                    ui.separator();
+                                                }
+                                                for &cmap in colormaps.iter().filter(|cmap| cmap.kind() == kind) {
+                                                    if ui.selectable_label(colormap == cmap, cmap.name()).clicked() {
+                                                        action = StretchAction::SetColormap(cmap);
+                                                    }
+                                                }
+                                            }
+                                        })
+                                        .response
+                                        .on_hover_text("Colormap");
+
+                                    ui.separator();
+
+                                    // Reverse toggle
+                                    let rev_label = egui::RichText::new(phosphor::ARROWS_DOWN_UP).color(text_color);
+                                    if ui.selectable_label(reversed, rev_label).on_hover_text("Reverse colormap").clicked() {
+                                        action = StretchAction::ToggleReverse;
+                                    }
+
+                                    // Discrete levels toggle, with the number of levels while on
+                                    let levels = self.colormap_levels;
+                                    let steps_label = egui::RichText::new(phosphor::STEPS).color(text_color);
+                                    if ui.selectable_label(levels.is_some(), steps_label).on_hover_text("Discrete colormap levels").clicked() {
+                                        let toggled = if levels.is_some() { None } else { Some(colormap::DEFAULT_COLORMAP_LEVELS) };
+                                        action = StretchAction::SetColormapLevels(toggled);
+                                    }
+                                    if let Some(mut n) = levels {
+                                        let drag = egui::DragValue::new(&mut n)
+                                            .range(2..=colormap::MAX_COLORMAP_LEVELS)
+                                            .speed(0.1)
+                                            .prefix("n = ");
+                                        if ui.add(drag).on_hover_text("Number of levels").changed() {
+                                            action = StretchAction::SetColormapLevels(Some(n));
+                                        }
+                                    }
+                                });
+                            });
+                        }
+
+                        // Stretch modes group
+                        frame_style.show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                for &option in StretchType::all() {
+                                    let label = egui::RichText::new(option.label()).color(text_color);
+                                    let hover = format!("{} stretch", option.title());
+                                    if ui.selectable_label(stretch_type == option && !symmetric, label).on_hover_text(hover).clicked() {
+                                        action = StretchAction::SetStretch(option);
                                     }
                                 }
-                            } else {
-                                // Standard colormaps for Lin/Log modes
-                                for &cmap in Colormap::standard_colormaps() {
-                                    let selected = colormap == cmap;
-                                    let label = egui::RichText::new(cmap.name()).color(text_color);
-                                    if ui.selectable_label(selected, label).clicked() {
-                                        action = StretchAction::SetColormap(cmap);
+                                // Composite channels have no symmetric mode
+                                if !self.is_composite() {
+                                    let div_label = egui::RichText::new("±").color(text_color);
+                                    if ui.selectable_label(symmetric, div_label).on_hover_text("Symmetric scaling (diverging)").clicked() {
+                                        action = StretchAction::SetDiverging;
                                     }
                                 }
+
+                                // Parameter of the selected stretch, if it has one
+                                let mut params = self.stretch_params;
+                                match stretch_type {
+                                    StretchType::Log => {
+                                        // Relative steps over the wide range
+                                        let speed = params.log_exponent * 0.01;
+                                        let drag = egui::DragValue::new(&mut params.log_exponent)
+                                            .range(1.01..=1e6)
+                                            .speed(speed)
+                                            .prefix("a = ");
+                                        if ui.add(drag).on_hover_text("Log exponent").changed() {
+                                            action = StretchAction::SetLogExponent(params.log_exponent);
+                                        }
+                                    }
+                                    StretchType::Asinh => {
+                                        let drag = egui::DragValue::new(&mut params.asinh_softening)
+                                            .range(0.001..=10.0)
+                                            .speed(0.001)
+                                            .prefix("a = ");
+                                        if ui.add(drag).on_hover_text("Asinh softening").changed() {
+                                            action = StretchAction::SetAsinhSoftening(params.asinh_softening);
+                                        }
+                                    }
+                                    StretchType::Power => {
+                                        let drag = egui::DragValue::new(&mut params.power_exponent)
+                                            .range(0.05..=10.0)
+                                            .speed(0.01)
+                                            .prefix("p = ");
+                                        if ui.add(drag).on_hover_text("Power exponent").changed() {
+                                            action = StretchAction::SetPowerExponent(params.power_exponent);
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            });
+                        });
+                    }
+
+                    // Complex component group (only for complex data)
+                    if self.is_complex() {
+                        frame_style.show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                for &component in ComplexComponent::all() {
+                                    let selected = self.complex_component == component;
+                                    let label = egui::RichText::new(component.label()).color(text_color);
+                                    if ui.selectable_label(selected, label).on_hover_text(component.name()).clicked() {
+                                        action = StretchAction::SetComplexComponent(component);
+                                    }
+                                }
+                            });
+                        });
+                    }
+
+                    // Mask overlay group (only when a mask is loaded)
+                    if self.has_mask() {
+                        frame_style.show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                let icon = if self.show_mask { phosphor::EYE } else { phosphor::EYE_SLASH };
+                                let mask_label = egui::RichText::new(format!("{} Mask", icon)).color(text_color);
+                                if ui.selectable_label(self.show_mask, mask_label).on_hover_text("Show mask overlay").clicked() {
+                                    action = StretchAction::ToggleMask;
+                                }
+
+                                let mut color = self.mask_color;
+                                if egui::color_picker::color_edit_button_srgba(ui, &mut color, egui::color_picker::Alpha::Opaque).changed() {
+                                    action = StretchAction::SetMaskColor(color);
+                                }
+
+                                let mut opacity = self.mask_opacity;
+                                ui.spacing_mut().slider_width = 60.0;
+                                if ui.add(egui::Slider::new(&mut opacity, 0.0..=1.0).show_value(false)).on_hover_text("Mask opacity").changed() {
+                                    action = StretchAction::SetMaskOpacity(opacity);
+                                }
+
+                                let exclude_label = egui::RichText::new(phosphor::FUNNEL).color(text_color);
+                                if ui.selectable_label(self.mask_excludes_limits, exclude_label).on_hover_text("Exclude masked pixels from min/max").clicked() {
+                                    action = StretchAction::ToggleMaskExcludesLimits;
+                                }
+                            });
+                        });
+                    }
+                });
+            });
+
+        action
+    }
+
+    /// Render cube controls at top-center of widget: an index slider per
+    /// non-displayed axis, playback and limit controls, and the display axes.
+    /// Returns an action to be applied after rendering.
+    fn render_slice_controls(&self, ctx: &egui::Context, _widget_rect: egui::Rect) -> SliceAction {
+        let Some(cube) = &self.cube else {
+            return SliceAction::None;
+        };
+        let slice_axes = cube.slice_axes();
+        let (y_axis, x_axis) = cube.display_axes();
+        let margin = 10.0;
+        let mut action = SliceAction::None;
+
+        egui::Area::new(egui::Id::new("slice_controls"))
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, margin))
+            .show(ctx, |ui| {
+                let frame_style = overlay_frame(ui);
+                let text_color = get_overlay_text_color(ui);
+
+                frame_style.show(ui, |ui| {
+                    // One index slider per non-displayed axis; the first two are
+                    // also stepped with the left/right and down/up arrow keys
+                    egui::Grid::new("slice_sliders").spacing(egui::vec2(4.0, 4.0)).show(ui, |ui| {
+                        let key_hints = [("←", "→"), ("↓", "↑")];
+                        for (row, &axis) in slice_axes.iter().enumerate() {
+                            let last = cube.shape()[axis] - 1;
+                            let current = self.axis_index[axis];
+                            let (back_key, forward_key) = key_hints.get(row).copied().unwrap_or_default();
+
+                            ui.label(egui::RichText::new(cube.label(axis)).color(text_color));
+
+                            let back = egui::Button::new(egui::RichText::new(phosphor::CARET_LEFT).color(text_color))
+                                .fill(Color32::TRANSPARENT);
+                            if ui.add_enabled(current > 0, back).on_hover_text(format!("Previous {}", back_key)).clicked() {
+                                action = SliceAction::SetAxisIndex(axis, current - 1);
                             }
 
-                            ui.separator();
+                            let mut index = current;
+                            ui.spacing_mut().slider_width = 160.0;
+                            let slider = egui::Slider::new(&mut index, 0..=last).show_value(false);
+                            if ui.add(slider).changed() {
+                                action = SliceAction::SetAxisIndex(axis, index);
+                            }
 
-                            // Reverse toggle
-                            let rev_label = egui::RichText::new(phosphor::ARROWS_DOWN_UP).color(text_color);
-                            if ui.selectable_label(reversed, rev_label).on_hover_text("Reverse colormap").clicked() {
-                                action = StretchAction::ToggleReverse;
+                            let forward = egui::Button::new(egui::RichText::new(phosphor::CARET_RIGHT).color(text_color))
+                                .fill(Color32::TRANSPARENT);
+                            if ui.add_enabled(current < last, forward).on_hover_text(format!("Next {}", forward_key)).clicked() {
+                                action = SliceAction::SetAxisIndex(axis, current + 1);
                             }
-                        });
+
+                            // Frame counter (0-based, like the axis index)
+                            let counter = format!("{:>width$} / {}", current, last, width = last.to_string().len());
+                            ui.label(egui::RichText::new(counter).color(text_color).monospace());
+                            ui.end_row();
+                        }
                     });
 
-                    // Stretch modes group
-                    frame_style.show(ui, |ui| {
-                        ui.horizontal(|ui| {
-                            let lin_label = egui::RichText::new("Lin").color(text_color);
-                            if ui.selectable_label(stretch_type == StretchType::Linear && !symmetric, lin_label).on_hover_text("Linear stretch").clicked() {
-                                action = StretchAction::SetLinear;
+                    ui.horizontal(|ui| {
+                        if let Some(&play_axis) = slice_axes.first() {
+                            let (play_icon, play_hint) = if self.playing {
+                                (phosphor::PAUSE, "Pause (space)")
+                            } else {
+                                (phosphor::PLAY, "Play (space)")
+                            };
+                            let play = egui::Button::new(egui::RichText::new(play_icon).color(text_color))
+                                .fill(Color32::TRANSPARENT);
+                            let play_hint = format!("{} along {}", play_hint, cube.label(play_axis));
+                            if ui.add(play).on_hover_text(play_hint).clicked() {
+                                action = SliceAction::TogglePlaying;
                             }
-                            let log_label = egui::RichText::new("Log").color(text_color);
-                            if ui.selectable_label(stretch_type == StretchType::Log, log_label).on_hover_text("Logarithmic stretch").clicked() {
-                                action = StretchAction::SetLog;
+
+                            let mut fps = self.playback_fps;
+                            let fps_drag = egui::DragValue::new(&mut fps)
+                                .range(1.0..=MAX_PLAYBACK_FPS)
+                                .speed(0.2)
+                                .max_decimals(1)
+                                .suffix(" fps");
+                            if ui.add(fps_drag).on_hover_text("Playback rate").changed() {
+                                action = SliceAction::SetPlaybackFps(fps);
                             }
-                            let div_label = egui::RichText::new("±").color(text_color);
-                            if ui.selectable_label(symmetric, div_label).on_hover_text("Symmetric scaling (diverging)").clicked() {
-                                action = StretchAction::SetDiverging;
+
+                            let bounce = self.playback_mode == PlaybackMode::Bounce;
+                            let (mode_icon, mode_hint, other_mode) = if bounce {
+                                (phosphor::ARROWS_LEFT_RIGHT, "Bounce at the ends (click to loop)", PlaybackMode::Loop)
+                            } else {
+                                (phosphor::REPEAT, "Loop to the start (click to bounce)", PlaybackMode::Bounce)
+                            };
+                            let mode_btn = egui::Button::new(egui::RichText::new(mode_icon).color(text_color))
+                                .fill(Color32::TRANSPARENT);
+                            if ui.add(mode_btn).on_hover_text(mode_hint).clicked() {
+                                action = SliceAction::SetPlaybackMode(other_mode);
                             }
-                        });
+
+                            ui.separator();
+
+                            let global = self.slice_limits == SliceLimits::Global;
+                            let global_label = egui::RichText::new(phosphor::STACK).color(text_color);
+                            if ui.selectable_label(global, global_label).on_hover_text("Scale limits from the whole cube").clicked() {
+                                action = SliceAction::SetSliceLimits(if global {
+                                    SliceLimits::PerSlice
+                                } else {
+                                    SliceLimits::Global
+                                });
+                            }
+
+                            ui.separator();
+                        }
+
+                        // Display axes: picking the axis already on the other side swaps them
+                        for (name, shown, other) in [("Y", y_axis, x_axis), ("X", x_axis, y_axis)] {
+                            ui.label(egui::RichText::new(name).color(text_color));
+                            egui::ComboBox::from_id_salt(("display_axis", name))
+                                .selected_text(cube.label(shown))
+                                .show_ui(ui, |ui| {
+                                    for axis in 0..cube.ndim() {
+                                        if ui.selectable_label(axis == shown, cube.label(axis)).clicked() && axis != shown {
+                                            let other = if axis == other { shown } else { other };
+                                            action = if name == "Y" {
+                                                SliceAction::SetDisplayAxes(axis, other)
+                                            } else {
+                                                SliceAction::SetDisplayAxes(other, axis)
+                                            };
+                                        }
+                                    }
+                                });
+                        }
                     });
                 });
             });
@@ -1347,9 +3024,79 @@ impl ArrayViewerWidget {
         action
     }
 
+    /// Paint a texture covering the image area, honoring rotation.
+    /// Textures are drawn Y-flipped so that row 0 is at the bottom.
+    fn paint_image_layer(
+        &self,
+        painter: &egui::Painter,
+        texture_id: egui::TextureId,
+        image_rect: egui::Rect,
+        tint: Color32,
+    ) {
+        let (img_width, img_height) = self.dimensions();
+        if self.transform.rotation().abs() < 0.001 {
+            // No rotation - use simple image draw (faster)
+            // Flip Y-axis for FITS convention: Y=0 at bottom
+            painter.image(
+                texture_id,
+                image_rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 1.0), egui::pos2(1.0, 0.0)),
+                tint,
+            );
+        } else {
+            // With rotation - use mesh with rotated vertices
+            let pivot_screen = self.transform.pivot_to_screen(image_rect, (img_width, img_height));
+            let rotation_rad = self.transform.rotation().to_radians();
+            let cos_r = rotation_rad.cos();
+            let sin_r = rotation_rad.sin();
+            
+            // Helper to rotate a point around pivot
+            let rotate = |p: egui::Pos2| -> egui::Pos2 {
+                let dx = p.x - pivot_screen.x;
+                let dy = p.y - pivot_screen.y;
+                egui::pos2(
+                    pivot_screen.x + dx * cos_r - dy * sin_r,
+                    pivot_screen.y + dx * sin_r + dy * cos_r,
+                )
+            };
+            
+            // Calculate rotated corners
+            let tl = rotate(image_rect.left_top());
+            let tr = rotate(image_rect.right_top());
+            let br = rotate(image_rect.right_bottom());
+            let bl = rotate(image_rect.left_bottom());
+            
+            // UV coordinates (Y-flipped for FITS convention)
+            let uv_tl = egui::pos2(0.0, 1.0);
+            let uv_tr = egui::pos2(1.0, 1.0);
+            let uv_br = egui::pos2(1.0, 0.0);
+            let uv_bl = egui::pos2(0.0, 0.0);
+            
+            // Build mesh with two triangles using Vertex struct
+            let mut mesh = egui::Mesh::with_texture(texture_id);
+            let color = tint;
+            mesh.vertices.push(egui::epaint::Vertex { pos: tl, uv: uv_tl, color });
+            mesh.vertices.push(egui::epaint::Vertex { pos: tr, uv: uv_tr, color });
+            mesh.vertices.push(egui::epaint::Vertex { pos: br, uv: uv_br, color });
+            mesh.vertices.push(egui::epaint::Vertex { pos: bl, uv: uv_bl, color });
+            mesh.add_triangle(0, 1, 2);
+            mesh.add_triangle(0, 2, 3);
+            
+            painter.add(egui::Shape::mesh(mesh));
+        }
+    }
+
     /// Render colorbar overlay at top-left of widget with editable limit values
     fn render_colorbar(&mut self, ctx: &egui::Context, widget_rect: egui::Rect) {
-        if !self.has_image() {
+        if !self.has_image() || self.is_rgb() {
+            return;
+        }
+        if self.is_composite() {
+            self.render_composite_limits(ctx, widget_rect);
+            return;
+        }
+        if self.colormap().is_qualitative() {
+            self.render_legend(ctx, widget_rect);
             return;
         }
 
@@ -1362,13 +3109,46 @@ impl ArrayViewerWidget {
         let spacing = 4.0_f32;
         let text_input_height = 20.0_f32;
         
-        // Calculate positions
-        let bar_pos = egui::pos2(widget_rect.min.x + COLORBAR_MARGIN, widget_rect.min.y + COLORBAR_MARGIN);
+        let swatch_height = 8.0_f32;
+        let swatch_step = swatch_height + 3.0;
+        let [bad, under, over] = self.special_colors.map(|setting| setting.enabled.then_some(setting.color));
+
+        // Calculate positions, leaving room for the over-range swatch above the bar
+        let bar_top = widget_rect.min.y + COLORBAR_MARGIN + if over.is_some() { swatch_step } else { 0.0 };
+        let bar_pos = egui::pos2(widget_rect.min.x + COLORBAR_MARGIN, bar_top);
         let bar_rect = egui::Rect::from_min_size(bar_pos, egui::vec2(bar_width, bar_height));
         
+        // Special color swatches: over-range above the bar, under-range and bad below it
+        let swatch_rect = |top: f32| egui::Rect::from_min_size(egui::pos2(bar_rect.min.x, top), egui::vec2(bar_width, swatch_height));
+        let mut swatches = Vec::new();
+        if let Some(color) = over {
+            swatches.push((SpecialColor::Over, color, swatch_rect(bar_rect.min.y - swatch_step)));
+        }
+        let mut controls_top = bar_rect.max.y;
+        for (kind, color) in [(SpecialColor::Under, under), (SpecialColor::Bad, bad)] {
+            if let Some(color) = color {
+                let rect = swatch_rect(controls_top + swatch_step - swatch_height);
+                controls_top = rect.max.y;
+                swatches.push((kind, color, rect));
+            }
+        }
+
         // Paint colorbar directly to the screen (no interaction, no Area)
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Middle, egui::Id::new("colorbar_paint")));
+        let swatch_stroke = egui::Stroke::new(bar_stroke_width, Color32::GRAY);
+        for (kind, color, rect) in swatches {
+            // Out-of-range swatches point away from the bar, like matplotlib's colorbar extensions
+            let points = match kind {
+                SpecialColor::Over => vec![rect.left_bottom(), rect.right_bottom(), rect.center_top()],
+                SpecialColor::Under => vec![rect.left_top(), rect.right_top(), rect.center_bottom()],
+                SpecialColor::Bad => {
+                    painter.rect(rect, 0.0, color, swatch_stroke, egui::StrokeKind::Outside);
+                    continue;
+                }
+            };
+            painter.add(egui::Shape::convex_polygon(points, color, swatch_stroke));
+        }
         if let Some(texture) = &self.colorbar_texture {
-            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Middle, egui::Id::new("colorbar_paint")));
             painter.rect_stroke(
                 bar_rect.expand(bar_stroke_offset),
                 0.0,
@@ -1383,114 +3163,43 @@ impl ArrayViewerWidget {
             );
         }
         
-        // Max value text input - separate Area just for this widget
-        let max_input_pos = egui::pos2(bar_rect.max.x + spacing, bar_rect.min.y);
-        egui::Area::new(egui::Id::new("colorbar_max_input"))
-            .fixed_pos(max_input_pos)
-            .order(egui::Order::Middle)
-            .show(ctx, |ui| {
-                let text_color = get_overlay_text_color(ui);
-                let is_dark = ui.visuals().dark_mode;
-                
-                let edit_bg = if is_dark {
-                    egui::Color32::from_black_alpha(180)
-                } else {
-                    egui::Color32::from_white_alpha(220)
-                };
-                let edit_bg_hover = if is_dark {
-                    egui::Color32::from_black_alpha(200)
-                } else {
-                    egui::Color32::from_white_alpha(240)
-                };
-                let edit_bg_active = if is_dark {
-                    egui::Color32::from_black_alpha(220)
-                } else {
-                    egui::Color32::from_white_alpha(255)
-                };
-                ui.style_mut().visuals.extreme_bg_color = edit_bg;
-                ui.style_mut().visuals.widgets.inactive.bg_fill = edit_bg;
-                ui.style_mut().visuals.widgets.hovered.bg_fill = edit_bg_hover;
-                ui.style_mut().visuals.widgets.active.bg_fill = edit_bg_active;
-                
-                let max_edit = egui::TextEdit::singleline(&mut self.max_limit_input_text)
-                    .desired_width(text_input_width)
-                    .horizontal_align(egui::Align::Center)
-                    .text_color(text_color)
-                    .font(egui::FontId::proportional(13.0));
-                let max_response = ui.add(max_edit);
-                
-                if max_response.lost_focus() || (max_response.has_focus() && ui.input(|i| i.key_pressed(Key::Enter))) {
-                    if let Ok(new_val) = self.max_limit_input_text.trim().parse::<f64>() {
-                        if (self.max_val - new_val).abs() > 1e-15 {
-                            self.max_val = new_val;
-                            self.texture_dirty = true;
-                        }
-                    } else {
-                        self.max_limit_input_text = if is_int {
-                            format!("{}", self.max_val as i64)
-                        } else {
-                            format_scientific(self.max_val)
-                        };
-                    }
-                }
-                max_response.on_hover_text("Maximum display value");
-            });
-        
-        // Min value text input - separate Area just for this widget
-        let min_input_pos = egui::pos2(bar_rect.max.x + spacing, bar_rect.max.y - text_input_height);
-        egui::Area::new(egui::Id::new("colorbar_min_input"))
-            .fixed_pos(min_input_pos)
-            .order(egui::Order::Middle)
-            .show(ctx, |ui| {
-                let text_color = get_overlay_text_color(ui);
-                let is_dark = ui.visuals().dark_mode;
-                
-                let edit_bg = if is_dark {
-                    egui::Color32::from_black_alpha(180)
-                } else {
-                    egui::Color32::from_white_alpha(220)
-                };
-                let edit_bg_hover = if is_dark {
-                    egui::Color32::from_black_alpha(200)
-                } else {
-                    egui::Color32::from_white_alpha(240)
-                };
-                let edit_bg_active = if is_dark {
-                    egui::Color32::from_black_alpha(220)
-                } else {
-                    egui::Color32::from_white_alpha(255)
-                };
-                ui.style_mut().visuals.extreme_bg_color = edit_bg;
-                ui.style_mut().visuals.widgets.inactive.bg_fill = edit_bg;
-                ui.style_mut().visuals.widgets.hovered.bg_fill = edit_bg_hover;
-                ui.style_mut().visuals.widgets.active.bg_fill = edit_bg_active;
-                
-                let min_edit = egui::TextEdit::singleline(&mut self.min_limit_input_text)
-                    .desired_width(text_input_width)
-                    .horizontal_align(egui::Align::Center)
-                    .text_color(text_color)
-                    .font(egui::FontId::proportional(13.0));
-                let min_response = ui.add(min_edit);
-                
-                if min_response.lost_focus() || (min_response.has_focus() && ui.input(|i| i.key_pressed(Key::Enter))) {
-                    if let Ok(new_val) = self.min_limit_input_text.trim().parse::<f64>() {
-                        if (self.min_val - new_val).abs() > 1e-15 {
-                            self.min_val = new_val;
-                            self.texture_dirty = true;
-                        }
+        // Min and max value text inputs - separate Areas just for these widgets
+        let mut limits_changed = false;
+        let min_input_top = bar_rect.max.y - text_input_height;
+        let inputs = [
+            ("colorbar_max_input", bar_rect.min.y, &mut self.max_limit_input_text, &mut self.max_val, "Maximum"),
+            ("colorbar_min_input", min_input_top, &mut self.min_limit_input_text, &mut self.min_val, "Minimum"),
+        ];
+        for (id, top, text, value, which) in inputs {
+            egui::Area::new(egui::Id::new(id))
+                .fixed_pos(egui::pos2(bar_rect.max.x + spacing, top))
+                .order(egui::Order::Middle)
+                .show(ctx, |ui| {
+                    let text_color = get_overlay_text_color(ui);
+                    let (bg, bg_hover, bg_active) = if ui.visuals().dark_mode {
+                        let alpha = Color32::from_black_alpha;
+                        (alpha(180), alpha(200), alpha(220))
                     } else {
-                        self.min_limit_input_text = if is_int {
-                            format!("{}", self.min_val as i64)
-                        } else {
-                            format_scientific(self.min_val)
-                        };
-                    }
-                }
-                min_response.on_hover_text("Minimum display value");
-            });
-        
+                        let alpha = Color32::from_white_alpha;
+                        (alpha(220), alpha(240), alpha(255))
+                    };
+                    let visuals = &mut ui.style_mut().visuals;
+                    visuals.extreme_bg_color = bg;
+                    visuals.widgets.inactive.bg_fill = bg;
+                    visuals.widgets.hovered.bg_fill = bg_hover;
+                    visuals.widgets.active.bg_fill = bg_active;
+
+                    limits_changed |= limit_text_edit(ui, text, value, is_int, text_input_width, text_color)
+                        .on_hover_text(format!("{} display value", which))
+                        .changed();
+                });
+        }
+        if limits_changed {
+            self.texture_dirty = true;
+        }
+
         // Reset button below the colorbar - compact with theme background
-        let reset_button_pos = egui::pos2(bar_rect.min.x, bar_rect.max.y + spacing);
+        let reset_button_pos = egui::pos2(bar_rect.min.x, controls_top + spacing);
         egui::Area::new(egui::Id::new("colorbar_reset_button"))
             .fixed_pos(reset_button_pos)
             .order(egui::Order::Middle)
@@ -1514,6 +3223,200 @@ impl ArrayViewerWidget {
                 }
                 response.on_hover_text("Reset contrast/bias and limits");
             });
+
+        // Scale mode picker next to the reset button
+        let scale_mode_pos = egui::pos2(bar_rect.max.x + spacing, controls_top + spacing);
+        let mut scale_change = None;
+        egui::Area::new(egui::Id::new("colorbar_scale_mode"))
+            .fixed_pos(scale_mode_pos)
+            .order(egui::Order::Middle)
+            .show(ctx, |ui| {
+                overlay_frame(ui).show(ui, |ui| {
+                    scale_change = self.scale_mode_picker(ui);
+                });
+            });
+        if let Some(change) = scale_change {
+            self.apply_scale_change(change);
+        }
+    }
+
+    /// Render the legend that replaces the colorbar for qualitative colormaps:
+    /// a swatch for each distinct label of the image
+    fn render_legend(&self, ctx: &egui::Context, widget_rect: egui::Rect) {
+        let colormap = self.colormap();
+        let bad = self.special_colors[SpecialColor::Bad as usize];
+        let swatch_size = egui::vec2(16.0, 12.0);
+        let swatch_stroke = egui::Stroke::new(1.0, Color32::GRAY);
+        egui::Area::new(egui::Id::new("colorbar_legend"))
+            .fixed_pos(widget_rect.min + egui::vec2(COLORBAR_MARGIN, COLORBAR_MARGIN))
+            .order(egui::Order::Middle)
+            .show(ctx, |ui| {
+                let text_color = get_overlay_text_color(ui);
+                overlay_frame(ui).show(ui, |ui| {
+                    let row = |ui: &mut Ui, color: Color32, text: String| {
+                        ui.horizontal(|ui| {
+                            let (rect, _) = ui.allocate_exact_size(swatch_size, egui::Sense::hover());
+                            ui.painter().rect(rect, 0.0, color, swatch_stroke, egui::StrokeKind::Inside);
+                            ui.label(egui::RichText::new(text).color(text_color).monospace());
+                        });
+                    };
+                    for &label in self.legend_labels.iter().take(MAX_LEGEND_ENTRIES) {
+                        row(ui, colormap.label_color(label), label.to_string());
+                    }
+                    if bad.enabled {
+                        row(ui, bad.color, "NaN".to_string());
+                    }
+                    if self.legend_labels.len() > MAX_LEGEND_ENTRIES {
+                        ui.label(egui::RichText::new("…").color(text_color))
+                            .on_hover_text(format!("Showing the {} smallest labels", MAX_LEGEND_ENTRIES));
+                    }
+                });
+            });
+    }
+
+    /// Scale mode picker with presets for common percentiles, plus an editor
+    /// for the percentile or MAD factor when that mode is selected. Returns
+    /// the new (mode, percentile, MAD factor) if the user changed any of them.
+    fn scale_mode_picker(&self, ui: &mut Ui) -> Option<(ScaleMode, f64, f64)> {
+        let text_color = get_overlay_text_color(ui);
+        let (mode, percentile, mad_factor) = (self.scale_mode, self.limits_percentile, self.mad_factor);
+        let (mut new_mode, mut new_percentile, mut new_k) = (mode, percentile, mad_factor);
+        let is_preset = limits::PERCENTILE_PRESETS.contains(&percentile);
+        let tracking = self.limits_policy == LimitsPolicy::Percentile;
+
+        ui.add_enabled_ui(!tracking, |ui| {
+            ui.horizontal(|ui| {
+                let selected = match mode {
+                    ScaleMode::Percentile => format!("{}%", percentile),
+                    _ => mode.label().to_string(),
+                };
+                egui::ComboBox::from_id_salt("scale_mode")
+                    .selected_text(egui::RichText::new(selected).color(text_color))
+                    .show_ui(ui, |ui| {
+                        for mode_option in ScaleMode::all().iter().copied() {
+                            if mode_option == ScaleMode::Percentile {
+                                for preset in limits::PERCENTILE_PRESETS {
+                                    let is_selected = mode == ScaleMode::Percentile && percentile == preset;
+                                    if ui.selectable_label(is_selected, format!("{}%", preset)).clicked() {
+                                        (new_mode, new_percentile) = (ScaleMode::Percentile, preset);
+                                    }
+                                }
+                                let is_custom = mode == ScaleMode::Percentile && !is_preset;
+                                if ui.selectable_label(is_custom, "custom %").clicked() {
+                                    new_mode = ScaleMode::Percentile;
+                                }
+                            } else if ui.selectable_label(mode == mode_option, mode_option.label()).clicked() {
+                                new_mode = mode_option;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("How automatic limits are computed");
+
+                match new_mode {
+                    ScaleMode::Percentile => {
+                        ui.add(egui::DragValue::new(&mut new_percentile).range(50.0..=100.0).speed(0.05).suffix("%"))
+                            .on_hover_text("Central percentile of the data spanned by the limits");
+                    }
+                    ScaleMode::Mad => {
+                        ui.add(egui::DragValue::new(&mut new_k).range(0.5..=20.0).speed(0.05).prefix("k = "))
+                            .on_hover_text("Limits are the median ± k·MAD");
+                    }
+                    _ => {}
+                }
+            });
+        })
+        .response
+        .on_disabled_hover_text("Percentile tracking is on");
+
+        let changed = new_mode != mode || new_percentile != percentile || new_k != mad_factor;
+        changed.then_some((new_mode, new_percentile, new_k))
+    }
+
+    /// Apply a (mode, percentile, MAD factor) change from the scale mode picker
+    fn apply_scale_change(&mut self, (mode, percentile, mad_factor): (ScaleMode, f64, f64)) {
+        self.limits_percentile = percentile;
+        self.mad_factor = mad_factor;
+        self.scale_mode = mode;
+        self.reset_auto_limits();
+    }
+
+    /// Render per-channel limit editors for RGB composites (in place of the colorbar)
+    fn render_composite_limits(&mut self, ctx: &egui::Context, widget_rect: egui::Rect) {
+        let text_input_width = 70.0_f32;
+        let mut reset_clicked = false;
+        let is_modified = self.is_display_modified();
+
+        let limits_rect = egui::Area::new(egui::Id::new("composite_limits"))
+            .fixed_pos(widget_rect.min + egui::vec2(COLORBAR_MARGIN, COLORBAR_MARGIN))
+            .order(egui::Order::Middle)
+            .show(ctx, |ui| {
+                let text_color = get_overlay_text_color(ui);
+                let Some(channels) = self.composite.as_mut() else {
+                    return;
+                };
+                let mut changed = false;
+
+                overlay_frame(ui).show(ui, |ui| {
+                    egui::Grid::new("composite_limits_grid").spacing(egui::vec2(4.0, 4.0)).show(ui, |ui| {
+                        for (channel, (name, color)) in channels.iter_mut().zip(COMPOSITE_CHANNELS) {
+                            ui.label(egui::RichText::new(name).color(color).strong());
+                            changed |= limit_text_edit(
+                                ui,
+                                &mut channel.min_limit_input_text,
+                                &mut channel.min_val,
+                                channel.is_integer,
+                                text_input_width,
+                                text_color,
+                            )
+                            .on_hover_text("Minimum display value")
+                            .changed();
+                            changed |= limit_text_edit(
+                                ui,
+                                &mut channel.max_limit_input_text,
+                                &mut channel.max_val,
+                                channel.is_integer,
+                                text_input_width,
+                                text_color,
+                            )
+                            .on_hover_text("Maximum display value")
+                            .changed();
+                            ui.end_row();
+                        }
+                    });
+
+                    let btn_icon = egui::RichText::new(phosphor::ARROW_COUNTER_CLOCKWISE)
+                        .color(if is_modified { text_color } else { text_color.gamma_multiply(0.4) })
+                        .size(12.0);
+                    let response = ui.add_enabled(is_modified, egui::Button::new(btn_icon));
+                    reset_clicked = response.on_hover_text("Reset contrast/bias and limits").clicked();
+                });
+
+                if changed {
+                    self.texture_dirty = true;
+                }
+            })
+            .response
+            .rect;
+
+        // Scale mode picker below the limit editors
+        let mut scale_change = None;
+        let picker_pos = limits_rect.left_bottom() + egui::vec2(0.0, 4.0);
+        egui::Area::new(egui::Id::new("composite_scale_mode"))
+            .fixed_pos(picker_pos)
+            .order(egui::Order::Middle)
+            .show(ctx, |ui| {
+                overlay_frame(ui).show(ui, |ui| {
+                    scale_change = self.scale_mode_picker(ui);
+                });
+            });
+
+        if reset_clicked {
+            self.reset_display();
+        }
+        if let Some(change) = scale_change {
+            self.apply_scale_change(change);
+        }
     }
 
     /// Render contrast/bias values while adjusting
@@ -1534,10 +3437,7 @@ impl ArrayViewerWidget {
                     .fill(bg)
                     .show(ui, |ui| {
                         ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
-                        let mode_str = match stretch_type {
-                            StretchType::Linear => "Linear",
-                            StretchType::Log => "Log",
-                        };
+                        let mode_str = stretch_type.title();
                         ui.label(
                             egui::RichText::new(format!(
                                 "{} | Contrast: {:.2} | Bias: {:.2}",
@@ -1642,32 +3542,82 @@ impl ArrayViewerWidget {
 
     /// Render hover info overlay at bottom-left of widget
     fn render_hover_overlay(&self, ctx: &egui::Context, widget_rect: egui::Rect) {
-        if let Some((x, y, value)) = self.hover_info() {
-            let is_int = self.is_integer();
-
+        if let Some(&(x, y, ref values)) = self.hover_info() {
             egui::Area::new(egui::Id::new("hover_overlay"))
                 .fixed_pos(egui::pos2(widget_rect.min.x + 10.0, widget_rect.max.y - 30.0))
                 .show(ctx, |ui| {
                     egui::Frame::popup(ui.style()).show(ui, |ui| {
                         ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
-                        if is_int {
-                            ui.label(format!("Pixel ({}, {}): {}", x, y, value as i64));
-                        } else {
-                            ui.label(format!("Pixel ({}, {}): {:.6}", x, y, value));
+                        // Values are shown exactly as stored (integers without decimals)
+                        let value = match self.channel_labels() {
+                            [] => values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", "),
+                            labels => labels
+                                .iter()
+                                .zip(values)
+                                .map(|(label, v)| format!("{} {}", label, v))
+                                .collect::<Vec<_>>()
+                                .join("  "),
+                        };
+                        let mut text = match self.cube_index_at(x, y) {
+                            // Full N-D index, with axis names when the host supplied them
+                            Some(index) => match &self.cube {
+                                Some(cube) if cube.has_labels() => {
+                                    let coords: Vec<String> = index
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(axis, i)| format!("{}={}", cube.label(axis), i))
+                                        .collect();
+                                    format!("{}: {}", coords.join(" "), value)
+                                }
+                                _ => format!("Pixel {:?}: {}", index, value),
+                            },
+                            None => format!("Pixel ({}, {}): {}", x, y, value),
+                        };
+                        if let Some(mask) = self.mask_value(x, y) {
+                            text.push_str(&format!("  mask: {}", mask));
                         }
+                        ui.label(text);
                     });
                 });
         }
     }
 }
 
-/// Apply stretch function to a normalized value (0-1)
-fn apply_stretch(x: f64, stretch_type: StretchType) -> f64 {
+/// Keep `cdf` in step with its stretch: dropped when `active` is false,
+/// rebuilt when missing or built for limits other than `range`
+fn refresh_cdf(
+    cdf: &mut Option<HistogramCdf>,
+    active: bool,
+    pixels: &PixelData,
+    component: ComplexComponent,
+    exclude: Option<&[u8]>,
+    range: (f64, f64),
+) {
+    if !active {
+        *cdf = None;
+    } else if cdf.as_ref().is_none_or(|cdf| cdf.range() != range) {
+        *cdf = Some(HistogramCdf::new(pixels, component, exclude, range));
+    }
+}
+
+/// Apply stretch function to a normalized value (0-1); histogram
+/// equalization uses `cdf` (linear without one)
+fn apply_stretch(x: f64, stretch_type: StretchType, params: &StretchParams, cdf: Option<&HistogramCdf>) -> f64 {
     match stretch_type {
         StretchType::Linear => x,
         StretchType::Log => {
-            (LOG_EXPONENT * x + 1.0).log10() / LOG_EXPONENT.log10()
+            let a = params.log_exponent;
+            (a * x + 1.0).log10() / (a + 1.0).log10()
         }
+        StretchType::Sqrt => x.sqrt(),
+        StretchType::Squared => x * x,
+        StretchType::Asinh => {
+            let a = params.asinh_softening;
+            (x / a).asinh() / (1.0 / a).asinh()
+        }
+        StretchType::Power => x.powf(params.power_exponent),
+        StretchType::Sinh => (x / SINH_SCALE).sinh() / (1.0 / SINH_SCALE).sinh(),
+        StretchType::HistEq => cdf.map_or(x, |cdf| cdf.apply(x)),
     }
 }
 
@@ -1676,6 +3626,63 @@ fn apply_contrast_bias(x: f64, contrast: f64, bias: f64) -> f64 {
     ((x - bias) * contrast + 0.5).clamp(0.0, 1.0)
 }
 
+/// Contrast/bias for cyclic colormaps: the result wraps around instead of clamping
+fn wrap_contrast_bias(x: f64, contrast: f64, bias: f64) -> f64 {
+    ((x - bias) * contrast + 0.5).rem_euclid(1.0)
+}
+
+/// Auto-scaling limits from a finite data range, falling back to 0-1 when
+/// there is no finite data and widening a degenerate (constant) range
+fn auto_limits(range: Option<(f64, f64)>) -> (f64, f64) {
+    let (min_val, mut max_val) = range.unwrap_or((0.0, 1.0));
+    if (max_val - min_val).abs() < f64::EPSILON {
+        max_val = min_val + 1.0;
+    }
+    (min_val, max_val)
+}
+
+/// Single-line editor for a display limit. The value is applied on enter or
+/// defocus; invalid input restores the text. The returned response is marked
+/// changed only when `value` was updated.
+fn limit_text_edit(
+    ui: &mut Ui,
+    text: &mut String,
+    value: &mut f64,
+    is_integer: bool,
+    width: f32,
+    text_color: Color32,
+) -> Response {
+    let edit = egui::TextEdit::singleline(text)
+        .desired_width(width)
+        .horizontal_align(egui::Align::Center)
+        .text_color(text_color)
+        .font(egui::FontId::proportional(13.0));
+    let mut response = ui.add(edit);
+    // Typing alone does not count as a change
+    response.flags.remove(egui::response::Flags::CHANGED);
+
+    if response.lost_focus() || (response.has_focus() && ui.input(|i| i.key_pressed(Key::Enter))) {
+        match text.trim().parse::<f64>() {
+            Ok(new_val) if (*value - new_val).abs() > 1e-15 => {
+                *value = new_val;
+                response.mark_changed();
+            }
+            Ok(_) => {}
+            Err(_) => *text = format_limit(*value, is_integer),
+        }
+    }
+    response
+}
+
+/// Format a limit value for its text input (integers without decimals)
+fn format_limit(v: f64, is_integer: bool) -> String {
+    if is_integer {
+        format!("{}", v as i64)
+    } else {
+        format_scientific(v)
+    }
+}
+
 /// Format a float in scientific notation for compact display
 fn format_scientific(v: f64) -> String {
     if v == 0.0 {
@@ -1718,3 +3725,208 @@ fn overlay_frame(ui: &Ui) -> egui::Frame {
 fn format_zoom_multiple(zoom: f32) -> String {
     format!("{:.3}x", zoom)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stretch_endpoints() {
+        // Every stretch maps 0 to 0 and 1 to 1, across the parameter ranges offered in the UI
+        let params = [
+            StretchParams::default(),
+            StretchParams { log_exponent: 1.01, asinh_softening: 0.001, power_exponent: 0.05 },
+            StretchParams { log_exponent: 2.0, asinh_softening: 1.0, power_exponent: 1.0 },
+            StretchParams { log_exponent: 1e6, asinh_softening: 10.0, power_exponent: 10.0 },
+        ];
+        for params in &params {
+            for &stretch in StretchType::all() {
+                let (lo, hi) = (apply_stretch(0.0, stretch, params, None), apply_stretch(1.0, stretch, params, None));
+                assert!(lo.abs() < 1e-12, "{:?} {:?}: f(0) = {}", stretch, params, lo);
+                assert!((hi - 1.0).abs() < 1e-12, "{:?} {:?}: f(1) = {}", stretch, params, hi);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stretch_monotonic() {
+        let params = [
+            StretchParams::default(),
+            StretchParams { log_exponent: 1.01, asinh_softening: 0.001, power_exponent: 0.05 },
+            StretchParams { log_exponent: 1e6, asinh_softening: 10.0, power_exponent: 10.0 },
+        ];
+        for params in &params {
+            for &stretch in StretchType::all() {
+                let values: Vec<f64> =
+                    (0..=100).map(|i| apply_stretch(i as f64 / 100.0, stretch, params, None)).collect();
+                for pair in values.windows(2) {
+                    assert!(pair[0] <= pair[1], "{:?} {:?} decreases: {:?}", stretch, params, pair);
+                    assert!((0.0..=1.0 + 1e-12).contains(&pair[1]), "{:?} {:?}: {}", stretch, params, pair[1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stretch_params() {
+        let stretch = |x, stretch, params| apply_stretch(x, stretch, &params, None);
+        let with = |asinh_softening, power_exponent| StretchParams { asinh_softening, power_exponent, ..Default::default() };
+
+        // Smaller softening brightens faint pixels more; large softening is near linear
+        let faint = |softening| stretch(0.1, StretchType::Asinh, with(softening, 1.0));
+        assert!(faint(0.01) > faint(1.0));
+        assert!((stretch(0.5, StretchType::Asinh, with(10.0, 1.0)) - 0.5).abs() < 0.01);
+
+        assert!((stretch(0.25, StretchType::Power, with(0.1, 0.5)) - 0.5).abs() < 1e-12);
+        assert!((stretch(0.25, StretchType::Power, with(0.1, 2.0)) - 0.0625).abs() < 1e-12);
+        assert!((stretch(0.3, StretchType::Power, with(0.1, 1.0)) - 0.3).abs() < 1e-12);
+
+        // Sinh darkens everything but the top of the range
+        let expected = (0.5 / SINH_SCALE).sinh() / (1.0 / SINH_SCALE).sinh();
+        assert!((stretch(0.5, StretchType::Sinh, StretchParams::default()) - expected).abs() < 1e-12);
+        assert!(expected < 0.5);
+    }
+
+    #[test]
+    fn test_stretch_param_setters() {
+        let mut widget = ArrayViewerWidget::new();
+        for invalid in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            widget.set_asinh_softening(invalid);
+            widget.set_power_exponent(invalid);
+        }
+        assert_eq!(widget.stretch_params(), StretchParams::default());
+
+        widget.set_asinh_softening(0.5);
+        widget.set_power_exponent(2.0);
+        assert_eq!(widget.stretch_params().asinh_softening, 0.5);
+        assert_eq!(widget.stretch_params().power_exponent, 2.0);
+    }
+
+    #[test]
+    fn test_log_exponent() {
+        let faint = |log_exponent| {
+            let params = StretchParams { log_exponent, ..Default::default() };
+            apply_stretch(0.1, StretchType::Log, &params, None)
+        };
+        // Larger exponents brighten faint pixels more
+        assert!(faint(1e4) > faint(DEFAULT_LOG_EXPONENT));
+        assert!(faint(DEFAULT_LOG_EXPONENT) > faint(10.0));
+
+        let mut widget = ArrayViewerWidget::new();
+        for invalid in [1.0, 0.5, -10.0, f64::NAN, f64::INFINITY] {
+            widget.set_log_exponent(invalid);
+        }
+        assert_eq!(widget.stretch_params().log_exponent, DEFAULT_LOG_EXPONENT);
+        widget.set_log_exponent(100.0);
+        assert_eq!(widget.stretch_params().log_exponent, 100.0);
+    }
+
+    #[test]
+    fn test_special_color_of() {
+        let mut widget = ArrayViewerWidget::new();
+        let colors = [Color32::from_rgb(1, 2, 3), Color32::from_rgb(4, 5, 6), Color32::from_rgb(7, 8, 9)];
+        let kinds = [SpecialColor::Bad, SpecialColor::Under, SpecialColor::Over];
+
+        // Disabled by default
+        for v in [f64::NAN, -1.0, 2.0] {
+            assert_eq!(widget.special_color_of(v, 0.0, 1.0), None);
+        }
+
+        for (kind, color) in kinds.into_iter().zip(colors) {
+            widget.set_special_color(kind, color);
+            widget.set_special_color_enabled(kind, true);
+        }
+        for v in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(widget.special_color_of(v, 0.0, 1.0), Some(colors[0]));
+        }
+        assert_eq!(widget.special_color_of(-0.5, 0.0, 1.0), Some(colors[1]));
+        assert_eq!(widget.special_color_of(1.5, 0.0, 1.0), Some(colors[2]));
+        // The limits themselves are in range
+        for v in [0.0, 0.5, 1.0] {
+            assert_eq!(widget.special_color_of(v, 0.0, 1.0), None);
+        }
+
+        widget.set_special_color_enabled(SpecialColor::Under, false);
+        assert_eq!(widget.special_color_of(-0.5, 0.0, 1.0), None);
+        assert_eq!(widget.special_color_of(1.5, 0.0, 1.0), Some(colors[2]));
+    }
+
+    #[test]
+    fn test_auto_limits() {
+        assert_eq!(auto_limits(Some((-2.0, 5.0))), (-2.0, 5.0));
+        // No finite data, or constant data
+        assert_eq!(auto_limits(None), (0.0, 1.0));
+        assert_eq!(auto_limits(Some((3.0, 3.0))), (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_contrast_bias() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        // Default contrast and bias leave values alone
+        for x in [0.0, 0.3, 0.99] {
+            assert!(close(apply_contrast_bias(x, DEFAULT_CONTRAST, DEFAULT_BIAS), x));
+            assert!(close(wrap_contrast_bias(x, DEFAULT_CONTRAST, DEFAULT_BIAS), x));
+        }
+        // Out of range results clamp for most colormaps, but wrap for cyclic ones
+        assert!(close(apply_contrast_bias(0.8, 2.0, 0.5), 1.0));
+        assert!(close(wrap_contrast_bias(0.8, 2.0, 0.5), 0.1));
+        assert!(close(apply_contrast_bias(0.2, 1.0, 0.8), 0.0));
+        assert!(close(wrap_contrast_bias(0.2, 1.0, 0.8), 0.9));
+    }
+
+    #[test]
+    fn test_composite_limits_policy() {
+        let channels = |scale: f64| [1.0, 2.0, 3.0].map(|c| PixelData::F64(vec![0.0, c * scale]));
+        let mut widget = ArrayViewerWidget::new();
+        widget.set_limits_policy(LimitsPolicy::KeepIfModified);
+        widget.set_composite_image(channels(1.0), 2, 1);
+        widget.set_active_channel(1);
+        widget.set_value_range(0.5, 1.5);
+
+        // Only the modified channel keeps its limits
+        widget.set_composite_image(channels(10.0), 2, 1);
+        assert_eq!(widget.value_range(), (0.5, 1.5));
+        widget.set_active_channel(0);
+        assert_eq!(widget.value_range(), (0.0, 10.0));
+
+        widget.set_limits_policy(LimitsPolicy::Auto);
+        widget.set_active_channel(1);
+        widget.set_composite_image(channels(1.0), 2, 1);
+        assert_eq!(widget.value_range(), (0.0, 2.0));
+    }
+
+    #[test]
+    fn test_colormap_modes() {
+        let mut widget = ArrayViewerWidget::new();
+        widget.set_image(PixelData::F32(vec![-3.0, 0.0, 1.0, 3.0]), 2, 2);
+
+        // Cyclic colormaps work for real-valued angles too
+        widget.set_colormap(Colormap::Twilight).unwrap();
+        assert_eq!(widget.colormap(), Colormap::Twilight);
+
+        // Each mode remembers its own colormap
+        widget.set_symmetric(true);
+        assert_eq!(widget.colormap(), Colormap::RdBu);
+        widget.set_colormap(Colormap::Hsv).unwrap();
+        assert_eq!(widget.colormap(), Colormap::Hsv);
+        widget.set_symmetric(false);
+        assert_eq!(widget.colormap(), Colormap::Twilight);
+    }
+
+    #[test]
+    fn test_set_colormap_errors() {
+        let mut widget = ArrayViewerWidget::new();
+        widget.set_image(PixelData::C64(vec![[1.0, 0.0], [0.0, 1.0]]), 2, 1);
+        widget.set_complex_component(ComplexComponent::Phase);
+        assert_eq!(widget.colormap(), Colormap::Hsv);
+        widget.set_colormap(Colormap::Viridis).unwrap();
+        assert_eq!(widget.colormap(), Colormap::Viridis);
+        // Set fails when the colormap would not be drawn, and changes nothing
+        assert!(widget.set_colormap(Colormap::Tab10).is_err());
+        assert_eq!(widget.colormap(), Colormap::Viridis);
+
+        let channels = [1, 2, 3].map(|v| PixelData::U8(vec![v; 2]));
+        widget.set_composite_image(channels, 2, 1);
+        assert!(widget.set_colormap(Colormap::Twilight).is_err());
+    }
+}